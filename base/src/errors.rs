@@ -55,4 +55,6 @@ pub enum WalletBaseError<'wa> {
     InvalidISO8601Timestamp(Cow<'wa, str>),
     #[error("The message that was sent to be signed did not match the message that was received")]
     MessageResponseMismatch,
+    #[error("The chain `{0}` is not a supported cluster")]
+    UnsupportedChain(Cow<'wa, str>),
 }
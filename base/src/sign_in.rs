@@ -1,10 +1,11 @@
-use core::{fmt::Debug, hash::Hash};
+use core::{fmt::Debug, hash::Hash, str::FromStr};
 use std::{
     borrow::Cow,
     time::{Duration, SystemTime},
 };
 
 use sha3::{Digest, Sha3_256};
+use zeroize::Zeroizing;
 
 use crate::{BaseUtils, Cluster, RandomBytes, WalletAccount, WalletBaseError, WalletBaseResult};
 
@@ -144,10 +145,13 @@ impl<'wa> SignInInput<'_> {
     /// An EIP-4361 Nonce which is an alphanumeric string containing a minimum of 8 characters.
     /// This is generated from the Cryptographically Secure Random Number Generator
     /// and the bytes converted to hex formatted string.
+    /// The random bytes are held in a [Zeroizing] buffer so they are cleared
+    /// when dropped, even while unwinding, leaving only the hex nonce.
     pub fn set_nonce(&mut self) -> &mut Self {
-        let random_bytes = RandomBytes::<32>::generate();
+        let mut random_bytes = Zeroizing::new([0u8; 32]);
+        RandomBytes::<32>::generate_with_buffer(&mut random_bytes);
 
-        let hash = Sha3_256::digest(random_bytes.expose());
+        let hash = Sha3_256::digest(random_bytes.as_slice());
         self.nonce.replace(Cow::Owned(
             hash.iter().map(|b| format!("{:02x}", b)).collect(),
         ));
@@ -393,10 +397,14 @@ impl<'wa> SignInInput<'_> {
                 .transpose()
         };
 
+        let mut in_resources = false;
+
         input
             .split("\n")
             .enumerate()
             .try_for_each(|(index, input)| {
+                // Field labels only match at the start of a line so that a statement
+                // or resource mentioning a label does not overwrite that field
                 if index == 1 {
                     signin_input.address.replace(input.trim().into());
                 }
@@ -405,43 +413,45 @@ impl<'wa> SignInInput<'_> {
                     signin_input.statement.replace(input.trim().into());
                 }
 
-                if input.contains("URI") {
+                if input.trim_start().starts_with("URI:") {
                     signin_input.uri = split_colon(input);
                 }
 
-                if input.contains("Version") {
+                if input.trim_start().starts_with("Version:") {
                     signin_input.version = split_colon(input);
                 }
 
-                if input.contains("Chain ID") {
+                if input.trim_start().starts_with("Chain ID:") {
                     if let Some((_left, right)) = input.split_once(":") {
                         let cluster = right.trim().into();
 
                         signin_input.chain_id.replace(cluster);
                     }
                 }
-                if input.contains("Nonce") {
+                if input.trim_start().starts_with("Nonce:") {
                     signin_input.nonce = split_colon(input);
                 }
 
-                if input.contains("Issued At") {
+                if input.trim_start().starts_with("Issued At:") {
                     signin_input.issued_at = split_colon_system_time(input)?;
                 }
 
-                if input.contains("Expiration") {
+                if input.trim_start().starts_with("Expiration Time:") {
                     signin_input.expiration_time = split_colon_system_time(input)?;
                 }
 
-                if input.contains("Not Before") {
+                if input.trim_start().starts_with("Not Before:") {
                     signin_input.not_before = split_colon_system_time(input)?;
                 }
 
-                if input.contains("Request ID") {
+                if input.trim_start().starts_with("Request ID:") {
                     signin_input.request_id = split_colon(input);
                 }
 
-                if input.starts_with("-") {
-                    if let Some(value) = input.split("-").nth(1) {
+                // Resources are the `- ` prefixed lines following the `Resources:` line,
+                // the URIs themselves may contain `-`
+                if in_resources {
+                    if let Some(value) = input.strip_prefix("- ") {
                         signin_input
                             .resources
                             .to_mut()
@@ -449,6 +459,10 @@ impl<'wa> SignInInput<'_> {
                     }
                 }
 
+                if input.trim() == "Resources:" {
+                    in_resources = true;
+                }
+
                 Ok::<(), WalletBaseError>(())
             })?;
 
@@ -530,6 +544,29 @@ impl<'wa> SignInInput<'_> {
         self.chain_id.as_deref()
     }
 
+    /// Get the `chain_id` field parsed into a typed [Cluster] using its `From<&str>` conversion.
+    /// Unrecognized chains resolve to whatever fallback that conversion chooses.
+    pub fn chain_id_cluster<C>(&self) -> Option<C>
+    where
+        C: Cluster + for<'a> From<&'a str>,
+    {
+        self.chain_id.as_deref().map(C::from)
+    }
+
+    /// Get the `chain_id` field parsed into a typed [Cluster] using its strict [FromStr] conversion.
+    /// Returns [WalletBaseError::UnsupportedChain] if the chain is not recognized.
+    pub fn chain_id_cluster_checked<C>(&self) -> WalletBaseResult<'_, Option<C>>
+    where
+        C: Cluster + FromStr,
+    {
+        self.chain_id
+            .as_deref()
+            .map(|chain| {
+                C::from_str(chain).or(Err(WalletBaseError::UnsupportedChain(Cow::Borrowed(chain))))
+            })
+            .transpose()
+    }
+
     /// Get the `nonce` field
     pub fn nonce(&self) -> Option<&str> {
         self.nonce.as_deref()
@@ -680,3 +717,192 @@ mod signin_input_sanity_checks {
         assert!(signin_input.set_not_before_time_seconds(4).is_ok());
     }
 }
+
+#[cfg(test)]
+mod chain_id_cluster_checks {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+    enum TestCluster {
+        MainNet,
+        DevNet,
+    }
+
+    impl Cluster for TestCluster {
+        fn network_namespace(&self) -> &str {
+            "solana"
+        }
+
+        fn chain(&self) -> &str {
+            match self {
+                Self::MainNet => "solana:mainnet",
+                Self::DevNet => "solana:devnet",
+            }
+        }
+
+        fn identifier(&self) -> &str {
+            match self {
+                Self::MainNet => "mainnet",
+                Self::DevNet => "devnet",
+            }
+        }
+
+        fn endpoint(&self) -> &str {
+            match self {
+                Self::MainNet => "https://api.mainnet-beta.solana.com",
+                Self::DevNet => "https://api.devnet.solana.com",
+            }
+        }
+
+        fn chains(&self) -> [&str; 4] {
+            [
+                "solana:mainnet",
+                "solana:devnet",
+                "solana:testnet",
+                "solana:localnet",
+            ]
+        }
+    }
+
+    impl From<&str> for TestCluster {
+        fn from(value: &str) -> Self {
+            value.parse().unwrap_or(Self::DevNet)
+        }
+    }
+
+    impl FromStr for TestCluster {
+        type Err = ();
+
+        fn from_str(value: &str) -> Result<Self, Self::Err> {
+            match value {
+                "solana:mainnet" => Ok(Self::MainNet),
+                "solana:devnet" => Ok(Self::DevNet),
+                _ => Err(()),
+            }
+        }
+    }
+
+    #[test]
+    fn chain_id_cluster() {
+        let mut signin_input = SignInInput::default();
+        assert!(signin_input.chain_id_cluster::<TestCluster>().is_none());
+        assert_eq!(
+            Ok(None),
+            signin_input.chain_id_cluster_checked::<TestCluster>()
+        );
+
+        signin_input.set_chain_id(TestCluster::MainNet);
+        assert_eq!(
+            Some(TestCluster::MainNet),
+            signin_input.chain_id_cluster::<TestCluster>()
+        );
+        assert_eq!(
+            Ok(Some(TestCluster::MainNet)),
+            signin_input.chain_id_cluster_checked::<TestCluster>()
+        );
+
+        signin_input.chain_id = Some(Cow::Borrowed("solana:foonet"));
+        assert_eq!(
+            Some(TestCluster::DevNet),
+            signin_input.chain_id_cluster::<TestCluster>()
+        );
+        assert_eq!(
+            Err(WalletBaseError::UnsupportedChain(Cow::Borrowed(
+                "solana:foonet"
+            ))),
+            signin_input.chain_id_cluster_checked::<TestCluster>()
+        );
+    }
+}
+
+#[cfg(test)]
+mod statement_label_checks {
+    use super::*;
+
+    #[test]
+    fn statement_mentioning_labels() {
+        let message = "example.com wants you to sign in with your Solana account:\n\
+            11111111111111111111111111111111\n\
+            \n\
+            Visit our URI policy: Nonce rules and Version: 2 apply\n\
+            \n\
+            URI: https://example.com/login\n\
+            Version: 1\n\
+            Nonce: 0123456789abcdef";
+
+        let parsed = SignInInput::parser(message).unwrap();
+        assert_eq!(
+            parsed.statement(),
+            Some("Visit our URI policy: Nonce rules and Version: 2 apply")
+        );
+        assert_eq!(parsed.uri(), Some("https://example.com/login"));
+        assert_eq!(parsed.version(), Some("1"));
+        assert_eq!(parsed.nonce(), Some("0123456789abcdef"));
+    }
+}
+
+#[cfg(test)]
+mod resources_format_checks {
+    use super::*;
+
+    #[test]
+    fn resources_after_header() {
+        let message = "example.com wants you to sign in with your Solana account:\n\
+            11111111111111111111111111111111\n\
+            \n\
+            - not a resource\n\
+            \n\
+            URI: https://example.com/login\n\
+            Resources:\n\
+            - https://my-site.example/terms\n\
+            - ipfs://bafy-foo";
+
+        let parsed = SignInInput::parser(message).unwrap();
+        assert_eq!(parsed.statement(), Some("- not a resource"));
+        assert_eq!(
+            parsed.resources(),
+            &["https://my-site.example/terms", "ipfs://bafy-foo"]
+        );
+    }
+}
+
+#[cfg(test)]
+mod nonce_checks {
+    use super::*;
+
+    #[test]
+    fn hex_nonce() {
+        let mut signin_input = SignInInput::default();
+        signin_input.set_nonce();
+
+        let nonce = signin_input.nonce().unwrap().to_string();
+        assert_eq!(nonce.len(), 64);
+        assert!(nonce
+            .chars()
+            .all(|char| char.is_ascii_digit() || ('a'..='f').contains(&char)));
+
+        signin_input.set_nonce();
+        assert_ne!(signin_input.nonce().unwrap(), nonce);
+    }
+}
+
+#[cfg(test)]
+mod address_checks {
+    use super::*;
+
+    #[test]
+    fn too_short_address() {
+        let mut signin_input = SignInInput::default();
+
+        // `2g` decodes to a single byte
+        assert_eq!(
+            Some(WalletBaseError::InvalidEd25519PublicKeyLen(1)),
+            signin_input.set_address("2g").err()
+        );
+        assert!(signin_input.address().is_none());
+
+        assert!(signin_input
+            .set_address("4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi")
+            .is_ok());
+    }
+}
@@ -1,15 +1,38 @@
-use std::{borrow::Borrow, sync::Arc};
+use std::{
+    borrow::Borrow,
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
-use async_channel::{bounded, Receiver};
+use async_channel::{bounded, Receiver, Sender};
 use async_lock::RwLock;
 use ed25519_dalek::Signature;
-use wallet_adapter_common::{clusters::Cluster, signin_standard::SignInOutput};
-use web_sys::{js_sys::Object, Document, Window};
+use wallet_adapter_common::{
+    clusters::Cluster,
+    feature_support::WalletFeature,
+    signin_standard::{SignInOutput, SiwsMessage},
+    WalletCommonUtils,
+};
+use web_sys::{
+    js_sys::{Array, Object, JSON},
+    wasm_bindgen::JsValue,
+    Document, Window,
+};
 
 use crate::{
-    events::InitEvents, send_wallet_event, SendOptions, SignedMessageOutput, SigninInput, Wallet,
-    WalletAccount, WalletError, WalletEvent, WalletEventReceiver, WalletEventSender, WalletResult,
-    WalletStorage,
+    encoding::to_base64, events::InitEvents, known_wallets, offchain_message,
+    open_account_subscription, rpc::RedactedHeaders, send_wallet_event, transaction, AccountUpdate,
+    Clock, Commitment, InnerUtils, JsDateClock, KnownWallet, Lamports, LogLevel, Logger, LoggerFn,
+    PortableAccount, Reflection, ResponseInspector, RpcClient, SendOptions, SignedMessageOutput,
+    SigninInput, SimulationResult, Wallet, WalletAccount, WalletError, WalletEvent,
+    WalletEventEnvelope, WalletEventEnvelopeReceiver, WalletEventKind, WalletEventReceiver,
+    WalletEventSender, WalletName, WalletReadyState, WalletResult, WalletStorage,
 };
 
 /// Contains the connected wallet and account.
@@ -21,6 +44,9 @@ pub struct ConnectionInfo {
     wallet: Option<Wallet>,
     account: Option<WalletAccount>,
     previous_accounts: Vec<WalletAccount>,
+    authorized_accounts: Vec<[u8; 32]>,
+    last_event: Option<WalletEvent>,
+    logger: Logger,
 }
 
 impl ConnectionInfo {
@@ -45,28 +71,83 @@ impl ConnectionInfo {
 
     /// Send a connect request to the browser wallet
     pub async fn connect(&mut self, sender: WalletEventSender) -> WalletResult<WalletAccount> {
+        self.connect_with_options(sender, false).await
+    }
+
+    /// Send a connect request to the browser wallet with the `silent` option,
+    /// see [Wallet::connect_with_options]. The first account the user authorized
+    /// becomes the connected account and all of them are added to the accounts
+    /// of the connected wallet
+    pub async fn connect_with_options(
+        &mut self,
+        sender: WalletEventSender,
+        silent: bool,
+    ) -> WalletResult<WalletAccount> {
         let wallet = self.connected_wallet()?;
 
-        let connected_account = wallet.features.connect.call_connect().await?;
+        let authorized = wallet.connect_authorized(silent).await?;
+        let connected_account = authorized[0].clone();
+
+        self.authorized_accounts = authorized
+            .iter()
+            .map(|account| account.public_key())
+            .collect();
+
+        if let Some(connected_wallet) = self.wallet.as_mut() {
+            for account in authorized {
+                if connected_wallet
+                    .accounts
+                    .iter()
+                    .all(|existing| existing.public_key() != account.public_key())
+                {
+                    connected_wallet.accounts.push(account);
+                }
+            }
+        }
 
         self.set_account(connected_account.clone());
 
-        send_wallet_event(WalletEvent::Connected(connected_account.clone()), sender).await;
+        self.send_wallet_event(WalletEvent::Connected(connected_account.clone()), sender)
+            .await;
 
         Ok(connected_account)
     }
 
     /// Set the disconnected account
     pub async fn set_disconnected(&mut self, sender: WalletEventSender) -> &mut Self {
-        self.wallet.take();
-        self.account.take();
-        self.previous_accounts.clear();
+        self.clear_connection();
 
-        send_wallet_event(WalletEvent::Disconnected, sender).await;
+        self.send_wallet_event(WalletEvent::Disconnected, sender)
+            .await;
 
         self
     }
 
+    /// Compare the connected wallet, the connected account and the previously connected
+    /// accounts by their data, ignoring the JavaScript objects they were parsed from.
+    /// Unlike `==`, a reconnect producing equivalent accounts compares equal,
+    /// which avoids spurious re-renders in reactive frameworks
+    pub fn semantically_eq(&self, other: &Self) -> bool {
+        let accounts_eq = |left: &[WalletAccount], right: &[WalletAccount]| {
+            left.len() == right.len()
+                && left
+                    .iter()
+                    .zip(right)
+                    .all(|(left, right)| left.account == right.account)
+        };
+
+        let wallets_eq = match (&self.wallet, &other.wallet) {
+            (Some(left), Some(right)) => {
+                left.data == right.data && accounts_eq(&left.accounts, &right.accounts)
+            }
+            (left, right) => left.is_none() && right.is_none(),
+        };
+
+        wallets_eq
+            && accounts_eq(self.account.as_slice(), other.account.as_slice())
+            && accounts_eq(&self.previous_accounts, &other.previous_accounts)
+    }
+
     /// Get the connected [wallet](Wallet)
     pub fn connected_wallet(&self) -> WalletResult<&Wallet> {
         self.wallet.as_ref().ok_or(WalletError::WalletNotFound)
@@ -77,6 +158,12 @@ impl ConnectionInfo {
         self.account.as_ref().ok_or(WalletError::AccountNotFound)
     }
 
+    /// Get the accounts of the connected [wallet](Wallet),
+    /// including all the accounts the user authorized when connecting
+    pub fn connected_accounts(&self) -> WalletResult<&[WalletAccount]> {
+        Ok(self.connected_wallet()?.accounts())
+    }
+
     /// Get the connected [wallet](Wallet) but return an [Option]
     /// to show the wallet exists instead of a [WalletResult]
     pub fn connected_wallet_raw(&self) -> Option<&Wallet> {
@@ -89,6 +176,62 @@ impl ConnectionInfo {
         self.account.as_ref()
     }
 
+    /// Get the accounts that were connected before the connected account,
+    /// for example before the user switched accounts in the wallet
+    pub fn previous_accounts(&self) -> &[WalletAccount] {
+        self.previous_accounts.as_slice()
+    }
+
+    /// Get the public keys of the accounts the user authorized on the last connect request,
+    /// on the last [WalletAdapter::request_more_accounts] or in the last `change` event
+    /// of the connected wallet. Unlike [Wallet::accounts],
+    /// accounts the user deauthorized since an earlier connect are not included
+    pub fn authorized_accounts(&self) -> &[[u8; 32]] {
+        self.authorized_accounts.as_slice()
+    }
+
+    /// Replace the authorized accounts with the accounts of a `change` event emitted by
+    /// the wallet with `wallet_name`. Changes of wallets other than the connected wallet
+    /// are ignored
+    pub(crate) fn update_authorized_accounts(
+        &mut self,
+        wallet_name: &str,
+        authorized: Vec<[u8; 32]>,
+    ) -> &mut Self {
+        let is_connected_wallet = self
+            .wallet
+            .as_ref()
+            .is_some_and(|wallet| WalletName::new(wallet.name()) == WalletName::new(wallet_name));
+
+        if is_connected_wallet {
+            self.authorized_accounts = authorized;
+        }
+
+        self
+    }
+
+    /// Clear the previously connected accounts, keeping the connected wallet and account
+    pub fn clear_previous_accounts(&mut self) -> &mut Self {
+        self.previous_accounts.clear();
+
+        self
+    }
+
+    /// Find an account belonging to the connected wallet by its public key.
+    /// The connected account, the previously connected accounts and
+    /// the accounts registered by the wallet are searched
+    pub fn account_by_pubkey(&self, public_key: [u8; 32]) -> Option<&WalletAccount> {
+        self.account
+            .iter()
+            .chain(self.previous_accounts.iter())
+            .chain(
+                self.wallet
+                    .iter()
+                    .flat_map(|wallet| wallet.accounts().iter()),
+            )
+            .find(|wallet_account| wallet_account.public_key() == public_key)
+    }
+
     /// Emit an [event](WalletEvent) after processing the `[standard:events].on` result
     pub async fn emit_wallet_event(
         &mut self,
@@ -96,8 +239,12 @@ impl ConnectionInfo {
         account_processing: Option<WalletAccount>,
         sender: WalletEventSender,
     ) {
+        let wallet_name = WalletName::new(wallet_name);
+
         match self.connected_wallet() {
             Ok(wallet) => {
+                let is_connected_wallet = WalletName::new(wallet.name()) == wallet_name;
+
                 let event_outcome = match account_processing {
                     Some(connected_account) => {
                         if self.account.is_none()
@@ -118,7 +265,7 @@ impl ConnectionInfo {
                             self.set_account(connected_account.clone());
 
                             WalletEvent::Connected(connected_account)
-                        } else if wallet.name().as_bytes() == wallet_name.as_bytes()
+                        } else if is_connected_wallet
                             && self.account.is_none()
                             && self.previous_accounts.iter().any(|wallet_account| {
                                 wallet_account.account.public_key
@@ -129,9 +276,7 @@ impl ConnectionInfo {
                             self.set_account(connected_account.clone());
 
                             WalletEvent::Reconnected(connected_account)
-                        } else if wallet.name().as_bytes() == wallet_name.as_bytes()
-                            && self.account.is_some()
-                        {
+                        } else if is_connected_wallet && self.account.is_some() {
                             self.push_previous_account();
                             self.set_account(connected_account.clone());
 
@@ -140,9 +285,12 @@ impl ConnectionInfo {
                             WalletEvent::Skip
                         }
                     }
+                    // The wallet emitted a change with an empty `accounts` array,
+                    // meaning the user logged out, so the wallet is cleared like
+                    // a disconnect and the dapp has to connect again
                     None => {
-                        if wallet.name().as_bytes() == wallet_name.as_bytes() {
-                            self.push_previous_account();
+                        if is_connected_wallet {
+                            self.clear_connection();
                             WalletEvent::Disconnected
                         } else {
                             WalletEvent::Skip
@@ -150,17 +298,81 @@ impl ConnectionInfo {
                     }
                 };
 
-                send_wallet_event(event_outcome, sender).await
+                self.send_wallet_event(event_outcome, sender).await
             }
             Err(error) => {
-                web_sys::console::log_2(
-                    &"ON EVENT EMITTED BUT NO CONNECTED WALLET FOUND: ".into(),
-                    &format!("{error:?}").into(),
+                self.logger.log(
+                    LogLevel::Warn,
+                    &format!("ON EVENT EMITTED BUT NO CONNECTED WALLET FOUND: {error:?}"),
                 );
             }
         }
     }
 
+    /// Clear the connection and emit [WalletEvent::Disconnected] after the wallet
+    /// with `wallet_name` notified that the user disconnected from within the wallet.
+    /// Notifications of wallets other than the connected wallet are ignored
+    pub async fn emit_wallet_disconnect(&mut self, wallet_name: &str, sender: WalletEventSender) {
+        let is_connected_wallet = self
+            .wallet
+            .as_ref()
+            .is_some_and(|wallet| WalletName::new(wallet.name()) == WalletName::new(wallet_name));
+
+        if is_connected_wallet {
+            self.set_disconnected(sender).await;
+        } else {
+            self.logger.log(
+                LogLevel::Debug,
+                &format!("DISCONNECT EMITTED BY {wallet_name} WHICH IS NOT CONNECTED"),
+            );
+        }
+    }
+
+    /// Compare the version of a `registered` wallet with the version of the connected wallet
+    /// and emit [WalletEvent::WalletDowngraded] if the connected wallet re-registered itself
+    /// with a lower version. Other wallets and upgrades are ignored
+    pub async fn on_version_change(&mut self, registered: &Wallet, sender: WalletEventSender) {
+        let Some(connected) = self.wallet.as_ref() else {
+            return;
+        };
+
+        if WalletName::new(connected.name()) != WalletName::new(registered.name())
+            || registered.version() >= connected.version()
+        {
+            return;
+        }
+
+        let downgraded = WalletEvent::WalletDowngraded {
+            from: connected.version(),
+            to: registered.version(),
+        };
+
+        self.send_wallet_event(downgraded, sender).await
+    }
+
+    /// Get the most recent event that changed the connection state.
+    /// This allows consumers that start listening for events after
+    /// a wallet has been connected to reconcile their state
+    pub fn last_event(&self) -> Option<&WalletEvent> {
+        self.last_event.as_ref()
+    }
+
+    async fn send_wallet_event(&mut self, wallet_event: WalletEvent, sender: WalletEventSender) {
+        if wallet_event.is_connection_event() {
+            self.last_event.replace(wallet_event.clone());
+        }
+
+        send_wallet_event(wallet_event, sender, &self.logger).await
+    }
+
+    /// Clear the connected wallet, the connected account and the known accounts
+    fn clear_connection(&mut self) {
+        self.wallet.take();
+        self.account.take();
+        self.previous_accounts.clear();
+        self.authorized_accounts.clear();
+    }
+
     fn push_previous_account(&mut self) {
         let take_connected_account = self.account.take();
 
@@ -172,21 +384,119 @@ impl ConnectionInfo {
     }
 }
 
+/// A snapshot of the connection state for binding a UI in one step,
+/// see [WalletAdapter::summary]
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConnectionSummary {
+    /// Whether an [account](WalletAccount) is connected
+    pub connected: bool,
+    /// The name of the connected wallet
+    pub wallet_name: Option<String>,
+    /// The Base58 address of the connected account
+    pub account_address: Option<String>,
+    /// The address of the connected account shortened like [WalletAccount::shorten_address]
+    pub shortened_address: Option<String>,
+    /// The icon of the connected wallet
+    pub icon: Option<String>,
+    /// The clusters supported by the connected wallet, in the same order as [WalletAdapter::clusters]
+    pub clusters: Vec<Cluster>,
+}
+
+/// The default maximum size in bytes of a message sent to a wallet to be signed, 64 KiB
+pub const DEFAULT_MAX_MESSAGE_BYTES: usize = 64 * 1024;
+
+// How often and how long [WalletAdapter::connect_and_wait_ready] checks the connected account
+const ACCOUNT_READY_ATTEMPTS: u8 = 10;
+const ACCOUNT_READY_INTERVAL: Duration = Duration::from_millis(50);
+
+// How often [WalletAdapter::confirm_transaction] polls the status of the transaction
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
 /// The [ConnectionInfo] wrapped in an `Arc<RwLock<T>>`
 pub type ConnectionInfoInner = Arc<RwLock<ConnectionInfo>>;
 
+/// Marks a connect request as in progress until dropped,
+/// so the flag is released when the request completes, fails or is cancelled
+#[derive(Debug)]
+struct ConnectGuard(Arc<AtomicBool>);
+
+impl ConnectGuard {
+    fn acquire(in_progress: &Arc<AtomicBool>) -> WalletResult<Self> {
+        in_progress
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .or(Err(WalletError::ConnectAlreadyInProgress))?;
+
+        Ok(Self(Arc::clone(in_progress)))
+    }
+}
+
+impl Drop for ConnectGuard {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+/// Cancels an in-flight request, see [WalletAdapter::sign_message_cancelable]
+#[derive(Debug, Clone)]
+pub struct CancelHandle(Sender<()>);
+
+impl CancelHandle {
+    /// Cancel the request, which then resolves with [WalletError::Cancelled].
+    /// Cancelling a request that already completed has no effect
+    pub fn cancel(&self) {
+        // A full channel means the request was already cancelled
+        let _ = self.0.try_send(());
+    }
+}
+
 /// Operations on a browser window.
 /// `Window` and `Document` object must be present otherwise
 /// an error is thrown.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct WalletAdapter {
     window: Window,
     document: Document,
     storage: WalletStorage,
     connection_info: ConnectionInfoInner,
     wallet_events: WalletEventReceiver,
+    wallet_events_seq: WalletEventEnvelopeReceiver,
     wallet_events_sender: WalletEventSender,
+    signal_sender: Sender<()>,
     signal_receiver: Receiver<()>,
+    active_event_listeners: Arc<AtomicUsize>,
+    wallet_registered: (Sender<()>, Receiver<()>),
+    max_message_bytes: usize,
+    response_inspector: ResponseInspector,
+    logger: Logger,
+    default_commitment: Commitment,
+    rpc_headers: Vec<(String, String)>,
+    connect_in_progress: Arc<AtomicBool>,
+    clock: Rc<dyn Clock>,
+}
+
+impl core::fmt::Debug for WalletAdapter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WalletAdapter")
+            .field("window", &self.window)
+            .field("document", &self.document)
+            .field("storage", &self.storage)
+            .field("connection_info", &self.connection_info)
+            .field("wallet_events", &self.wallet_events)
+            .field("wallet_events_seq", &self.wallet_events_seq)
+            .field("wallet_events_sender", &self.wallet_events_sender)
+            .field("signal_sender", &self.signal_sender)
+            .field("signal_receiver", &self.signal_receiver)
+            .field("active_event_listeners", &self.active_event_listeners)
+            .field("wallet_registered", &self.wallet_registered)
+            .field("max_message_bytes", &self.max_message_bytes)
+            .field("response_inspector", &self.response_inspector)
+            .field("logger", &self.logger)
+            .field("default_commitment", &self.default_commitment)
+            .field("rpc_headers", &RedactedHeaders(&self.rpc_headers))
+            .field("connect_in_progress", &self.connect_in_progress)
+            .field("clock", &self.clock)
+            .finish()
+    }
 }
 
 impl WalletAdapter {
@@ -240,23 +550,60 @@ impl WalletAdapter {
         let storage = WalletStorage::default();
 
         let (sender, receiver) = bounded::<WalletEvent>(capacity);
-        let (_, signal_receiver) = bounded::<()>(capacity);
+        let (seq_sender, seq_receiver) = bounded::<WalletEventEnvelope>(capacity);
+        let (signal_sender, signal_receiver) = bounded::<()>(1);
+
+        let logger = Logger::default();
 
         let mut new_self = Self {
             window: window.clone(),
             document,
             storage,
-            connection_info: Arc::new(RwLock::new(ConnectionInfo::default())),
+            connection_info: Arc::new(RwLock::new(ConnectionInfo {
+                logger: logger.clone(),
+                ..Default::default()
+            })),
             wallet_events: receiver,
-            wallet_events_sender: sender,
+            wallet_events_seq: seq_receiver,
+            wallet_events_sender: WalletEventSender::new(sender, seq_sender),
+            signal_sender,
             signal_receiver,
+            active_event_listeners: Arc::default(),
+            wallet_registered: bounded::<()>(1),
+            max_message_bytes: DEFAULT_MAX_MESSAGE_BYTES,
+            response_inspector: ResponseInspector::default(),
+            logger,
+            default_commitment: Commitment::default(),
+            rpc_headers: Vec::default(),
+            connect_in_progress: Arc::default(),
+            clock: Rc::new(JsDateClock),
         };
 
         InitEvents::new(&window).init(&mut new_self)?;
 
+        if new_self.wallets().is_empty() && !new_self.is_secure_context() {
+            new_self.logger.log(
+                LogLevel::Warn,
+                "No wallets found and the page is not served over a secure context. \
+                Browser wallets do not inject into insecure origins, \
+                serve the page over `https://` or from `localhost`",
+            );
+        }
+
         Ok(new_self)
     }
 
+    /// Check whether the page is served over a secure context, `https://` or `localhost`,
+    /// by reading `window.isSecureContext`. Wallet extensions usually do not register
+    /// themselves on insecure origins so no wallets will be found
+    pub fn is_secure_context(&self) -> bool {
+        Reflection::new(self.window.clone().into())
+            .and_then(|window| window.reflect_inner("isSecureContext"))
+            .ok()
+            .and_then(|is_secure_context| is_secure_context.as_bool())
+            .unwrap_or_default()
+    }
+
     /// Initializes with a [web_sys::Window] and [web_sys::Document] that have been
     /// initialized elsewhere. For example some Rust frontend frameworks already
     /// expose the window and document objects, you could pass them here.
@@ -264,6 +611,52 @@ impl WalletAdapter {
         Self::init_with_channel_capacity_window_and_document(5, window, document)
     }
 
+    /// Resolves once at least one wallet has registered itself or the `grace` period elapses,
+    /// whichever comes first. Wallets register asynchronously after [Self::init] so
+    /// [Self::wallets] may be empty if called immediately after initializing.
+    pub fn ready(&self, grace: Duration) -> impl Future<Output = ()> {
+        let storage = self.storage.clone();
+        let registered = self.wallet_registered.1.clone();
+        let logger = self.logger.clone();
+
+        async move {
+            if !storage.get_wallets().is_empty() {
+                return;
+            }
+
+            InnerUtils::race(
+                async {
+                    let _ = registered.recv().await;
+                },
+                async {
+                    if let Err(error) = InnerUtils::sleep(grace).await {
+                        logger.log(
+                            LogLevel::Error,
+                            &format!("WALLET ADAPTER READY ERROR: {error:?}"),
+                        );
+                    }
+                },
+            )
+            .await
+        }
+    }
+
+    pub(crate) fn wallet_registered_sender(&self) -> Sender<()> {
+        self.wallet_registered.0.clone()
+    }
+
+    pub(crate) fn wallet_events_sender(&self) -> WalletEventSender {
+        self.wallet_events_sender.clone()
+    }
+
+    pub(crate) fn connection_info_inner(&self) -> ConnectionInfoInner {
+        self.connection_info.clone()
+    }
+
+    pub(crate) fn logger(&self) -> Logger {
+        self.logger.clone()
+    }
+
     /// Listen for [WalletEvent] to be notified when a wallet
     /// receives `connected`, `disconnected` and `accountChanged` events triggered
     /// when the `change` event is dispatched by a connected browser extension
@@ -271,24 +664,172 @@ impl WalletAdapter {
         self.wallet_events.clone()
     }
 
-    /// Send a connect request to the browser wallet
+    /// Same as [Self::events] but each event is wrapped in a [WalletEventEnvelope]
+    /// containing a monotonically increasing sequence number so that stale events,
+    /// for example when rapidly connecting and disconnecting, can be discarded.
+    /// When the channel is full the oldest envelope is dropped.
+    pub fn events_seq(&self) -> WalletEventEnvelopeReceiver {
+        self.wallet_events_seq.clone()
+    }
+
+    /// Wait for the next [WalletEvent] of the `kind` emitted after this is called, for example
+    /// until the next [WalletEvent::Disconnected], ignoring the events of other kinds emitted meanwhile.
+    /// Fails with [WalletError::EventWaitTimeout] if no such event is received within the `timeout`.
+    /// The events are read from a subscription of this call, so every event is still delivered
+    /// to the listeners of [Self::events] and [Self::events_seq]
+    pub async fn next_event_of(
+        &self,
+        kind: WalletEventKind,
+        timeout: Duration,
+    ) -> WalletResult<WalletEvent> {
+        let events = self.wallet_events_sender.subscribe();
+
+        InnerUtils::race(
+            async {
+                loop {
+                    let event = events.recv().await.or(Err(WalletError::ChannelError))?;
+
+                    if event.kind() == kind {
+                        return Ok(event);
+                    }
+                }
+            },
+            async {
+                InnerUtils::sleep(timeout).await?;
+
+                Err(WalletError::EventWaitTimeout(kind, timeout))
+            },
+        )
+        .await
+    }
+
+    /// Set a hook that is invoked with the raw [JsValue] response of the wallet before it is parsed,
+    /// for example to log the exact object returned by a wallet that responds with an unexpected shape.
+    /// The hook applies to the connected wallet and to wallets connected afterwards,
+    /// waiting for pending reads of the connection to finish before it is applied.
+    pub async fn set_response_inspector(&mut self, inspector: Rc<dyn Fn(&JsValue)>) -> &mut Self {
+        self.response_inspector = ResponseInspector::new(inspector);
+
+        if let Some(wallet) = self.connection_info.write().await.wallet.as_mut() {
+            wallet.set_response_inspector(self.response_inspector.clone());
+        }
+
+        self
+    }
+
+    /// Set a hook that receives the internal diagnostics of the adapter, the connected wallet
+    /// and the background task listening for wallet events instead of the browser console,
+    /// for example to silence them or forward them to `tracing`
+    pub fn set_logger(&mut self, logger: LoggerFn) -> &mut Self {
+        self.logger.set(logger);
+
+        self
+    }
+
+    /// Replace the [Clock] used to validate the times of Sign In With Solana requests,
+    /// for example with a fixed time in tests. Defaults to [JsDateClock]
+    pub fn set_clock(&mut self, clock: impl Clock + 'static) -> &mut Self {
+        self.clock = Rc::new(clock);
+
+        self
+    }
+
+    /// Send a connect request to the browser wallet.
+    /// A second connect request made while one is still in progress, including from a clone
+    /// of this adapter, fails with [WalletError::ConnectAlreadyInProgress]
     pub async fn connect(&mut self, wallet: Wallet) -> WalletResult<WalletAccount> {
+        self.connect_inner(wallet, false).await
+    }
+
+    /// Send a silent connect request to the browser wallet which connects without prompting
+    /// the user only if the dapp was previously authorized, for example to restore a session
+    /// when the page loads. If the dapp was not authorized [WalletError::ConnectHasNoAccounts]
+    /// is returned and the dapp can decide whether to prompt the user with [Self::connect]
+    pub async fn connect_silent_authorized(
+        &mut self,
+        wallet: Wallet,
+    ) -> WalletResult<WalletAccount> {
+        self.connect_inner(wallet, true).await
+    }
+
+    /// Same as [Self::connect] but only returns once the wallet is ready to sign with the
+    /// connected account. Some wallets resolve the connect request before they finished
+    /// setting up the account internally, so a sign request sent right after connecting
+    /// occasionally fails with a transient error. The readiness is confirmed by checking,
+    /// up to 10 times 50ms apart, that the `accounts` the wallet exposes include the
+    /// connected account, so only a single connect request is sent. If the wallet never
+    /// confirms or its accounts cannot be read, a warning is logged and the account
+    /// is returned anyway
+    pub async fn connect_and_wait_ready(&mut self, wallet: Wallet) -> WalletResult<WalletAccount> {
+        let account = self.connect(wallet).await?;
+        let wallet = self.connection_info().await.connected_wallet()?.clone();
+
+        for attempt in 1..=ACCOUNT_READY_ATTEMPTS {
+            match wallet.current_accounts() {
+                Ok(accounts)
+                    if accounts
+                        .iter()
+                        .any(|exposed| exposed.public_key() == account.public_key()) =>
+                {
+                    return Ok(account);
+                }
+                Ok(_) => {}
+                Err(error) => {
+                    self.logger.log(
+                        LogLevel::Warn,
+                        &format!(
+                            "UNABLE TO READ THE ACCOUNTS OF {} TO CONFIRM IT IS READY: {error:?}",
+                            wallet.name()
+                        ),
+                    );
+
+                    return Ok(account);
+                }
+            }
+
+            if attempt < ACCOUNT_READY_ATTEMPTS {
+                InnerUtils::sleep(ACCOUNT_READY_INTERVAL).await?;
+            }
+        }
+
+        self.logger.log(
+            LogLevel::Warn,
+            &format!(
+                "{} DID NOT CONFIRM THE CONNECTED ACCOUNT {} IS READY",
+                wallet.name(),
+                account.address()
+            ),
+        );
+
+        Ok(account)
+    }
+
+    async fn connect_inner(
+        &mut self,
+        mut wallet: Wallet,
+        silent: bool,
+    ) -> WalletResult<WalletAccount> {
+        let _guard = ConnectGuard::acquire(&self.connect_in_progress)?;
+
+        wallet
+            .set_response_inspector(self.response_inspector.clone())
+            .set_logger(self.logger.clone());
+
         let wallet_name = wallet.name().to_string();
         let sender = self.wallet_events_sender.clone();
-        let signal_receiver = self.signal_receiver.clone();
 
-        if self.connection_info().await.connected_wallet().is_ok() {
-            let capacity = signal_receiver.capacity().unwrap_or(5);
-            let (_, signal_receiver) = bounded::<()>(capacity);
-            self.signal_receiver = signal_receiver;
-        }
+        // Closing the signal unsubscribes the event listener of the previous connection
+        self.signal_receiver.close();
+        let (signal_sender, signal_receiver) = bounded::<()>(1);
+        self.signal_sender = signal_sender;
+        self.signal_receiver = signal_receiver.clone();
 
         let wallet_account = self
             .connection_info
             .write()
             .await
             .set_wallet(wallet)
-            .connect(sender.clone())
+            .connect_with_options(sender.clone(), silent)
             .await?;
 
         self.connection_info()
@@ -299,12 +840,45 @@ impl WalletAdapter {
                 wallet_name,
                 sender,
                 signal_receiver,
+                self.active_event_listeners.clone(),
             )
             .await?;
 
         Ok(wallet_account)
     }
 
+    /// Send a connect request to the browser wallet retrying up to `attempts` times
+    /// when the wallet fails with [WalletError::WalletConnectError], for example
+    /// when a freshly injected wallet is still initializing.
+    /// The delay between attempts starts at `base_delay` and doubles after every attempt.
+    /// Requests rejected by the user are never retried.
+    pub async fn connect_with_retry(
+        &mut self,
+        wallet: Wallet,
+        attempts: u8,
+        base_delay: Duration,
+    ) -> WalletResult<WalletAccount> {
+        let mut delay = base_delay;
+        let mut attempt = 1u8;
+
+        loop {
+            match self.connect(wallet.clone()).await {
+                Err(WalletError::WalletConnectError(error)) if attempt < attempts => {
+                    self.logger.log(
+                        LogLevel::Warn,
+                        &format!("CONNECT ATTEMPT {attempt} FAILED, RETRYING: {error}"),
+                    );
+
+                    InnerUtils::sleep(delay).await?;
+
+                    delay = delay.saturating_mul(2);
+                    attempt += 1;
+                }
+                outcome => return outcome,
+            }
+        }
+    }
+
     /// Lookup a wallet entry by name from the registered wallets
     /// and then send a connect request to the browser extension wallet
     pub async fn connect_by_name(&mut self, wallet_name: &str) -> WalletResult<WalletAccount> {
@@ -313,10 +887,71 @@ impl WalletAdapter {
         self.connect(wallet).await
     }
 
-    /// Send a disconnect request to the browser wallet
+    /// Send another connect request to the connected wallet so that the user can
+    /// authorize more accounts. Only the accounts that are not already in [Wallet::accounts]
+    /// are returned, an empty [Vec] if none were added. The new accounts are added to the
+    /// connected wallet and to the wallet in storage
+    pub async fn request_more_accounts(&mut self) -> WalletResult<Vec<WalletAccount>> {
+        let wallet = self.connection_info().await.connected_wallet()?.clone();
+
+        let authorized = wallet.connect_accounts().await?;
+
+        self.connection_info.write().await.authorized_accounts = authorized
+            .iter()
+            .map(|account| account.public_key())
+            .collect();
+
+        let new_accounts = authorized
+            .into_iter()
+            .filter(|authorized| {
+                wallet
+                    .accounts()
+                    .iter()
+                    .all(|existing| existing.public_key() != authorized.public_key())
+            })
+            .collect::<Vec<WalletAccount>>();
+
+        if new_accounts.is_empty() {
+            return Ok(new_accounts);
+        }
+
+        if let Some(connected_wallet) = self.connection_info.write().await.wallet.as_mut() {
+            connected_wallet
+                .accounts
+                .extend(new_accounts.iter().cloned());
+        }
+
+        let storage_key = WalletName::new(wallet.name()).storage_key();
+        if let Some(stored_wallet) = self
+            .storage
+            .clone_inner()
+            .borrow_mut()
+            .get_mut(&storage_key)
+        {
+            stored_wallet.accounts.extend(new_accounts.iter().cloned());
+        }
+
+        Ok(new_accounts)
+    }
+
+    /// Send a disconnect request to the browser wallet.
+    /// Wallets that do not support `standard:disconnect` are only
+    /// disconnected locally. Errors from the browser wallet are logged
+    /// using the [logger](Self::set_logger) since the local state is always cleared.
     pub async fn disconnect(&mut self) {
         let sender = self.wallet_events_sender.clone();
 
+        let wallet = self.connection_info().await.connected_wallet_raw().cloned();
+
+        if let Some(wallet) = wallet {
+            if let Err(error) = wallet.disconnect().await {
+                self.logger.log(
+                    LogLevel::Error,
+                    &format!("WALLET DISCONNECT ERROR: {error:?}"),
+                );
+            }
+        }
+
         self.connection_info
             .write()
             .await
@@ -325,26 +960,99 @@ impl WalletAdapter {
         self.signal_receiver.close();
     }
 
-    /// Send a sign in request to the browser wallet to Sign In With Solana
+    /// Send a sign in request to the browser wallet to Sign In With Solana.
+    /// If an account is connected the `public_key` must be the public key of that account,
+    /// otherwise [WalletError::SignInKeyMismatch] is returned without sending the request
     pub async fn sign_in(
         &self,
         signin_input: &SigninInput,
         public_key: [u8; 32],
     ) -> WalletResult<SignInOutput> {
-        self.connection_info()
-            .await
+        let connection_info = self.connection_info().await;
+        Self::check_sign_in_key(&connection_info, public_key)?;
+
+        connection_info
             .connected_wallet()?
             .sign_in(signin_input, public_key)
             .await
     }
 
-    /// Send a sign and send transaction request to the browser wallet
+    fn check_sign_in_key(
+        connection_info: &ConnectionInfo,
+        public_key: [u8; 32],
+    ) -> WalletResult<()> {
+        match connection_info.connected_account() {
+            Ok(account) if account.public_key() != public_key => {
+                Err(WalletError::SignInKeyMismatch)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Same as [Self::sign_in] but checks the expiration time of the `signin_input` against
+    /// the [clock](Self::set_clock) before sending the request, so that an expired request is
+    /// never shown to the user, and again after the wallet responds, since the user may take
+    /// longer than the request is valid for. [WalletError::ExpirationTimeIsInThePast] is returned
+    /// if the request expired
+    pub async fn sign_in_checked(
+        &self,
+        signin_input: &SigninInput,
+        public_key: [u8; 32],
+    ) -> WalletResult<SignInOutput> {
+        self.check_not_expired(signin_input)?;
+
+        let output = self.sign_in(signin_input, public_key).await?;
+
+        self.check_not_expired(signin_input)?;
+
+        Ok(output)
+    }
+
+    fn check_not_expired(&self, signin_input: &SigninInput) -> WalletResult<()> {
+        match signin_input.expiration_time() {
+            Some(expiration_time) if self.clock.now() > *expiration_time => {
+                Err(WalletError::ExpirationTimeIsInThePast)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Same as [Self::sign_in] but fails with [WalletError::SignInTimeout] if the wallet does
+    /// not respond within the `timeout`, for example when the user leaves the popup open.
+    /// The pending request is dropped so no state is retained, a late response is ignored
+    pub async fn sign_in_with_timeout(
+        &self,
+        signin_input: &SigninInput,
+        public_key: [u8; 32],
+        timeout: Duration,
+    ) -> WalletResult<SignInOutput> {
+        // Clone the wallet so the connection is not locked while waiting for the user
+        let wallet = {
+            let connection_info = self.connection_info().await;
+            Self::check_sign_in_key(&connection_info, public_key)?;
+
+            connection_info.connected_wallet()?.clone()
+        };
+
+        InnerUtils::race(wallet.sign_in(signin_input, public_key), async {
+            InnerUtils::sleep(timeout).await?;
+
+            Err(WalletError::SignInTimeout(timeout))
+        })
+        .await
+    }
+
+    /// Send a sign and send transaction request to the browser wallet.
+    /// If the [preflight commitment](SendOptions::preflight_commitment) of the `options`
+    /// is not set it is set to the [Self::default_commitment]
     pub async fn sign_and_send_transaction(
         &self,
         transaction_bytes: &[u8],
         cluster: Cluster,
         options: SendOptions,
     ) -> WalletResult<Signature> {
+        let options = self.with_default_commitment(options);
+
         let connection_info = self.connection_info();
 
         connection_info
@@ -359,6 +1067,37 @@ impl WalletAdapter {
             .await
     }
 
+    /// Same as [Self::sign_and_send_transaction] but then waits until the transaction reaches
+    /// the [confirm commitment](SendOptions::confirm_commitment) of the `options` on the `cluster`,
+    /// see [Self::confirm_transaction] for the errors returned if it does not
+    pub async fn sign_and_send_transaction_confirmed(
+        &self,
+        transaction_bytes: &[u8],
+        cluster: Cluster,
+        options: SendOptions,
+        timeout: Duration,
+    ) -> WalletResult<Signature> {
+        let options = self.with_default_commitment(options);
+        let commitment = options.confirm_commitment();
+
+        let signature = self
+            .sign_and_send_transaction(transaction_bytes, cluster, options)
+            .await?;
+
+        self.confirm_transaction_at(&signature, cluster, commitment, timeout)
+            .await?;
+
+        Ok(signature)
+    }
+
+    fn with_default_commitment(&self, mut options: SendOptions) -> SendOptions {
+        if options.preflight_commitment().is_none() {
+            options.set_preflight_commitment(self.default_commitment);
+        }
+
+        options
+    }
+
     /// Send a sign transaction request to the browser wallet
     pub async fn sign_transaction(
         &self,
@@ -378,28 +1117,607 @@ impl WalletAdapter {
             .await
     }
 
-    /// Send a sign message request to the browser wallet
-    pub async fn sign_message<'a>(
+    /// Same as [Self::sign_transaction] for a single transaction, returning the one signed
+    /// transaction. [WalletError::ReceivedAnEmptySignedMessagesArray] is returned if the wallet
+    /// returned nothing and [WalletError::UnexpectedSignedTransactionCount] if it returned more
+    pub async fn sign_transaction_single(
         &self,
-        message: &'a [u8],
-    ) -> WalletResult<SignedMessageOutput<'a>> {
-        let connection_info = self.connection_info();
+        tx_bytes: &[u8],
+        cluster: Option<Cluster>,
+    ) -> WalletResult<Vec<u8>> {
+        let mut signed = self.sign_transaction(&[tx_bytes], cluster).await?;
 
-        self.connection_info()
-            .await
-            .connected_wallet()?
-            .sign_message(message, connection_info.await.connected_account()?)
-            .await
+        match signed.len() {
+            0 => Err(WalletError::ReceivedAnEmptySignedMessagesArray),
+            1 => Ok(signed.remove(0)),
+            count => Err(WalletError::UnexpectedSignedTransactionCount(count)),
+        }
     }
 
-    /// Check if an [account](WalletAccount) is connected
-    pub async fn is_connected(&self) -> bool {
-        self.connection_info
-            .as_ref()
-            .write()
-            .await
-            .account
-            .is_some()
+    /// Send a request to the browser wallet to sign the transaction and merge the
+    /// `extra_signatures` as `(public key, signature)` pairs, for example signatures
+    /// of a backend co-signer, into the signature slots of their public keys.
+    /// Every public key must be a required signer of the transaction, which
+    /// is validated before the user is prompted to sign
+    pub async fn sign_transaction_partial(
+        &self,
+        tx_bytes: &[u8],
+        cluster: Option<Cluster>,
+        extra_signatures: &[([u8; 32], [u8; 64])],
+    ) -> WalletResult<Vec<u8>> {
+        transaction::merge_signatures(tx_bytes, extra_signatures)?;
+
+        let signed = self
+            .sign_transaction(&[tx_bytes], cluster)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(WalletError::ExpectedValueNotFound(
+                "signedTransaction".to_string(),
+            ))?;
+
+        transaction::merge_signatures(&signed, extra_signatures)
+    }
+
+    /// Send a request to the browser wallet to sign all the transactions in a single approval
+    /// using the `solana:signAllTransactions` feature
+    pub async fn sign_all_transactions(
+        &self,
+        transactions: &[impl AsRef<[u8]>],
+        cluster: Option<Cluster>,
+    ) -> WalletResult<Vec<Vec<u8>>> {
+        let connection_info = self.connection_info().await;
+
+        connection_info
+            .connected_wallet()?
+            .sign_all_transactions(transactions, cluster, connection_info.connected_account()?)
+            .await
+    }
+
+    /// Same as [Self::sign_all_transactions] for wallets that limit the number of transactions
+    /// signed in a single approval. The `transactions` are split into chunks of at most
+    /// `chunk_size` transactions, each signed in its own approval, and the signed transactions
+    /// are returned in the same order. [WalletError::InvalidChunkSize] is returned if
+    /// `chunk_size` is zero
+    pub async fn sign_all_transactions_chunked(
+        &self,
+        transactions: &[impl AsRef<[u8]>],
+        cluster: Option<Cluster>,
+        chunk_size: usize,
+    ) -> WalletResult<Vec<Vec<u8>>> {
+        if chunk_size == 0 {
+            return Err(WalletError::InvalidChunkSize);
+        }
+
+        let mut signed_txs = Vec::with_capacity(transactions.len());
+
+        for chunk in transactions.chunks(chunk_size) {
+            signed_txs.extend(self.sign_all_transactions(chunk, cluster).await?);
+        }
+
+        Ok(signed_txs)
+    }
+
+    /// Verify the signature of the account with `public_key` in each of the `signed` transactions,
+    /// for example the result of [Self::sign_all_transactions]. Each signature is verified using
+    /// strict Ed25519 verification and the result of each transaction is returned in the same order.
+    /// In transactions with multiple required signers only the signature slot of the account is verified
+    /// and a transaction that the account is not required to sign is reported as `false`.
+    pub fn verify_signed_transactions(
+        &self,
+        signed: &[Vec<u8>],
+        public_key: &[u8; 32],
+    ) -> WalletResult<Vec<bool>> {
+        transaction::verify_signed_transactions(signed, public_key)
+    }
+
+    /// Sign all the transactions using `solana:signAllTransactions` if the connected wallet supports it,
+    /// otherwise each transaction is signed using `solana:signTransaction`.
+    /// When signing more than one transaction without `solana:signAllTransactions`
+    /// the event [WalletEvent::MultipleApprovalsRequired] is emitted since the user
+    /// will be asked to approve each transaction
+    pub async fn sign_all_transactions_or_fallback(
+        &self,
+        transactions: &[impl AsRef<[u8]>],
+        cluster: Option<Cluster>,
+    ) -> WalletResult<Vec<Vec<u8>>> {
+        let connection_info = self.connection_info().await;
+        let wallet = connection_info.connected_wallet()?;
+        let account = connection_info.connected_account()?;
+
+        if wallet.solana_sign_all_transactions() {
+            return wallet
+                .sign_all_transactions(transactions, cluster, account)
+                .await;
+        }
+
+        if transactions.len() > 1 {
+            send_wallet_event(
+                WalletEvent::MultipleApprovalsRequired(transactions.len()),
+                self.wallet_events_sender.clone(),
+                &self.logger,
+            )
+            .await;
+        }
+
+        let mut signed_txs = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            signed_txs.extend(
+                wallet
+                    .sign_transaction(&[transaction], cluster, account)
+                    .await?,
+            );
+        }
+
+        Ok(signed_txs)
+    }
+
+    /// Send a sign message request to the browser wallet.
+    /// The signature is verified against the message and the public key of the connected
+    /// account before it is returned, [WalletError::InvalidSignature] is returned
+    /// if the verification fails, so there is no need to verify it again
+    pub async fn sign_message<'a>(
+        &self,
+        message: &'a [u8],
+    ) -> WalletResult<SignedMessageOutput<'a>> {
+        self.check_message_size(message)?;
+
+        let connection_info = self.connection_info();
+
+        self.connection_info()
+            .await
+            .connected_wallet()?
+            .sign_message(message, connection_info.await.connected_account()?)
+            .await
+    }
+
+    /// Route Rust panics to `console.error` with the panic message and location instead of
+    /// the `unreachable` error the browser reports for a panic in WebAssembly.
+    /// Call it once at startup, calling it again has no effect.
+    /// Requires the `panic-hook` feature
+    #[cfg(feature = "panic-hook")]
+    pub fn install_panic_hook() {
+        static INSTALL: std::sync::Once = std::sync::Once::new();
+
+        INSTALL.call_once(|| {
+            let default_hook = std::panic::take_hook();
+
+            std::panic::set_hook(Box::new(move |info| {
+                #[cfg(target_arch = "wasm32")]
+                web_sys::console::error_1(&info.to_string().into());
+
+                default_hook(info);
+            }));
+        });
+    }
+
+    /// Same as [Self::sign_message] but the `message` is encoded as a version `0` Solana
+    /// off-chain message for the application `domain` and the connected account before it is
+    /// signed, so that the signature cannot be replayed as a transaction or for another domain.
+    /// Verifiers implementing the off-chain message standard can check the signature over
+    /// [SignedMessageOutput::signed_bytes], or use [SignedMessageOutput::verify].
+    /// The `domain` is at most 32 bytes, otherwise [WalletError::SigningDomainTooLong]
+    /// is returned before the request is sent to the wallet.
+    /// Messages that are not valid UTF-8 are signed with the extended format,
+    /// verifiers that require the extended format to be UTF-8 will reject them
+    pub async fn sign_message_domain_separated<'a>(
+        &self,
+        domain: &'a str,
+        message: &'a [u8],
+    ) -> WalletResult<SignedMessageOutput<'a>> {
+        let signer = self
+            .connection_info()
+            .await
+            .connected_account()?
+            .account
+            .public_key;
+        let encoded = offchain_message(domain, &signer, message)?;
+
+        let signed = self.sign_message(&encoded).await?;
+
+        Ok(signed.with_domain(domain, message))
+    }
+
+    /// Same as [Self::sign_message] but the result is repackaged as a [SignInOutput],
+    /// so that a dapp can verify signed messages with its Sign In With Solana pipeline.
+    /// Only valid UTF-8 messages are accepted, otherwise [WalletError::InvalidUtf8Message]
+    /// is returned before the request is sent to the wallet
+    pub async fn sign_message_as_siws_output(&self, message: &[u8]) -> WalletResult<SignInOutput> {
+        let message_str = core::str::from_utf8(message).or(Err(WalletError::InvalidUtf8Message))?;
+
+        let signed = self.sign_message(message).await?;
+
+        Ok(SignInOutput {
+            account: self
+                .connection_info()
+                .await
+                .connected_account()?
+                .account
+                .clone(),
+            message: SiwsMessage::new(message_str),
+            signature: signed.signature().to_bytes(),
+            public_key: signed.public_key()?.to_bytes(),
+        })
+    }
+
+    /// Send a sign message request to the browser wallet that can be abandoned, for example
+    /// when the user navigates away. Calling [CancelHandle::cancel] drops the request and
+    /// resolves the future with [WalletError::Cancelled]. Dropping the [CancelHandle]
+    /// without calling [CancelHandle::cancel] does not cancel the request.
+    /// The popup of the wallet may remain open since wallets cannot be told to close it
+    pub fn sign_message_cancelable<'a>(
+        &'a self,
+        message: &'a [u8],
+    ) -> (
+        impl Future<Output = WalletResult<SignedMessageOutput<'a>>> + 'a,
+        CancelHandle,
+    ) {
+        let (sender, receiver) = bounded::<()>(1);
+
+        let request = InnerUtils::race(self.sign_message(message), async move {
+            match receiver.recv().await {
+                Ok(()) => Err(WalletError::Cancelled),
+                Err(_) => core::future::pending().await,
+            }
+        });
+
+        (request, CancelHandle(sender))
+    }
+
+    /// Send a sign transaction request to the browser wallet using a certain `account`
+    /// of the connected wallet instead of the connected account.
+    /// [WalletError::AccountNotInWallet] is returned if the account does not belong to the connected wallet.
+    pub async fn sign_transaction_with_account(
+        &self,
+        transactions: &[impl AsRef<[u8]>],
+        cluster: Option<Cluster>,
+        account: &WalletAccount,
+    ) -> WalletResult<Vec<Vec<u8>>> {
+        let connection_info = self.connection_info().await;
+        let wallet = connection_info.connected_wallet()?;
+
+        connection_info
+            .account_by_pubkey(account.public_key())
+            .ok_or(WalletError::AccountNotInWallet)?;
+
+        wallet
+            .sign_transaction(transactions, cluster, account)
+            .await
+    }
+
+    /// Send a sign message request to the browser wallet using a certain `account`
+    /// of the connected wallet instead of the connected account.
+    /// [WalletError::AccountNotInWallet] is returned if the account does not belong to the connected wallet.
+    pub async fn sign_message_with_account<'a>(
+        &self,
+        message: &'a [u8],
+        account: &WalletAccount,
+    ) -> WalletResult<SignedMessageOutput<'a>> {
+        self.check_message_size(message)?;
+
+        let connection_info = self.connection_info().await;
+        let wallet = connection_info.connected_wallet()?;
+
+        connection_info
+            .account_by_pubkey(account.public_key())
+            .ok_or(WalletError::AccountNotInWallet)?;
+
+        wallet.sign_message(message, account).await
+    }
+
+    /// Set the maximum size in bytes of a message that can be sent to the wallet to be signed.
+    /// Wallets can freeze when asked to sign very large messages so
+    /// [WalletError::MessageTooLarge] is returned instead of sending the request.
+    /// Defaults to [DEFAULT_MAX_MESSAGE_BYTES]
+    pub fn set_max_message_bytes(&mut self, max: usize) -> &mut Self {
+        self.max_message_bytes = max;
+
+        self
+    }
+
+    /// Get the maximum size in bytes of a message that can be sent to the wallet to be signed
+    pub fn max_message_bytes(&self) -> usize {
+        self.max_message_bytes
+    }
+
+    /// Set the [Commitment] used by the RPC helpers like [Self::get_balance]
+    /// and [Self::confirm_transaction] and as the preflight commitment when the
+    /// [SendOptions] passed to [Self::sign_and_send_transaction] do not set one.
+    /// Defaults to [Commitment::Finalized]
+    pub fn set_default_commitment(&mut self, commitment: Commitment) -> &mut Self {
+        self.default_commitment = commitment;
+
+        self
+    }
+
+    /// Get the default [Commitment]
+    pub fn default_commitment(&self) -> Commitment {
+        self.default_commitment
+    }
+
+    /// Set a header sent with the requests of the RPC helpers like [Self::get_balance]
+    /// and of [Self::rpc_client], for example an API key required by a private RPC provider.
+    /// Setting a header with the same name again replaces its value
+    pub fn set_rpc_header(&mut self, name: &str, value: &str) -> &mut Self {
+        self.rpc_headers
+            .retain(|(existing, _)| !existing.eq_ignore_ascii_case(name));
+        self.rpc_headers.push((name.to_string(), value.to_string()));
+
+        self
+    }
+
+    /// Get a [RpcClient] sending requests to the endpoint of the `cluster`
+    /// with the headers set by [Self::set_rpc_header]
+    pub fn rpc_client(&self, cluster: Cluster) -> RpcClient {
+        self.rpc_headers.iter().fold(
+            RpcClient::new(cluster.endpoint()),
+            |client, (name, value)| client.with_header(name, value),
+        )
+    }
+
+    /// Get the balance in lamports of the account with the `public_key`
+    /// from the `cluster` using the [Self::default_commitment]
+    pub async fn get_balance(
+        &self,
+        public_key: [u8; 32],
+        cluster: Cluster,
+    ) -> WalletResult<Lamports> {
+        let params = Array::of2(
+            &bs58::encode(public_key).into_string().into(),
+            &RpcClient::commitment_config(self.default_commitment)?,
+        );
+
+        let result = self
+            .rpc_client(cluster)
+            .call("getBalance", params.into())
+            .await?;
+
+        Ok(Lamports(Reflection::new(result)?.u64("value")?))
+    }
+
+    /// Get the address of the associated token account holding the tokens of the `mint`
+    /// for the connected account, see [crate::associated_token_address]
+    pub async fn associated_token_address(&self, mint: &[u8; 32]) -> WalletResult<[u8; 32]> {
+        let owner = self
+            .connection_info()
+            .await
+            .connected_account()?
+            .public_key();
+
+        Ok(crate::associated_token_address(&owner, mint)?)
+    }
+
+    /// Subscribe to the changes of the account with the `public_key` on the `cluster`
+    /// at the `commitment` using an `accountSubscribe` websocket subscription,
+    /// for example to show a live balance. Every change is pushed as an [AccountUpdate]
+    /// to the returned [Receiver]. Closing or dropping the receiver unsubscribes
+    /// and closes the websocket, while the receiver is closed if the cluster closes the websocket
+    pub fn subscribe_account(
+        &self,
+        public_key: &[u8; 32],
+        cluster: Cluster,
+        commitment: Commitment,
+    ) -> WalletResult<Receiver<AccountUpdate>> {
+        open_account_subscription(
+            cluster.ws_endpoint(),
+            public_key,
+            commitment,
+            self.logger.clone(),
+        )
+    }
+
+    /// Get the latest blockhash encoded as a base58 string
+    /// from the `cluster` using the [Self::default_commitment]
+    pub async fn get_latest_blockhash(&self, cluster: Cluster) -> WalletResult<String> {
+        let params = Array::of1(&RpcClient::commitment_config(self.default_commitment)?);
+
+        let result = self
+            .rpc_client(cluster)
+            .call("getLatestBlockhash", params.into())
+            .await?;
+
+        Reflection::new_from_str(&result, "value")?.string("blockhash")
+    }
+
+    /// Wait until the transaction with the `signature` reaches the [Self::default_commitment]
+    /// on the `cluster` by polling `getSignatureStatuses`. Fails with
+    /// [WalletError::TransactionFailed] if the transaction failed and with
+    /// [WalletError::ConfirmationTimeout] if it is not confirmed within the `timeout`
+    pub async fn confirm_transaction(
+        &self,
+        signature: &Signature,
+        cluster: Cluster,
+        timeout: Duration,
+    ) -> WalletResult<()> {
+        self.confirm_transaction_at(signature, cluster, self.default_commitment, timeout)
+            .await
+    }
+
+    async fn confirm_transaction_at(
+        &self,
+        signature: &Signature,
+        cluster: Cluster,
+        commitment: Commitment,
+        timeout: Duration,
+    ) -> WalletResult<()> {
+        let client = self.rpc_client(cluster);
+        let signatures = Array::of1(&WalletCommonUtils::base58_signature(*signature).into());
+
+        InnerUtils::race(
+            async {
+                loop {
+                    let result = client
+                        .call("getSignatureStatuses", Array::of1(&signatures).into())
+                        .await?;
+                    let status = Reflection::new_from_str(&result, "value")?
+                        .into_array()?
+                        .get(0);
+
+                    // `null` until the cluster has seen the transaction
+                    if let Ok(status) = Reflection::new(status) {
+                        if let Ok(error) = status.reflect_inner("err") {
+                            return Err(WalletError::TransactionFailed(Reflection::get_string(
+                                &JSON::stringify(&error)?.into(),
+                            )?));
+                        }
+
+                        let reached = status
+                            .string_optional("confirmationStatus")?
+                            .map(|reached| Commitment::try_from(reached.as_str()))
+                            .transpose()?;
+
+                        if reached.is_some_and(|reached| reached >= commitment) {
+                            return Ok(());
+                        }
+                    }
+
+                    InnerUtils::sleep(CONFIRMATION_POLL_INTERVAL).await?;
+                }
+            },
+            async {
+                InnerUtils::sleep(timeout).await?;
+
+                Err(WalletError::ConfirmationTimeout(timeout))
+            },
+        )
+        .await
+    }
+
+    /// Get the current slot of the `cluster` at the `commitment`
+    pub async fn get_slot(&self, cluster: Cluster, commitment: Commitment) -> WalletResult<u64> {
+        let params = Array::of1(&RpcClient::commitment_config(commitment)?);
+
+        let result = self
+            .rpc_client(cluster)
+            .call("getSlot", params.into())
+            .await?;
+
+        Reflection::number_to_u64(&result, "result")?
+            .ok_or(WalletError::ExpectedValueNotFound("result".to_string()))
+    }
+
+    /// Get the genesis hash encoded as a base58 string from the `cluster`
+    pub async fn get_genesis_hash(&self, cluster: Cluster) -> WalletResult<String> {
+        let result = self
+            .rpc_client(cluster)
+            .call("getGenesisHash", Array::new().into())
+            .await?;
+
+        Reflection::get_string(&result)
+    }
+
+    /// Verify that the endpoint of the `cluster` serves the expected network by comparing
+    /// its genesis hash with the [known genesis hash](Cluster::genesis_hash) of the `cluster`.
+    /// Returns a [WalletError::UnsupportedChain] error for [Cluster::LocalNet]
+    /// since a local validator has no known genesis hash
+    pub async fn verify_cluster(&self, cluster: Cluster) -> WalletResult<bool> {
+        let expected = cluster
+            .genesis_hash()
+            .ok_or(WalletError::UnsupportedChain(cluster.chain().to_string()))?;
+
+        Ok(self.get_genesis_hash(cluster).await? == expected)
+    }
+
+    /// Get the prioritization fees in micro-lamports per compute unit paid by transactions
+    /// in the recent slots that lock all the writable `accounts` on the `cluster`,
+    /// one fee per slot. The fees of all transactions are returned if `accounts` is empty
+    /// and an empty [Vec] is returned if the cluster has no recent data
+    pub async fn get_recent_prioritization_fees(
+        &self,
+        cluster: Cluster,
+        accounts: &[[u8; 32]],
+    ) -> WalletResult<Vec<u64>> {
+        let addresses = accounts
+            .iter()
+            .map(|account| JsValue::from(bs58::encode(account).into_string()))
+            .collect::<Array>();
+
+        let result = self
+            .rpc_client(cluster)
+            .call("getRecentPrioritizationFees", Array::of1(&addresses).into())
+            .await?;
+
+        if result.is_null() {
+            return Ok(Vec::default());
+        }
+
+        Reflection::new(result)?
+            .into_array()?
+            .iter()
+            .map(|entry| Reflection::new(entry)?.u64("prioritizationFee"))
+            .collect()
+    }
+
+    /// Simulate the serialized transaction `tx_bytes` on the `cluster` using the
+    /// [Self::default_commitment] before asking the user to sign it.
+    /// Signatures are not verified so the transaction can be unsigned.
+    /// A transaction that would fail is reported in [SimulationResult::err]
+    /// instead of failing this method
+    pub async fn simulate_transaction(
+        &self,
+        tx_bytes: &[u8],
+        cluster: Cluster,
+    ) -> WalletResult<SimulationResult> {
+        let mut config = Reflection::new(RpcClient::commitment_config(self.default_commitment)?)?;
+        config.set_object_str("encoding", "base64")?;
+        config.set_object(&"sigVerify".into(), &false.into())?;
+
+        let params = Array::of2(&to_base64(tx_bytes).into(), &config.take());
+
+        let result = self
+            .rpc_client(cluster)
+            .call("simulateTransaction", params.into())
+            .await?;
+
+        SimulationResult::parse(Reflection::new(result)?.reflect_inner("value")?)
+    }
+
+    /// Get the fee in lamports the `cluster` would charge for the serialized `message_bytes`
+    /// at the `commitment`, for example to show the expected fee before the user signs.
+    /// [None] is returned if the blockhash of the message is too old
+    pub async fn get_fee_for_message(
+        &self,
+        message_bytes: &[u8],
+        cluster: Cluster,
+        commitment: Commitment,
+    ) -> WalletResult<Option<u64>> {
+        let params = Array::of2(
+            &to_base64(message_bytes).into(),
+            &RpcClient::commitment_config(commitment)?,
+        );
+
+        let result = self
+            .rpc_client(cluster)
+            .call("getFeeForMessage", params.into())
+            .await?;
+
+        Reflection::new(result)?.opt_u64("value")
+    }
+
+    fn check_message_size(&self, message: &[u8]) -> WalletResult<()> {
+        if message.len() > self.max_message_bytes {
+            return Err(WalletError::MessageTooLarge(message.len()));
+        }
+
+        Ok(())
+    }
+
+    /// Get the most recent [WalletEvent] that changed the connection state,
+    /// that is `Connected`, `Reconnected`, `Disconnected` or `AccountChanged`.
+    /// Useful for components that start listening to [Self::events] after
+    /// the event was already emitted
+    pub async fn last_event(&self) -> Option<WalletEvent> {
+        self.connection_info().await.last_event().cloned()
+    }
+
+    /// Check if an [account](WalletAccount) is connected
+    pub async fn is_connected(&self) -> bool {
+        self.connection_info
+            .as_ref()
+            .write()
+            .await
+            .account
+            .is_some()
     }
 
     /// Get the connected [ConnectionInfo] containing the
@@ -408,6 +1726,63 @@ impl WalletAdapter {
         self.connection_info.as_ref().read().await
     }
 
+    /// Get the public keys in `previous`, for example a snapshot of
+    /// [ConnectionInfo::authorized_accounts] taken before reconnecting or before a `change`
+    /// event of the wallet, that are no longer authorized by the user, so that the dapp can
+    /// invalidate data cached for them
+    pub async fn accounts_removed_since(&self, previous: &[[u8; 32]]) -> Vec<[u8; 32]> {
+        let connection_info = self.connection_info().await;
+        let authorized = connection_info.authorized_accounts();
+
+        previous
+            .iter()
+            .filter(|public_key| !authorized.contains(public_key))
+            .copied()
+            .collect()
+    }
+
+    /// Clear the accounts that were connected before the connected account,
+    /// for example for a "clear session" action, without disconnecting
+    pub async fn clear_account_history(&self) {
+        self.connection_info.write().await.clear_previous_accounts();
+    }
+
+    /// Get a [ConnectionSummary] of the connected wallet and account, read while holding the
+    /// lock on the [ConnectionInfo] once instead of awaiting a getter for each field
+    pub async fn summary(&self) -> ConnectionSummary {
+        let connection_info = self.connection_info().await;
+
+        let wallet = connection_info.connected_wallet().ok();
+        let account = connection_info.connected_account().ok();
+
+        let clusters = wallet
+            .map(|wallet| {
+                [
+                    (Cluster::MainNet, wallet.mainnet()),
+                    (Cluster::DevNet, wallet.devnet()),
+                    (Cluster::LocalNet, wallet.localnet()),
+                    (Cluster::TestNet, wallet.testnet()),
+                ]
+                .into_iter()
+                .filter_map(|(cluster, supported)| supported.then_some(cluster))
+                .collect()
+            })
+            .unwrap_or_default();
+
+        ConnectionSummary {
+            connected: account.is_some(),
+            wallet_name: wallet.map(|wallet| wallet.name().to_string()),
+            account_address: account.map(|account| account.address().to_string()),
+            shortened_address: account
+                .and_then(|account| account.shorten_address().ok())
+                .map(|shortened| shortened.to_string()),
+            icon: wallet
+                .and_then(|wallet| wallet.icon())
+                .map(|icon| icon.to_string()),
+            clusters,
+        }
+    }
+
     /// Get an entry in the `Window` object
     pub fn get_entry(&self, property: &str) -> Option<Object> {
         self.window.get(property)
@@ -448,26 +1823,155 @@ impl WalletAdapter {
         Ok(clusters)
     }
 
+    /// Get the [WalletAccount] of the connected wallet corresponding to a [PortableAccount]
+    /// by matching the public key. Returns [WalletError::AccountNotFound]
+    /// if the connected wallet no longer provides the account
+    pub async fn restore_account(&self, portable: &PortableAccount) -> WalletResult<WalletAccount> {
+        self.connection_info()
+            .await
+            .account_by_pubkey(portable.public_key)
+            .cloned()
+            .ok_or(WalletError::AccountNotFound)
+    }
+
+    /// Get the clusters supported by the connected account.
+    /// An account may support fewer clusters than the wallet
+    /// so this is the set of clusters the user can actually transact on
+    pub async fn account_clusters(&self) -> WalletResult<Vec<Cluster>> {
+        let connection_info = self.connection_info().await;
+        let account = connection_info.connected_account()?;
+
+        let mut clusters = Vec::<Cluster>::default();
+
+        if account.mainnet() {
+            clusters.push(Cluster::MainNet);
+        }
+        if account.devnet() {
+            clusters.push(Cluster::DevNet);
+        }
+        if account.localnet() {
+            clusters.push(Cluster::LocalNet);
+        }
+        if account.testnet() {
+            clusters.push(Cluster::TestNet);
+        }
+
+        Ok(clusters)
+    }
+
     /// Get the registered wallets
     pub fn wallets(&self) -> Vec<Wallet> {
         self.storage.borrow().get_wallets()
     }
 
-    /// Get a certain wallet by its name
-    pub fn get_wallet(&self, wallet_name: &str) -> WalletResult<Wallet> {
-        self.storage
-            .get_wallet(wallet_name)
-            .ok_or(WalletError::WalletNotFound)
+    /// Iterate over the registered wallets. The wallets are currently cloned
+    /// out of the storage up front so that the storage is not borrowed while iterating,
+    /// only the iterator is part of the API so this may become lazy in the future
+    pub fn iter_wallets(&self) -> impl Iterator<Item = Wallet> {
+        self.wallets().into_iter()
     }
 
-    /// Check if the connected wallet supports mainnet cluster
-    pub async fn mainnet(&self) -> WalletResult<bool> {
-        Ok(self.connection_info().await.connected_wallet()?.mainnet())
+    /// Group the registered wallets by the clusters they support, for example to show
+    /// a section of wallets per network. A wallet supporting several clusters
+    /// is part of the group of each cluster. See [Self::wallets_by_cluster_ordered]
+    /// for a stable order
+    pub fn wallets_by_cluster(&self) -> HashMap<Cluster, Vec<Wallet>> {
+        let mut groups = HashMap::<Cluster, Vec<Wallet>>::new();
+
+        self.iter_wallets().for_each(|wallet| {
+            wallet.chains().iter().for_each(|cluster| {
+                groups.entry(*cluster).or_default().push(wallet.clone());
+            })
+        });
+
+        groups
     }
 
-    /// Check if the connected wallet supports devnet cluster
-    pub async fn devnet(&self) -> WalletResult<bool> {
-        Ok(self.connection_info().await.connected_wallet()?.devnet())
+    /// Same as [Self::wallets_by_cluster] but the clusters are ordered from
+    /// [Cluster::MainNet] to [Cluster::LocalNet] and the wallets of each cluster
+    /// are sorted by name, so the groups can be rendered without flickering
+    pub fn wallets_by_cluster_ordered(&self) -> BTreeMap<Cluster, Vec<Wallet>> {
+        self.wallets_by_cluster()
+            .into_iter()
+            .map(|(cluster, mut wallets)| {
+                wallets.sort_by(|left, right| left.name().cmp(right.name()));
+
+                (cluster, wallets)
+            })
+            .collect()
+    }
+
+    /// Build the [MockWallet](crate::MockWallet) and register it like a browser extension
+    /// would, for local development without installing a wallet.
+    /// Requires the `dev` feature
+    #[cfg(feature = "dev")]
+    pub fn register_mock_wallet(&self, mock: crate::MockWallet) -> WalletResult<()> {
+        WalletStorage::register(&self.storage.clone_inner(), mock.build()?);
+
+        // A full channel already has a pending notification
+        let _ = self.wallet_registered.0.try_send(());
+
+        Ok(())
+    }
+
+    /// Get the names of the registered wallets sorted alphabetically,
+    /// useful when only a list of wallets to choose from is rendered
+    pub fn wallet_names(&self) -> Vec<String> {
+        self.storage.wallet_names()
+    }
+
+    /// Get the number of `[standard:events]` listeners that are still registered
+    /// with a wallet. At most one listener, the one of the connected wallet, is active
+    /// since disconnecting or reconnecting unsubscribes the previous listener
+    pub fn active_event_listeners(&self) -> usize {
+        self.active_event_listeners.load(Ordering::SeqCst)
+    }
+
+    /// Get the [WalletReadyState] of a wallet by its name.
+    /// A wallet in storage is [WalletReadyState::Installed], one of the
+    /// [known wallets](crate::known_wallets) that is not in storage is
+    /// [WalletReadyState::Loadable], otherwise it is [WalletReadyState::NotDetected]
+    pub fn ready_state(&self, wallet_name: &str) -> WalletReadyState {
+        if self.storage.get_wallet(wallet_name).is_some() {
+            WalletReadyState::Installed
+        } else if known_wallets()
+            .iter()
+            .any(|known_wallet| known_wallet.is_named(wallet_name))
+        {
+            WalletReadyState::Loadable
+        } else {
+            WalletReadyState::NotDetected
+        }
+    }
+
+    /// Get the [known wallets](crate::known_wallets) that are not installed,
+    /// for example to show install prompts in a wallet picker
+    pub fn installable_wallets(&self) -> Vec<KnownWallet> {
+        self.storage.missing_known_wallets()
+    }
+
+    /// Get a certain wallet by its name
+    pub fn get_wallet(&self, wallet_name: &str) -> WalletResult<Wallet> {
+        self.storage
+            .get_wallet(wallet_name)
+            .ok_or(WalletError::WalletNotFound)
+    }
+
+    /// Check if a wallet in storage supports a certain [WalletFeature].
+    /// Unlike the other support checks, the wallet does not need to be connected
+    /// which is useful when showing the user a list of wallets to choose from
+    pub fn wallet_supports(&self, wallet_name: &str, feature: WalletFeature) -> WalletResult<bool> {
+        Ok(self.get_wallet(wallet_name)?.supports(feature))
+    }
+
+    /// Check if the connected wallet supports mainnet cluster
+    pub async fn mainnet(&self) -> WalletResult<bool> {
+        Ok(self.connection_info().await.connected_wallet()?.mainnet())
+    }
+
+    /// Check if the connected wallet supports devnet cluster
+    pub async fn devnet(&self) -> WalletResult<bool> {
+        Ok(self.connection_info().await.connected_wallet()?.devnet())
     }
 
     /// Check if the connected wallet supports testnet cluster
@@ -542,6 +2046,15 @@ impl WalletAdapter {
             .connected_wallet()?
             .solana_sign_transaction())
     }
+
+    /// Check if the connected wallet supports `solana:signAllTransactions` feature
+    pub async fn solana_sign_all_transactions(&self) -> WalletResult<bool> {
+        Ok(self
+            .connection_info()
+            .await
+            .connected_wallet()?
+            .solana_sign_all_transactions())
+    }
 }
 
 impl PartialEq for WalletAdapter {
@@ -552,3 +2065,2563 @@ impl PartialEq for WalletAdapter {
     }
 }
 impl Eq for WalletAdapter {}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod wallet_supports_checks {
+    use sha3::{Digest, Sha3_256};
+    use wallet_adapter_common::{feature_support::FeatureSupport, WalletData};
+    use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+    use super::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    async fn stored_wallet_not_connected() {
+        let adapter = WalletAdapter::init().unwrap();
+
+        let wallet = Wallet {
+            data: WalletData::new()
+                .set_name("Foo Wallet")
+                .set_supported_features(FeatureSupport {
+                    connect: true,
+                    sign_message: true,
+                    ..Default::default()
+                }),
+            ..Default::default()
+        };
+        let hash: [u8; 32] = Sha3_256::digest("foo wallet".as_bytes()).into();
+        adapter
+            .storage()
+            .clone_inner()
+            .borrow_mut()
+            .insert(hash, wallet);
+
+        assert!(!adapter.is_connected().await);
+        assert_eq!(
+            Ok(true),
+            adapter.wallet_supports("Foo Wallet", WalletFeature::Connect)
+        );
+        assert_eq!(
+            Ok(true),
+            adapter.wallet_supports("foo wallet", WalletFeature::SignMessage)
+        );
+        assert_eq!(
+            Ok(false),
+            adapter.wallet_supports("Foo Wallet", WalletFeature::SignIn)
+        );
+        assert_eq!(
+            Err(WalletError::WalletNotFound),
+            adapter.wallet_supports("Bar Wallet", WalletFeature::Connect)
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod connect_retry_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys;
+
+    use super::*;
+    use crate::test_support::{mock_count, TestWallet};
+
+    #[wasm_bindgen_test]
+    async fn fails_twice_then_succeeds() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        let wallet = TestWallet::new().set_connect_failures(2, "Error").build();
+
+        let account = adapter
+            .connect_with_retry(wallet.clone(), 3, Duration::from_millis(1))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            account.address(),
+            "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi"
+        );
+        assert_eq!(mock_count(&wallet, "mockCalls"), 3.0);
+        assert!(adapter.is_connected().await);
+    }
+
+    #[wasm_bindgen_test]
+    async fn gives_up_after_attempts() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        let wallet = TestWallet::new().set_connect_failures(2, "Error").build();
+
+        let outcome = adapter
+            .connect_with_retry(wallet.clone(), 2, Duration::from_millis(1))
+            .await;
+
+        assert!(matches!(outcome, Err(WalletError::WalletConnectError(_))));
+        assert_eq!(mock_count(&wallet, "mockCalls"), 2.0);
+    }
+
+    #[wasm_bindgen_test]
+    async fn user_rejection_is_not_retried() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        let wallet = TestWallet::new()
+            .set_connect_failures(2, "UserRejectedRequestError")
+            .build();
+
+        let outcome = adapter
+            .connect_with_retry(wallet.clone(), 3, Duration::from_millis(1))
+            .await;
+
+        assert!(outcome.unwrap_err().is_user_rejection());
+        assert_eq!(mock_count(&wallet, "mockCalls"), 1.0);
+    }
+
+    #[wasm_bindgen_test]
+    async fn sleep_waits() {
+        let start = js_sys::Date::now();
+
+        InnerUtils::sleep(Duration::from_millis(20)).await.unwrap();
+
+        assert!(js_sys::Date::now() - start >= 19.0);
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod account_clusters_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+    use crate::test_support::TestWallet;
+
+    #[wasm_bindgen_test]
+    async fn account_supports_fewer_clusters() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        let wallet = TestWallet::new().build();
+
+        assert_eq!(
+            Err(WalletError::AccountNotFound),
+            adapter.account_clusters().await
+        );
+
+        adapter.connect(wallet).await.unwrap();
+
+        assert_eq!(
+            Ok(vec![Cluster::MainNet, Cluster::DevNet, Cluster::TestNet]),
+            adapter.clusters().await
+        );
+        assert_eq!(Ok(vec![Cluster::DevNet]), adapter.account_clusters().await);
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod account_override_checks {
+    use ed25519_dalek::{Signer, SigningKey};
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::{Array, Uint8Array};
+
+    use super::*;
+    use crate::{
+        test_support::{account_object, multi_account_wallet},
+        Reflection,
+    };
+
+    const MESSAGE: &[u8] = b"Sign with a non-active account";
+
+    #[wasm_bindgen_test]
+    async fn sign_with_non_active_account() {
+        let signing_keys = [
+            SigningKey::from_bytes(&[1u8; 32]),
+            SigningKey::from_bytes(&[2u8; 32]),
+        ];
+        let accounts = Array::new();
+        let signatures = Array::new();
+        signing_keys.iter().for_each(|signing_key| {
+            accounts.push(&account_object(signing_key));
+            signatures.push(&Uint8Array::from(
+                signing_key.sign(MESSAGE).to_bytes().as_slice(),
+            ));
+        });
+
+        let mut adapter = WalletAdapter::init().unwrap();
+        let wallet = multi_account_wallet(&accounts, &signatures);
+        let non_active_account = wallet.accounts()[1].clone();
+
+        let active_account = adapter.connect(wallet).await.unwrap();
+        assert_eq!(
+            active_account.public_key(),
+            signing_keys[0].verifying_key().to_bytes()
+        );
+
+        let signed = adapter
+            .sign_message_with_account(MESSAGE, &non_active_account)
+            .await
+            .unwrap();
+        assert_eq!(
+            signed.public_key().unwrap(),
+            signing_keys[1].verifying_key()
+        );
+
+        let signed_txs = adapter
+            .sign_transaction_with_account(&[[7u8, 7]], None, &non_active_account)
+            .await
+            .unwrap();
+        assert_eq!(signed_txs, vec![vec![7u8, 7, 1]]);
+
+        let foreign_account = WalletAccount::parse(
+            Reflection::new(account_object(&SigningKey::from_bytes(&[3u8; 32])).into()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            Some(WalletError::AccountNotInWallet),
+            adapter
+                .sign_message_with_account(MESSAGE, &foreign_account)
+                .await
+                .err()
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod last_event_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+    use crate::test_support::TestWallet;
+
+    #[wasm_bindgen_test]
+    async fn last_event_reflects_connection() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        let wallet = TestWallet::new().build();
+
+        assert_eq!(None, adapter.last_event().await);
+
+        let account = adapter.connect(wallet).await.unwrap();
+        assert_eq!(
+            Some(WalletEvent::Connected(account)),
+            adapter.last_event().await
+        );
+
+        adapter.disconnect().await;
+        assert_eq!(Some(WalletEvent::Disconnected), adapter.last_event().await);
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod sign_all_transactions_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::{
+        js_sys::{Function, Reflect},
+        wasm_bindgen::JsValue,
+    };
+
+    use super::*;
+    use crate::test_support::TestWallet;
+
+    // A wallet that "signs" a transaction by appending `1` when using `solana:signTransaction`
+    // and `2` when using `solana:signAllTransactions`. The number of requests
+    // to the wallet is exposed by the returned function
+    fn signing_wallet(sign_all: bool) -> (Wallet, Function) {
+        let sign_features = Function::new_no_args(
+            r#"
+            let requests = 0;
+            const sign = (marker) => async (...inputs) => {
+                requests += 1;
+
+                return inputs.map(({ transaction }) => ({
+                    signedTransaction: new Uint8Array([...transaction, marker]),
+                }));
+            };
+
+            return {
+                signTransaction: {
+                    version: "1.0.0",
+                    supportedTransactionVersions: ["legacy", 0],
+                    signTransaction: sign(1),
+                },
+                signAllTransactions: {
+                    version: "1.0.0",
+                    supportedTransactionVersions: ["legacy", 0],
+                    signAllTransactions: sign(2),
+                },
+                requests: () => requests,
+            };
+            "#,
+        )
+        .call0(&JsValue::null())
+        .unwrap();
+        let sign_feature = |name: &str| Reflect::get(&sign_features, &name.into()).unwrap();
+
+        let mut wallet = TestWallet::new();
+        wallet.add_feature("solana:signTransaction", &sign_feature("signTransaction"));
+        if sign_all {
+            wallet.add_feature(
+                "solana:signAllTransactions",
+                &sign_feature("signAllTransactions"),
+            );
+        }
+
+        (wallet.build(), sign_feature("requests").into())
+    }
+
+    fn requests(mock_requests: &Function) -> f64 {
+        mock_requests
+            .call0(&JsValue::null())
+            .unwrap()
+            .as_f64()
+            .unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    async fn native_sign_all_transactions() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        let (wallet, mock_requests) = signing_wallet(true);
+        adapter.connect(wallet).await.unwrap();
+
+        assert_eq!(Ok(true), adapter.solana_sign_all_transactions().await);
+
+        let signed_txs = adapter
+            .sign_all_transactions_or_fallback(&[[7u8], [8u8]], Some(Cluster::DevNet))
+            .await
+            .unwrap();
+
+        assert_eq!(signed_txs, vec![vec![7u8, 2], vec![8u8, 2]]);
+        assert_eq!(requests(&mock_requests), 1.0);
+    }
+
+    #[wasm_bindgen_test]
+    async fn fallback_sign_transaction() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        let events = adapter.events();
+        let (wallet, mock_requests) = signing_wallet(false);
+        adapter.connect(wallet).await.unwrap();
+
+        assert_eq!(Ok(false), adapter.solana_sign_all_transactions().await);
+        assert_eq!(
+            Err(WalletError::MissingSignAllTransactionsFunction),
+            adapter.sign_all_transactions(&[[7u8]], None).await
+        );
+
+        let signed_txs = adapter
+            .sign_all_transactions_or_fallback(&[[7u8], [8u8]], Some(Cluster::DevNet))
+            .await
+            .unwrap();
+
+        assert_eq!(signed_txs, vec![vec![7u8, 1], vec![8u8, 1]]);
+        assert_eq!(requests(&mock_requests), 2.0);
+
+        assert!(matches!(events.recv().await, Ok(WalletEvent::Connected(_))));
+        assert_eq!(
+            Ok(WalletEvent::MultipleApprovalsRequired(2)),
+            events.recv().await
+        );
+    }
+    #[wasm_bindgen_test]
+    async fn chunked_preserves_order() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        let (wallet, mock_requests) = signing_wallet(true);
+        adapter.connect(wallet).await.unwrap();
+
+        let transactions = (0u8..5).map(|index| [index]).collect::<Vec<[u8; 1]>>();
+        let signed_txs = adapter
+            .sign_all_transactions_chunked(&transactions, Some(Cluster::DevNet), 2)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            signed_txs,
+            (0u8..5)
+                .map(|index| vec![index, 2])
+                .collect::<Vec<Vec<u8>>>()
+        );
+        assert_eq!(requests(&mock_requests), 3.0);
+
+        assert_eq!(
+            Err(WalletError::InvalidChunkSize),
+            adapter
+                .sign_all_transactions_chunked(&transactions, None, 0)
+                .await
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod ready_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::{
+        js_sys::{self, Function},
+        wasm_bindgen::JsValue,
+    };
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    async fn wallet_registers_within_grace() {
+        let adapter = WalletAdapter::init().unwrap();
+
+        // Register a wallet after a short delay like a browser extension would
+        Function::new_no_args(
+            r#"
+            setTimeout(() => {
+                const wallet = {
+                    name: "Late Wallet",
+                    version: "1.0.0",
+                    chains: ["solana:devnet"],
+                    accounts: [],
+                    features: {},
+                };
+
+                window.dispatchEvent(
+                    new CustomEvent("wallet-standard:register-wallet", {
+                        detail: ({ register }) => register(wallet),
+                    })
+                );
+            }, 20);
+            "#,
+        )
+        .call0(&JsValue::null())
+        .unwrap();
+
+        let start = js_sys::Date::now();
+        adapter.ready(Duration::from_secs(5)).await;
+
+        assert!(js_sys::Date::now() - start < 5_000.0);
+        assert!(adapter.get_wallet("Late Wallet").is_ok());
+    }
+
+    #[wasm_bindgen_test]
+    async fn grace_elapses_without_wallets() {
+        let adapter = WalletAdapter::init().unwrap();
+
+        let start = js_sys::Date::now();
+        adapter.ready(Duration::from_millis(20)).await;
+
+        assert!(js_sys::Date::now() - start >= 19.0);
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod portable_account_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+    use crate::test_support::TestWallet;
+
+    #[wasm_bindgen_test]
+    async fn portable_account_round_trip() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        let wallet = TestWallet::new().build();
+        let account = adapter.connect(wallet).await.unwrap();
+
+        let portable = account.to_portable();
+        assert_eq!(portable.address, account.address());
+        assert_eq!(portable.public_key, account.public_key());
+        assert_eq!(portable.chains, account.chains());
+
+        assert_eq!(Ok(account), adapter.restore_account(&portable).await);
+
+        let removed = PortableAccount {
+            public_key: [2u8; 32],
+            ..portable
+        };
+        assert_eq!(
+            Err(WalletError::AccountNotFound),
+            adapter.restore_account(&removed).await
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod max_message_bytes_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    async fn max_message_bytes() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        assert_eq!(DEFAULT_MAX_MESSAGE_BYTES, adapter.max_message_bytes());
+
+        adapter.set_max_message_bytes(8);
+
+        // Messages within the limit reach the wallet which is not connected
+        assert_eq!(
+            Some(WalletError::WalletNotFound),
+            adapter.sign_message(&[0u8; 7]).await.err()
+        );
+        assert_eq!(
+            Some(WalletError::WalletNotFound),
+            adapter.sign_message(&[0u8; 8]).await.err()
+        );
+        assert_eq!(
+            Some(WalletError::MessageTooLarge(9)),
+            adapter.sign_message(&[0u8; 9]).await.err()
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod event_seq_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+    use crate::test_support::TestWallet;
+
+    #[wasm_bindgen_test]
+    async fn event_seq_increases() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        let events = adapter.events_seq();
+        let wallet = TestWallet::new().build();
+
+        let account = adapter.connect(wallet.clone()).await.unwrap();
+        adapter.disconnect().await;
+        adapter.connect(wallet).await.unwrap();
+
+        let first = events.recv().await.unwrap();
+        let second = events.recv().await.unwrap();
+        let third = events.recv().await.unwrap();
+
+        assert_eq!(WalletEvent::Connected(account), first.event);
+        assert_eq!(WalletEvent::Disconnected, second.event);
+        assert!(first.seq < second.seq);
+        assert!(second.seq < third.seq);
+        assert_eq!(3, third.seq);
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod response_inspector_checks {
+    use std::cell::RefCell;
+
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+    use crate::test_support::TestWallet;
+    use crate::Reflection;
+
+    #[wasm_bindgen_test]
+    async fn inspector_receives_response() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        let wallet = TestWallet::new().build();
+
+        let responses = Rc::new(RefCell::new(Vec::<JsValue>::new()));
+        let responses_inner = responses.clone();
+        adapter
+            .set_response_inspector(Rc::new(move |response: &JsValue| {
+                responses_inner.borrow_mut().push(response.clone())
+            }))
+            .await;
+
+        adapter.connect(wallet).await.unwrap();
+
+        let responses = responses.take();
+        assert_eq!(responses.len(), 1);
+
+        let accounts = Reflection::new(responses[0].clone())
+            .unwrap()
+            .reflect_js_array("accounts")
+            .unwrap();
+        assert_eq!(accounts.length(), 1);
+    }
+
+    #[wasm_bindgen_test]
+    async fn applies_to_connected_wallet() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        let wallet = TestWallet::new().build();
+        adapter.connect(wallet).await.unwrap();
+
+        let responses = Rc::new(RefCell::new(Vec::<JsValue>::new()));
+        let responses_inner = responses.clone();
+        adapter
+            .set_response_inspector(Rc::new(move |response: &JsValue| {
+                responses_inner.borrow_mut().push(response.clone())
+            }))
+            .await;
+
+        adapter
+            .connection_info()
+            .await
+            .connected_wallet()
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+
+        assert_eq!(responses.take().len(), 1);
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod default_commitment_checks {
+    use ed25519_dalek::SigningKey;
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::{self, Reflect, Uint8Array};
+
+    use super::*;
+    use crate::rpc::rpc_checks::MockFetch;
+    use crate::test_support::{account_object, multi_account_wallet};
+
+    const SIGNATURE: [u8; 64] = [9u8; 64];
+
+    fn status_response(status: &str) -> MockFetch {
+        MockFetch::with_result(&format!(r#"{{"context":{{"slot":1}},"value":[{status}]}}"#))
+    }
+
+    fn requested_commitment(request: &Reflection) -> String {
+        let params = request.reflect_js_array("params").unwrap();
+
+        Reflection::new(params.get(params.length() - 1))
+            .unwrap()
+            .string("commitment")
+            .unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    async fn rpc_uses_default_commitment() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        assert_eq!(adapter.default_commitment(), Commitment::Finalized);
+
+        let mock = MockFetch::with_result(
+            r#"{"context":{"slot":1},"value":{"blockhash":"EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N","lastValidBlockHeight":3}}"#,
+        );
+        assert_eq!(
+            adapter.get_latest_blockhash(Cluster::DevNet).await.unwrap(),
+            "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N"
+        );
+        assert_eq!(requested_commitment(&mock.requests()[0]), "finalized");
+        drop(mock);
+
+        adapter.set_default_commitment(Commitment::Confirmed);
+
+        let mock = MockFetch::with_result(r#"{"context":{"slot":1},"value":42}"#);
+        assert_eq!(
+            adapter
+                .get_balance([1u8; 32], Cluster::DevNet)
+                .await
+                .unwrap(),
+            Lamports(42)
+        );
+
+        let request = &mock.requests()[0];
+        assert_eq!(request.string("method").unwrap(), "getBalance");
+        assert_eq!(requested_commitment(request), "confirmed");
+    }
+
+    #[wasm_bindgen_test]
+    async fn explicit_preflight_commitment_kept() {
+        let accounts = Array::of1(&account_object(&SigningKey::from_bytes(&[1u8; 32])));
+        let signatures = Array::of1(&Uint8Array::from(SIGNATURE.as_slice()));
+
+        let mut adapter = WalletAdapter::init().unwrap();
+        adapter.set_default_commitment(Commitment::Confirmed);
+        adapter
+            .connect(multi_account_wallet(&accounts, &signatures))
+            .await
+            .unwrap();
+
+        let sent_commitment = || {
+            let options = Reflect::get(&js_sys::global(), &"mockSendOptions".into()).unwrap();
+
+            Reflection::new(options)
+                .unwrap()
+                .string("preflightCommitment")
+                .unwrap()
+        };
+
+        adapter
+            .sign_and_send_transaction(&[1, 2], Cluster::DevNet, SendOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(sent_commitment(), "confirmed");
+
+        // An explicit commitment equal to the default of SendOptions is not overridden
+        let mut options = SendOptions::default();
+        options.set_preflight_commitment(Commitment::Finalized);
+        adapter
+            .sign_and_send_transaction(&[1, 2], Cluster::DevNet, options)
+            .await
+            .unwrap();
+        assert_eq!(sent_commitment(), "finalized");
+    }
+
+    #[wasm_bindgen_test]
+    async fn sign_and_send_waits_for_confirm_commitment() {
+        let accounts = Array::of1(&account_object(&SigningKey::from_bytes(&[1u8; 32])));
+        let signatures = Array::of1(&Uint8Array::from(SIGNATURE.as_slice()));
+        let timeout = Duration::from_millis(50);
+
+        let mut adapter = WalletAdapter::init().unwrap();
+        adapter
+            .connect(multi_account_wallet(&accounts, &signatures))
+            .await
+            .unwrap();
+
+        let mock = status_response(
+            r#"{"slot":1,"confirmations":1,"err":null,"confirmationStatus":"confirmed"}"#,
+        );
+
+        let mut options = SendOptions::default();
+        options
+            .set_preflight_commitment(Commitment::Processed)
+            .set_confirm_commitment(Commitment::Finalized);
+        assert_eq!(
+            Err(WalletError::ConfirmationTimeout(timeout)),
+            adapter
+                .sign_and_send_transaction_confirmed(&[1, 2], Cluster::DevNet, options, timeout)
+                .await
+        );
+
+        let mut options = SendOptions::default();
+        options.set_confirm_commitment(Commitment::Confirmed);
+        assert_eq!(
+            Ok(Signature::from_bytes(&SIGNATURE)),
+            adapter
+                .sign_and_send_transaction_confirmed(&[1, 2], Cluster::DevNet, options, timeout)
+                .await
+        );
+        assert_eq!(
+            mock.requests()[0].string("method").unwrap(),
+            "getSignatureStatuses"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn confirm_transaction_polls_status() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        let signature = Signature::from_bytes(&SIGNATURE);
+        let timeout = Duration::from_millis(50);
+
+        let mock = status_response(
+            r#"{"slot":1,"confirmations":null,"err":null,"confirmationStatus":"finalized"}"#,
+        );
+        assert_eq!(
+            Ok(()),
+            adapter
+                .confirm_transaction(&signature, Cluster::DevNet, timeout)
+                .await
+        );
+        let request = &mock.requests()[0];
+        assert_eq!(request.string("method").unwrap(), "getSignatureStatuses");
+        assert_eq!(
+            JSON::stringify(&request.reflect_inner("params").unwrap()).unwrap(),
+            format!(r#"[["{}"]]"#, bs58::encode(SIGNATURE).into_string()).as_str()
+        );
+        drop(mock);
+
+        // The default commitment is not reached before the timeout
+        let mock = status_response(
+            r#"{"slot":1,"confirmations":1,"err":null,"confirmationStatus":"confirmed"}"#,
+        );
+        assert_eq!(
+            Err(WalletError::ConfirmationTimeout(timeout)),
+            adapter
+                .confirm_transaction(&signature, Cluster::DevNet, timeout)
+                .await
+        );
+        adapter.set_default_commitment(Commitment::Confirmed);
+        assert_eq!(
+            Ok(()),
+            adapter
+                .confirm_transaction(&signature, Cluster::DevNet, timeout)
+                .await
+        );
+        drop(mock);
+
+        // Not seen by the cluster yet
+        let mock = status_response("null");
+        assert_eq!(
+            Err(WalletError::ConfirmationTimeout(timeout)),
+            adapter
+                .confirm_transaction(&signature, Cluster::DevNet, timeout)
+                .await
+        );
+        drop(mock);
+
+        let _mock = status_response(
+            r#"{"slot":1,"confirmations":0,"err":{"InstructionError":[0,{"Custom":1}]},"confirmationStatus":"processed"}"#,
+        );
+        assert_eq!(
+            Err(WalletError::TransactionFailed(
+                r#"{"InstructionError":[0,{"Custom":1}]}"#.to_string()
+            )),
+            adapter
+                .confirm_transaction(&signature, Cluster::DevNet, timeout)
+                .await
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn rpc_headers() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        adapter
+            .set_rpc_header("x-api-key", "foo")
+            .set_rpc_header("X-API-KEY", "bar");
+        assert_eq!(
+            adapter.rpc_client(Cluster::DevNet).headers(),
+            &[("X-API-KEY".to_string(), "bar".to_string())]
+        );
+
+        let mock = MockFetch::with_result("327123456");
+        adapter
+            .get_slot(Cluster::DevNet, Commitment::Confirmed)
+            .await
+            .unwrap();
+        assert_eq!(mock.header(0, "x-api-key").as_deref(), Some("bar"));
+        assert!(!format!("{adapter:?}").contains("\"bar\""));
+    }
+
+    #[wasm_bindgen_test]
+    async fn get_fee_for_message() {
+        let adapter = WalletAdapter::init().unwrap();
+        let message = [0u8, 1, 2, 250, 255];
+
+        let mock = MockFetch::with_result(r#"{"context":{"slot":1},"value":5000}"#);
+        assert_eq!(
+            adapter
+                .get_fee_for_message(&message, Cluster::DevNet, Commitment::Confirmed)
+                .await
+                .unwrap(),
+            Some(5000)
+        );
+
+        let request = &mock.requests()[0];
+        assert_eq!(request.string("method").unwrap(), "getFeeForMessage");
+        assert_eq!(requested_commitment(request), "confirmed");
+        let params = request.reflect_js_array("params").unwrap();
+        assert_eq!(params.get(0).as_string().unwrap(), "AAEC+v8=");
+        drop(mock);
+
+        let mock = MockFetch::with_result(r#"{"context":{"slot":1},"value":null}"#);
+        assert_eq!(
+            adapter
+                .get_fee_for_message(&message, Cluster::DevNet, Commitment::Confirmed)
+                .await
+                .unwrap(),
+            None
+        );
+        drop(mock);
+
+        let _mock = MockFetch::new(
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32602,"message":"invalid message"}}"#,
+        );
+        assert_eq!(
+            Err(WalletError::RpcError("invalid message".to_string())),
+            adapter
+                .get_fee_for_message(&message, Cluster::DevNet, Commitment::Confirmed)
+                .await
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn get_slot() {
+        let adapter = WalletAdapter::init().unwrap();
+
+        let mock = MockFetch::with_result("327123456");
+        assert_eq!(
+            adapter
+                .get_slot(Cluster::DevNet, Commitment::Processed)
+                .await
+                .unwrap(),
+            327_123_456
+        );
+
+        let request = &mock.requests()[0];
+        assert_eq!(request.string("method").unwrap(), "getSlot");
+        assert_eq!(requested_commitment(request), "processed");
+        drop(mock);
+
+        let _mock = MockFetch::with_result("-1");
+        assert!(matches!(
+            adapter
+                .get_slot(Cluster::DevNet, Commitment::Processed)
+                .await,
+            Err(WalletError::InvalidNumber(_))
+        ));
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod wallet_names_checks {
+    use sha3::{Digest, Sha3_256};
+    use wallet_adapter_common::WalletData;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn names_match_stored_wallets() {
+        let adapter = WalletAdapter::init().unwrap();
+        let storage = adapter.storage().clone_inner();
+        storage.borrow_mut().clear();
+
+        for name in ["Foo Wallet", "Bar Wallet", "Baz Wallet"] {
+            let wallet = Wallet {
+                data: WalletData::new().set_name(name),
+                ..Default::default()
+            };
+            let hash: [u8; 32] = Sha3_256::digest(name.to_lowercase().as_bytes()).into();
+            storage.borrow_mut().insert(hash, wallet);
+        }
+
+        let mut stored_names = adapter
+            .wallets()
+            .iter()
+            .map(|wallet| wallet.name().to_string())
+            .collect::<Vec<String>>();
+        stored_names.sort();
+
+        assert_eq!(adapter.wallet_names(), stored_names);
+        assert_eq!(
+            adapter.wallet_names(),
+            vec!["Bar Wallet", "Baz Wallet", "Foo Wallet"]
+        );
+
+        let mut iterated_names = adapter
+            .iter_wallets()
+            .map(|wallet| wallet.name().to_string())
+            .collect::<Vec<String>>();
+        iterated_names.sort();
+
+        assert_eq!(adapter.wallet_names(), iterated_names);
+        assert_eq!(adapter.iter_wallets().count(), adapter.wallets().len());
+    }
+
+    #[wasm_bindgen_test]
+    fn grouped_by_cluster() {
+        let adapter = WalletAdapter::init().unwrap();
+        let storage = adapter.storage().clone_inner();
+        storage.borrow_mut().clear();
+
+        let wallets: [(&str, &[Cluster]); 3] = [
+            ("Foo Wallet", &[Cluster::MainNet, Cluster::DevNet]),
+            ("Bar Wallet", &[Cluster::DevNet]),
+            ("Baz Wallet", &[Cluster::MainNet, Cluster::TestNet]),
+        ];
+
+        for (name, clusters) in wallets {
+            let wallet = Wallet {
+                data: WalletData::new().set_name(name).add_chains(clusters),
+                ..Default::default()
+            };
+            let hash: [u8; 32] = Sha3_256::digest(name.to_lowercase().as_bytes()).into();
+            storage.borrow_mut().insert(hash, wallet);
+        }
+
+        let names = |wallets: &[Wallet]| {
+            wallets
+                .iter()
+                .map(|wallet| wallet.name().to_string())
+                .collect::<Vec<String>>()
+        };
+
+        let groups = adapter.wallets_by_cluster();
+        assert_eq!(groups.len(), 3);
+        assert!(!groups.contains_key(&Cluster::LocalNet));
+        let mut devnet = names(&groups[&Cluster::DevNet]);
+        devnet.sort();
+        assert_eq!(devnet, vec!["Bar Wallet", "Foo Wallet"]);
+
+        let ordered = adapter.wallets_by_cluster_ordered();
+        assert_eq!(
+            ordered.keys().copied().collect::<Vec<Cluster>>(),
+            vec![Cluster::MainNet, Cluster::DevNet, Cluster::TestNet]
+        );
+        assert_eq!(
+            names(&ordered[&Cluster::MainNet]),
+            vec!["Baz Wallet", "Foo Wallet"]
+        );
+        assert_eq!(
+            names(&ordered[&Cluster::DevNet]),
+            vec!["Bar Wallet", "Foo Wallet"]
+        );
+        assert_eq!(names(&ordered[&Cluster::TestNet]), vec!["Baz Wallet"]);
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod simulate_transaction_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+    use crate::rpc::rpc_checks::MockFetch;
+
+    #[wasm_bindgen_test]
+    async fn logs_and_units_consumed() {
+        let adapter = WalletAdapter::init().unwrap();
+
+        let mock = MockFetch::with_result(
+            r#"{"context":{"slot":1},"value":{"err":null,"logs":["Program 11111111111111111111111111111111 invoke [1]","Program 11111111111111111111111111111111 success"],"unitsConsumed":150}}"#,
+        );
+        let simulation = adapter
+            .simulate_transaction(&[1, 2, 3], Cluster::DevNet)
+            .await
+            .unwrap();
+
+        assert!(simulation.is_ok());
+        assert_eq!(simulation.units_consumed(), Some(150));
+        assert_eq!(
+            simulation.logs()[1],
+            "Program 11111111111111111111111111111111 success"
+        );
+
+        let request = &mock.requests()[0];
+        assert_eq!(request.string("method").unwrap(), "simulateTransaction");
+        let params = request.reflect_js_array("params").unwrap();
+        assert_eq!(params.get(0).as_string().unwrap(), "AQID");
+        let config = Reflection::new(params.get(1)).unwrap();
+        assert_eq!(config.string("encoding").unwrap(), "base64");
+        drop(mock);
+
+        let _mock = MockFetch::with_result(
+            r#"{"context":{"slot":1},"value":{"err":{"InstructionError":[0,{"Custom":1}]},"logs":null}}"#,
+        );
+        let simulation = adapter
+            .simulate_transaction(&[1, 2, 3], Cluster::DevNet)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            simulation.err(),
+            Some(r#"{"InstructionError":[0,{"Custom":1}]}"#)
+        );
+        assert!(simulation.logs().is_empty());
+        assert_eq!(simulation.units_consumed(), None);
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod genesis_hash_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+    use crate::{rpc::rpc_checks::MockFetch, MAINNET_GENESIS_HASH};
+
+    #[wasm_bindgen_test]
+    async fn verify_mainnet() {
+        let adapter = WalletAdapter::init().unwrap();
+
+        let mock = MockFetch::with_result(r#""5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d""#);
+        assert_eq!(
+            adapter.get_genesis_hash(Cluster::MainNet).await.unwrap(),
+            MAINNET_GENESIS_HASH
+        );
+        assert_eq!(
+            mock.requests()[0].string("method").unwrap(),
+            "getGenesisHash"
+        );
+
+        assert_eq!(Ok(true), adapter.verify_cluster(Cluster::MainNet).await);
+        assert_eq!(Ok(false), adapter.verify_cluster(Cluster::DevNet).await);
+        assert_eq!(
+            Err(WalletError::UnsupportedChain("solana:localnet".to_string())),
+            adapter.verify_cluster(Cluster::LocalNet).await
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod connect_in_progress_checks {
+    use std::cell::RefCell;
+
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Function;
+
+    use super::*;
+    use crate::test_support::TestWallet;
+
+    // A wallet whose `standard:connect` resolves after 50ms
+    fn slow_wallet() -> Wallet {
+        let create_wallet = Function::new_no_args(
+            r#"
+            return {
+                name: "Slow Wallet",
+                version: "1.0.0",
+                chains: ["solana:devnet"],
+                accounts: [],
+                features: {
+                    "standard:connect": {
+                        version: "1.0.0",
+                        connect: async () => {
+                            await new Promise((resolve) => setTimeout(resolve, 50));
+
+                            return {
+                                accounts: [{
+                                    address: "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi",
+                                    publicKey: new Uint8Array(32).fill(1),
+                                    chains: ["solana:devnet"],
+                                    features: ["standard:connect"],
+                                }],
+                            };
+                        },
+                    },
+                },
+            };
+            "#,
+        );
+
+        Wallet::from_jsvalue(create_wallet.call0(&JsValue::null()).unwrap()).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    async fn overlapping_connects() {
+        let adapter = WalletAdapter::init().unwrap();
+        let first_outcome = Rc::new(RefCell::new(None));
+
+        let mut first = adapter.clone();
+        let first_outcome_writer = Rc::clone(&first_outcome);
+        wasm_bindgen_futures::spawn_local(async move {
+            let outcome = first.connect(slow_wallet()).await;
+            first_outcome_writer.replace(Some(outcome));
+        });
+
+        InnerUtils::sleep(Duration::from_millis(10)).await.unwrap();
+
+        let mut second = adapter.clone();
+        assert_eq!(
+            Err(WalletError::ConnectAlreadyInProgress),
+            second.connect(slow_wallet()).await
+        );
+
+        InnerUtils::sleep(Duration::from_millis(100)).await.unwrap();
+
+        assert!(first_outcome.take().unwrap().is_ok());
+        assert!(adapter.is_connected().await);
+        assert!(second.connect(slow_wallet()).await.is_ok());
+    }
+
+    #[wasm_bindgen_test]
+    async fn released_on_error() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        let wallet = TestWallet::new().set_connect_failures(1, "Error").build();
+
+        assert!(matches!(
+            adapter.connect(wallet.clone()).await,
+            Err(WalletError::WalletConnectError(_))
+        ));
+        assert!(adapter.connect(wallet).await.is_ok());
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod wallet_name_event_checks {
+    use wallet_adapter_common::WalletData;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    async fn differently_cased_event_name() {
+        let (sender, receiver) = bounded::<WalletEvent>(5);
+        let (seq_sender, _seq_receiver) = bounded::<WalletEventEnvelope>(5);
+        let sender = WalletEventSender::new(sender, seq_sender);
+
+        let mut connection_info = ConnectionInfo::new();
+        connection_info
+            .set_wallet(Wallet {
+                data: WalletData::new().set_name("Phantom"),
+                ..Default::default()
+            })
+            .set_account(WalletAccount::default());
+
+        let mut changed_account = WalletAccount::default();
+        changed_account.account.public_key = [1u8; 32];
+
+        connection_info
+            .emit_wallet_event("phantom", Some(changed_account.clone()), sender.clone())
+            .await;
+        assert_eq!(
+            WalletEvent::AccountChanged(changed_account),
+            receiver.recv().await.unwrap()
+        );
+
+        connection_info
+            .emit_wallet_event(" PHANTOM ", None, sender)
+            .await;
+        assert_eq!(WalletEvent::Disconnected, receiver.recv().await.unwrap());
+    }
+
+    #[wasm_bindgen_test]
+    async fn account_change_is_not_skipped() {
+        let (sender, receiver) = bounded::<WalletEvent>(5);
+        let (seq_sender, _seq_receiver) = bounded::<WalletEventEnvelope>(5);
+        let sender = WalletEventSender::new(sender, seq_sender);
+
+        let mut connection_info = ConnectionInfo::new();
+        connection_info
+            .set_wallet(Wallet {
+                data: WalletData::new().set_name("phantom"),
+                ..Default::default()
+            })
+            .set_account(WalletAccount::default());
+
+        let mut changed_account = WalletAccount::default();
+        changed_account.account.public_key = [2u8; 32];
+
+        connection_info
+            .emit_wallet_event("Phantom", Some(changed_account.clone()), sender.clone())
+            .await;
+        let event = receiver.recv().await.unwrap();
+        assert_ne!(WalletEvent::Skip, event);
+        assert_eq!(WalletEvent::AccountChanged(changed_account.clone()), event);
+
+        connection_info
+            .emit_wallet_event("Solflare", Some(changed_account), sender)
+            .await;
+        assert_eq!(WalletEvent::Skip, receiver.recv().await.unwrap());
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod prioritization_fees_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+    use crate::rpc::rpc_checks::MockFetch;
+
+    #[wasm_bindgen_test]
+    async fn fee_entries() {
+        let adapter = WalletAdapter::init().unwrap();
+
+        let mock = MockFetch::with_result(
+            r#"[{"slot":348125,"prioritizationFee":0},{"slot":348126,"prioritizationFee":1000},{"slot":348127,"prioritizationFee":500}]"#,
+        );
+        assert_eq!(
+            adapter
+                .get_recent_prioritization_fees(Cluster::MainNet, &[[1u8; 32]])
+                .await
+                .unwrap(),
+            vec![0, 1000, 500]
+        );
+
+        let request = &mock.requests()[0];
+        assert_eq!(
+            request.string("method").unwrap(),
+            "getRecentPrioritizationFees"
+        );
+        let accounts = Reflection::new(request.reflect_js_array("params").unwrap().get(0))
+            .unwrap()
+            .into_array()
+            .unwrap();
+        assert_eq!(
+            accounts.get(0).as_string().unwrap(),
+            "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi"
+        );
+        drop(mock);
+
+        for result in ["[]", "null"] {
+            let _mock = MockFetch::with_result(result);
+            assert!(adapter
+                .get_recent_prioritization_fees(Cluster::MainNet, &[])
+                .await
+                .unwrap()
+                .is_empty());
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod request_more_accounts_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Function;
+
+    use super::*;
+
+    // A wallet that has authorized one account and whose `standard:connect`
+    // returns an extra account from the second call onwards
+    fn expanding_wallet() -> Wallet {
+        let create_wallet = Function::new_no_args(
+            r#"
+            let calls = 0;
+            const account = (address, fill) => ({
+                address,
+                publicKey: new Uint8Array(32).fill(fill),
+                chains: ["solana:devnet"],
+                features: ["standard:connect"],
+            });
+            const first = account("4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi", 1);
+            const second = account("8qbHbw2BbbTHBW1sbeqakYXVKRQM8Ne7pLK7m6CVfeR", 2);
+
+            return {
+                name: "Expanding Wallet",
+                version: "1.0.0",
+                chains: ["solana:devnet"],
+                accounts: [first],
+                features: {
+                    "standard:connect": {
+                        version: "1.0.0",
+                        connect: async () => {
+                            calls += 1;
+
+                            return { accounts: calls == 1 ? [first] : [first, second] };
+                        },
+                    },
+                },
+            };
+            "#,
+        );
+
+        Wallet::from_jsvalue(create_wallet.call0(&JsValue::null()).unwrap()).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    async fn extra_account() {
+        let mut adapter = WalletAdapter::init().unwrap();
+
+        adapter.connect(expanding_wallet()).await.unwrap();
+
+        let new_accounts = adapter.request_more_accounts().await.unwrap();
+        assert_eq!(new_accounts.len(), 1);
+        assert_eq!(new_accounts[0].public_key(), [2u8; 32]);
+        assert_eq!(
+            adapter
+                .connection_info()
+                .await
+                .connected_wallet()
+                .unwrap()
+                .accounts()
+                .len(),
+            2
+        );
+
+        assert!(adapter.request_more_accounts().await.unwrap().is_empty());
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod logger_checks {
+    use std::cell::RefCell;
+
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    async fn custom_logger_receives_diagnostics() {
+        let mut adapter = WalletAdapter::init().unwrap();
+
+        let logs = Rc::new(RefCell::new(Vec::<(LogLevel, String)>::new()));
+        let logs_writer = Rc::clone(&logs);
+        adapter.set_logger(Rc::new(move |level: LogLevel, message: &str| {
+            logs_writer.borrow_mut().push((level, message.to_string()));
+        }));
+
+        // No wallet is connected so the event cannot be processed
+        adapter
+            .connection_info
+            .write()
+            .await
+            .emit_wallet_event("Foo Wallet", None, adapter.wallet_events_sender.clone())
+            .await;
+
+        let logs = logs.take();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].0, LogLevel::Warn);
+        assert!(logs[0]
+            .1
+            .starts_with("ON EVENT EMITTED BUT NO CONNECTED WALLET FOUND"));
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod silent_connect_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Reflect;
+
+    use super::*;
+    use crate::test_support::{mock_function, TestWallet};
+
+    fn last_silent_input(wallet: &Wallet) -> JsValue {
+        let input = mock_function(wallet, "mockLastInput")
+            .call0(&JsValue::null())
+            .unwrap();
+
+        Reflect::get(&input, &"silent".into()).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    async fn silent_option() {
+        let mut adapter = WalletAdapter::init().unwrap();
+
+        let wallet = TestWallet::new().build();
+        assert!(adapter
+            .connect_silent_authorized(wallet.clone())
+            .await
+            .is_ok());
+        assert_eq!(last_silent_input(&wallet), JsValue::TRUE);
+
+        let wallet = TestWallet::new().set_silent_authorized(false).build();
+        assert_eq!(
+            Err(WalletError::ConnectHasNoAccounts),
+            adapter.connect_silent_authorized(wallet.clone()).await
+        );
+        assert_eq!(last_silent_input(&wallet), JsValue::TRUE);
+
+        assert!(adapter.connect(wallet.clone()).await.is_ok());
+        assert_eq!(last_silent_input(&wallet), JsValue::FALSE);
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod sign_in_timeout_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Function;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    async fn never_resolving_sign_in() {
+        let wallet = Function::new_no_args(
+            r#"
+            const account = {
+                address: "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi",
+                publicKey: new Uint8Array(32).fill(1),
+                chains: ["solana:devnet"],
+                features: ["standard:connect", "solana:signIn"],
+            };
+
+            return {
+                name: "Unresponsive Wallet",
+                version: "1.0.0",
+                chains: ["solana:devnet"],
+                accounts: [account],
+                features: {
+                    "standard:connect": {
+                        version: "1.0.0",
+                        connect: async () => ({ accounts: [account] }),
+                    },
+                    "solana:signIn": {
+                        version: "1.0.0",
+                        signIn: () => new Promise(() => {}),
+                    },
+                },
+            };
+            "#,
+        )
+        .call0(&JsValue::null())
+        .unwrap();
+
+        let mut adapter = WalletAdapter::init().unwrap();
+        adapter
+            .connect(Wallet::from_jsvalue(wallet).unwrap())
+            .await
+            .unwrap();
+        let last_event = adapter.last_event().await;
+
+        let timeout = Duration::from_millis(20);
+        assert_eq!(
+            Err(WalletError::SignInTimeout(timeout)),
+            adapter
+                .sign_in_with_timeout(&SigninInput::new(), [1u8; 32], timeout)
+                .await
+        );
+
+        assert!(adapter.is_connected().await);
+        assert_eq!(last_event, adapter.last_event().await);
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod sign_in_key_mismatch_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Function;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    async fn key_of_another_account() {
+        let wallet = Function::new_no_args(
+            r#"
+            const account = {
+                address: "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi",
+                publicKey: new Uint8Array(32).fill(1),
+                chains: ["solana:devnet"],
+                features: ["standard:connect", "solana:signIn"],
+            };
+            globalThis.mismatchSignInCalls = 0;
+
+            return {
+                name: "Sign In Wallet",
+                version: "1.0.0",
+                chains: ["solana:devnet"],
+                accounts: [account],
+                features: {
+                    "standard:connect": {
+                        version: "1.0.0",
+                        connect: async () => ({ accounts: [account] }),
+                    },
+                    "solana:signIn": {
+                        version: "1.0.0",
+                        signIn: async () => {
+                            globalThis.mismatchSignInCalls += 1;
+                            return new Promise(() => {});
+                        },
+                    },
+                },
+            };
+            "#,
+        )
+        .call0(&JsValue::null())
+        .unwrap();
+
+        let mut adapter = WalletAdapter::init().unwrap();
+        adapter
+            .connect(Wallet::from_jsvalue(wallet).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            Err(WalletError::SignInKeyMismatch),
+            adapter.sign_in(&SigninInput::new(), [2u8; 32]).await
+        );
+        assert_eq!(
+            Err(WalletError::SignInKeyMismatch),
+            adapter
+                .sign_in_with_timeout(&SigninInput::new(), [2u8; 32], Duration::from_millis(20))
+                .await
+        );
+
+        let calls = web_sys::js_sys::Reflect::get(
+            &web_sys::js_sys::global(),
+            &"mismatchSignInCalls".into(),
+        )
+        .unwrap();
+        assert_eq!(calls, JsValue::from(0));
+
+        // The key of the connected account reaches the wallet
+        assert_eq!(
+            Err(WalletError::SignInTimeout(Duration::from_millis(20))),
+            adapter
+                .sign_in_with_timeout(&SigninInput::new(), [1u8; 32], Duration::from_millis(20))
+                .await
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod wallet_downgrade_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::{Function, Reflect};
+
+    use super::*;
+    use crate::{Registration, SemverVersion};
+
+    fn wallet_object(version: &str) -> JsValue {
+        Function::new_with_args(
+            "version",
+            r#"
+            return {
+                name: "Versioned Wallet",
+                version,
+                chains: ["solana:devnet"],
+                accounts: [],
+                features: {},
+            };
+            "#,
+        )
+        .call1(&JsValue::null(), &version.into())
+        .unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    async fn re_register_with_lower_version() {
+        let adapter = WalletAdapter::init().unwrap();
+        let wallet = wallet_object("2.0.0");
+        adapter
+            .connection_info
+            .write()
+            .await
+            .set_wallet(Wallet::from_jsvalue(wallet.clone()).unwrap());
+
+        let register_object = InitEvents::register_object_with_registration(
+            adapter.storage().clone_inner(),
+            Registration::new(&adapter),
+        );
+        let register: Function = Reflect::get(&register_object, &"register".into())
+            .unwrap()
+            .into();
+
+        // The wallet registers itself again after the extension updated its version
+        Reflect::set(&wallet, &"version".into(), &"2.1.0".into()).unwrap();
+        register.call1(&JsValue::null(), &wallet).unwrap();
+        Reflect::set(&wallet, &"version".into(), &"1.0.0".into()).unwrap();
+        register.call1(&JsValue::null(), &wallet).unwrap();
+
+        // The upgrade emits no event so the first event is the downgrade
+        assert_eq!(
+            WalletEvent::WalletDowngraded {
+                from: SemverVersion::parse("2.0.0").unwrap(),
+                to: SemverVersion::parse("1.0.0").unwrap(),
+            },
+            adapter.events().recv().await.unwrap()
+        );
+        assert!(adapter.events().is_empty());
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod ready_state_checks {
+    use wallet_adapter_common::WalletData;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn installed_and_not_detected() {
+        let adapter = WalletAdapter::init().unwrap();
+        let wallet = Wallet {
+            data: WalletData::new().set_name("Ready Wallet"),
+            ..Default::default()
+        };
+        adapter
+            .storage()
+            .clone_inner()
+            .borrow_mut()
+            .insert(WalletName::new("Ready Wallet").storage_key(), wallet);
+
+        assert_eq!(
+            adapter.ready_state("Ready Wallet"),
+            WalletReadyState::Installed
+        );
+        assert_eq!(
+            adapter.ready_state("ready wallet"),
+            WalletReadyState::Installed
+        );
+        assert_eq!(
+            adapter.ready_state("Missing Wallet"),
+            WalletReadyState::NotDetected
+        );
+        // The test browser has no wallet extensions installed
+        assert_eq!(adapter.ready_state("Backpack"), WalletReadyState::Loadable);
+        assert!(adapter
+            .installable_wallets()
+            .iter()
+            .any(|known_wallet| known_wallet.is_named("Backpack")));
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod cancel_sign_message_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Function;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    async fn cancel_before_wallet_resolves() {
+        let wallet = Function::new_no_args(
+            r#"
+            const account = {
+                address: "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi",
+                publicKey: new Uint8Array(32).fill(1),
+                chains: ["solana:devnet"],
+                features: ["standard:connect", "solana:signMessage"],
+            };
+
+            return {
+                name: "Pending Wallet",
+                version: "1.0.0",
+                chains: ["solana:devnet"],
+                accounts: [account],
+                features: {
+                    "standard:connect": {
+                        version: "1.0.0",
+                        connect: async () => ({ accounts: [account] }),
+                    },
+                    "solana:signMessage": {
+                        version: "1.0.0",
+                        signMessage: () => new Promise(() => {}),
+                    },
+                },
+            };
+            "#,
+        )
+        .call0(&JsValue::null())
+        .unwrap();
+
+        let mut adapter = WalletAdapter::init().unwrap();
+        adapter
+            .connect(Wallet::from_jsvalue(wallet).unwrap())
+            .await
+            .unwrap();
+
+        let (request, cancel_handle) = adapter.sign_message_cancelable(b"Hello Solana");
+        cancel_handle.cancel();
+        cancel_handle.cancel();
+
+        assert_eq!(Err(WalletError::Cancelled), request.await);
+        assert!(adapter.is_connected().await);
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod connect_accounts_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Function;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    async fn connect_response_with_three_accounts() {
+        let wallet = Function::new_no_args(
+            r#"
+            const account = (fill, address) => ({
+                address,
+                publicKey: new Uint8Array(32).fill(fill),
+                chains: ["solana:devnet"],
+                features: ["standard:connect"],
+            });
+            const accounts = [
+                account(1, "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi"),
+                account(2, "8qbHbw2BbbTHBW1sbeqakYXVKRQM8Ne7pLK7m6CVfeR"),
+                account(3, "CktRuQ2mttgRGkXJtyksdKHjUdc2C4TgDzyB98oEzy8"),
+            ];
+
+            return {
+                name: "Three Accounts Wallet",
+                version: "1.0.0",
+                chains: ["solana:devnet"],
+                accounts: [],
+                features: {
+                    "standard:connect": {
+                        version: "1.0.0",
+                        connect: async () => ({ accounts }),
+                    },
+                },
+            };
+            "#,
+        )
+        .call0(&JsValue::null())
+        .unwrap();
+
+        let mut adapter = WalletAdapter::init().unwrap();
+        let connected = adapter
+            .connect(Wallet::from_jsvalue(wallet).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(connected.public_key(), [1u8; 32]);
+
+        let connection_info = adapter.connection_info().await;
+        assert_eq!(connection_info.connected_account().unwrap(), &connected);
+        assert_eq!(
+            connection_info
+                .connected_accounts()
+                .unwrap()
+                .iter()
+                .map(|account| account.public_key())
+                .collect::<Vec<[u8; 32]>>(),
+            vec![[1u8; 32], [2u8; 32], [3u8; 32]]
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod clock_checks {
+    use std::time::SystemTime;
+
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct FixedClock(SystemTime);
+
+    impl Clock for FixedClock {
+        fn now(&self) -> SystemTime {
+            self.0
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn sign_in_checked_uses_clock() {
+        let mut signin_input = SigninInput::new();
+        signin_input.set_expiration_time_seconds(60).unwrap();
+        let expiration_time = *signin_input.expiration_time().unwrap();
+
+        let mut adapter = WalletAdapter::init().unwrap();
+
+        adapter.set_clock(FixedClock(expiration_time + Duration::from_secs(1)));
+        assert_eq!(
+            Err(WalletError::ExpirationTimeIsInThePast),
+            adapter.sign_in_checked(&signin_input, [1u8; 32]).await
+        );
+
+        // Not expired so the request reaches the missing connected wallet
+        adapter.set_clock(FixedClock(expiration_time));
+        assert_eq!(
+            Err(WalletError::WalletNotFound),
+            adapter.sign_in_checked(&signin_input, [1u8; 32]).await
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod duplicate_wallet_name_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::{Function, Reflect};
+
+    use super::*;
+    use crate::Registration;
+
+    fn wallet_object(icon: &str) -> JsValue {
+        Function::new_with_args(
+            "icon",
+            r#"
+            return {
+                name: "Solana",
+                version: "1.0.0",
+                icon,
+                chains: ["solana:devnet"],
+                accounts: [],
+                features: {},
+            };
+            "#,
+        )
+        .call1(&JsValue::null(), &icon.into())
+        .unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    async fn two_wallets_with_the_same_name() {
+        let adapter = WalletAdapter::init().unwrap();
+
+        let register_object = InitEvents::register_object_with_registration(
+            adapter.storage().clone_inner(),
+            Registration::new(&adapter),
+        );
+        let register: Function = Reflect::get(&register_object, &"register".into())
+            .unwrap()
+            .into();
+
+        let icon = "data:image/svg+xml;base64,Zmlyc3Q=";
+        let updated_icon = "data:image/svg+xml;base64,c2Vjb25k";
+
+        // Different wallets reporting the same data
+        let first = wallet_object(icon);
+        let second = wallet_object(icon);
+
+        register.call1(&JsValue::null(), &first).unwrap();
+        register.call1(&JsValue::null(), &second).unwrap();
+        // The same wallet registering again with a new icon replaces itself
+        Reflect::set(&first, &"icon".into(), &updated_icon.into()).unwrap();
+        register.call1(&JsValue::null(), &first).unwrap();
+
+        assert_eq!(
+            WalletEvent::DuplicateWalletName("Solana".to_string()),
+            adapter.events().recv().await.unwrap()
+        );
+        assert!(adapter.events().is_empty());
+
+        let icons = adapter
+            .storage()
+            .wallets_by_name("solana")
+            .iter()
+            .map(|wallet| wallet.icon().unwrap().to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(icons, vec![updated_icon, icon]);
+        assert_eq!(
+            adapter.get_wallet("Solana").unwrap().icon().unwrap(),
+            updated_icon
+        );
+
+        // Each wallet is identified by the key it is stored under
+        let name = WalletName::new("Solana");
+        let ids = adapter
+            .storage()
+            .wallets_by_name("solana")
+            .iter()
+            .map(Wallet::id)
+            .collect::<Vec<[u8; 32]>>();
+        assert_eq!(ids, vec![name.storage_key_at(0), name.storage_key_at(1)]);
+        assert_ne!(ids[0], ids[1]);
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod semantic_eq_checks {
+    use wallet_adapter_common::{WalletAccountData, WalletData};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    fn connection_info(label: &str) -> ConnectionInfo {
+        let account = WalletAccount {
+            account: WalletAccountData {
+                address: "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi".to_string(),
+                public_key: [1u8; 32],
+                label: Some(label.to_string()),
+                ..Default::default()
+            },
+            // A distinct JS object for every account
+            js_value: Object::new().into(),
+        };
+
+        let mut connection_info = ConnectionInfo::new();
+        connection_info
+            .set_wallet(Wallet {
+                data: WalletData::new().set_name("Phantom"),
+                accounts: vec![account.clone()],
+                ..Default::default()
+            })
+            .set_account(account);
+
+        connection_info
+    }
+
+    #[wasm_bindgen_test]
+    fn equal_data_distinct_js_values() {
+        let first = connection_info("Main");
+        let second = connection_info("Main");
+
+        assert_ne!(first, second);
+        assert!(first.semantically_eq(&second));
+
+        assert!(!first.semantically_eq(&connection_info("Savings")));
+        assert!(!first.semantically_eq(&ConnectionInfo::new()));
+        assert!(ConnectionInfo::new().semantically_eq(&ConnectionInfo::new()));
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod siws_output_checks {
+    use ed25519_dalek::{Signer, SigningKey};
+    use wallet_adapter_common::WalletCommonUtils;
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Uint8Array;
+
+    use super::*;
+    use crate::test_support::{account_object, multi_account_wallet};
+
+    const MESSAGE: &[u8] = b"Verify me like a sign in";
+
+    #[wasm_bindgen_test]
+    async fn repackaged_output_verifies() {
+        let signing_key = SigningKey::from_bytes(&[4u8; 32]);
+        let accounts = Array::of1(&account_object(&signing_key));
+        let signatures = Array::of1(&Uint8Array::from(
+            signing_key.sign(MESSAGE).to_bytes().as_slice(),
+        ));
+
+        let mut adapter = WalletAdapter::init().unwrap();
+        adapter
+            .connect(multi_account_wallet(&accounts, &signatures))
+            .await
+            .unwrap();
+
+        let output = adapter.sign_message_as_siws_output(MESSAGE).await.unwrap();
+        assert_eq!(output.message.as_bytes(), MESSAGE);
+        assert_eq!(output.public_key, signing_key.verifying_key().to_bytes());
+        assert_eq!(
+            output.address(),
+            WalletCommonUtils::address(signing_key.verifying_key())
+        );
+        assert!(WalletCommonUtils::verify(
+            &output.public_key,
+            output.message.as_bytes(),
+            &output.signature
+        )
+        .is_ok());
+
+        assert_eq!(
+            Err(WalletError::InvalidUtf8Message),
+            adapter.sign_message_as_siws_output(&[0xff, 0xfe]).await
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod next_event_of_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    async fn skips_unrelated_events() {
+        let adapter = WalletAdapter::init().unwrap();
+        let sender = adapter.wallet_events_sender();
+        let timeout = Duration::from_millis(100);
+
+        let emitter = sender.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            InnerUtils::sleep(Duration::from_millis(10)).await.unwrap();
+            emitter
+                .send(WalletEvent::MultipleApprovalsRequired(2))
+                .await
+                .unwrap();
+            emitter.send(WalletEvent::Disconnected).await.unwrap();
+        });
+
+        assert_eq!(
+            Ok(WalletEvent::Disconnected),
+            adapter
+                .next_event_of(WalletEventKind::Disconnected, timeout)
+                .await
+        );
+
+        // The skipped event is still delivered to the listeners of the adapter
+        let events = adapter.events();
+        assert_eq!(
+            Ok(WalletEvent::MultipleApprovalsRequired(2)),
+            events.try_recv()
+        );
+        assert_eq!(Ok(WalletEvent::Disconnected), events.try_recv());
+        assert_eq!(
+            WalletEvent::MultipleApprovalsRequired(2),
+            adapter.events_seq().try_recv().unwrap().event
+        );
+
+        // Events emitted before the call are not waited for
+        sender.send(WalletEvent::Disconnected).await.unwrap();
+
+        assert_eq!(
+            Err(WalletError::EventWaitTimeout(
+                WalletEventKind::Disconnected,
+                timeout
+            )),
+            adapter
+                .next_event_of(WalletEventKind::Disconnected, timeout)
+                .await
+        );
+        assert_eq!(Ok(WalletEvent::Disconnected), events.try_recv());
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod summary_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+    use crate::test_support::TestWallet;
+
+    #[wasm_bindgen_test]
+    async fn connected_and_disconnected() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        assert_eq!(ConnectionSummary::default(), adapter.summary().await);
+
+        let wallet = TestWallet::new().build();
+        adapter.connect(wallet).await.unwrap();
+
+        assert_eq!(
+            ConnectionSummary {
+                connected: true,
+                wallet_name: Some("Mock Wallet".to_string()),
+                account_address: Some("4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi".to_string()),
+                shortened_address: Some("4vJ9...KLKi".to_string()),
+                icon: None,
+                clusters: vec![Cluster::MainNet, Cluster::DevNet, Cluster::TestNet],
+            },
+            adapter.summary().await
+        );
+        assert_eq!(
+            adapter.clusters().await.unwrap(),
+            adapter.summary().await.clusters
+        );
+
+        adapter.disconnect().await;
+        assert_eq!(ConnectionSummary::default(), adapter.summary().await);
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod throw_vs_reject_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Function;
+
+    use super::*;
+
+    // A wallet whose `standard:connect` and `solana:signMessage` fail with `fail`,
+    // which either throws synchronously or returns a rejected promise
+    fn failing_wallet(fail: &str) -> Wallet {
+        let create_wallet = Function::new_with_args(
+            "fail",
+            r#"
+            const account = {
+                address: "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi",
+                publicKey: new Uint8Array(32).fill(1),
+                chains: ["solana:devnet"],
+                features: ["standard:connect", "solana:signMessage"],
+            };
+            const failure = new Function("name", "message", fail);
+
+            return {
+                name: "Failing Wallet",
+                version: "1.0.0",
+                chains: ["solana:devnet"],
+                accounts: [account],
+                features: {
+                    "standard:connect": {
+                        version: "1.0.0",
+                        connect: (input) => input.silent
+                            ? failure("ConnectError", "Connect failed")
+                            : Promise.resolve({ accounts: [account] }),
+                    },
+                    "solana:signMessage": {
+                        version: "1.0.0",
+                        signMessage: () => failure("SignError", "Signing failed"),
+                    },
+                },
+            };
+            "#,
+        );
+
+        Wallet::from_jsvalue(create_wallet.call1(&JsValue::null(), &fail.into()).unwrap()).unwrap()
+    }
+
+    async fn failures(fail: &str) -> (WalletError, WalletError) {
+        let wallet = failing_wallet(fail);
+
+        let connect_error = wallet.connect_authorized(true).await.unwrap_err();
+
+        let mut adapter = WalletAdapter::init().unwrap();
+        adapter.connect(wallet).await.unwrap();
+        let sign_error = adapter.sign_message(b"Hello Solana").await.unwrap_err();
+
+        (connect_error, sign_error)
+    }
+
+    #[wasm_bindgen_test]
+    async fn throw_and_reject_are_consistent() {
+        let thrown =
+            failures("const error = new Error(message); error.name = name; throw error;").await;
+        let rejected = failures(
+            "const error = new Error(message); error.name = name; return Promise.reject(error);",
+        )
+        .await;
+
+        for (connect_error, sign_error) in [thrown, rejected] {
+            assert_eq!(
+                WalletError::WalletConnectError("Connect failed".to_string()),
+                connect_error
+            );
+            assert!(matches!(
+                sign_error,
+                WalletError::JsError { name, message, .. }
+                    if name == "SignError" && message == "Signing failed"
+            ));
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn thrown_string() {
+        let (connect_error, sign_error) = failures("throw message;").await;
+
+        assert_eq!(
+            WalletError::WalletConnectError("Connect failed".to_string()),
+            connect_error
+        );
+        assert_eq!(
+            WalletError::JsError {
+                name: "Error".to_string(),
+                message: "Signing failed".to_string(),
+                stack: String::default(),
+            },
+            sign_error
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(target_arch = "wasm32", feature = "panic-hook"))]
+mod panic_hook_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn installs_once() {
+        WalletAdapter::install_panic_hook();
+        WalletAdapter::install_panic_hook();
+
+        assert!(WalletAdapter::init().is_ok());
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod account_history_checks {
+    use ed25519_dalek::SigningKey;
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Function;
+
+    use super::*;
+    use crate::test_support::{account_object, multi_account_wallet};
+
+    #[wasm_bindgen_test]
+    async fn clear_keeps_active_account() {
+        let first = SigningKey::from_bytes(&[3u8; 32]);
+        let second = SigningKey::from_bytes(&[6u8; 32]);
+        let accounts = Array::of2(&account_object(&first), &account_object(&second));
+
+        let mut adapter = WalletAdapter::init().unwrap();
+        adapter
+            .connect(multi_account_wallet(&accounts, &Array::new()))
+            .await
+            .unwrap();
+
+        let switched = adapter
+            .connection_info()
+            .await
+            .connected_accounts()
+            .unwrap()[1]
+            .clone();
+        adapter
+            .connection_info_inner()
+            .write()
+            .await
+            .emit_wallet_event(
+                "Multi Account Wallet",
+                Some(switched.clone()),
+                adapter.wallet_events_sender(),
+            )
+            .await;
+
+        assert_eq!(
+            adapter.connection_info().await.previous_accounts()[0].public_key(),
+            first.verifying_key().to_bytes()
+        );
+
+        adapter.clear_account_history().await;
+
+        let connection_info = adapter.connection_info().await;
+        assert!(connection_info.previous_accounts().is_empty());
+        assert_eq!(connection_info.connected_account(), Ok(&switched));
+        assert!(connection_info.connected_wallet().is_ok());
+    }
+
+    #[wasm_bindgen_test]
+    async fn removed_since_reconnect() {
+        let first = SigningKey::from_bytes(&[3u8; 32]);
+        let second = SigningKey::from_bytes(&[6u8; 32]);
+        let accounts = Array::of2(&account_object(&first), &account_object(&second));
+
+        // Authorizes both accounts on the first connect and only the first one afterwards
+        let wallet = Function::new_with_args(
+            "accounts",
+            r#"
+            let connects = 0;
+
+            return {
+                name: "Shrinking Wallet",
+                version: "1.0.0",
+                chains: ["solana:devnet"],
+                accounts: [],
+                features: {
+                    "standard:connect": {
+                        version: "1.0.0",
+                        connect: async () => ({
+                            accounts: connects++ === 0 ? accounts : accounts.slice(0, 1),
+                        }),
+                    },
+                },
+            };
+            "#,
+        )
+        .call1(&JsValue::null(), &accounts)
+        .unwrap();
+        let wallet = Wallet::from_jsvalue(wallet).unwrap();
+
+        let mut adapter = WalletAdapter::init().unwrap();
+        adapter.connect(wallet.clone()).await.unwrap();
+
+        let snapshot = adapter
+            .connection_info()
+            .await
+            .authorized_accounts()
+            .to_vec();
+        assert_eq!(snapshot.len(), 2);
+        assert!(adapter.accounts_removed_since(&snapshot).await.is_empty());
+
+        adapter.connect(wallet).await.unwrap();
+
+        assert_eq!(
+            adapter.accounts_removed_since(&snapshot).await,
+            vec![second.verifying_key().to_bytes()]
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod secure_context_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::{
+        js_sys::{Function, Reflect},
+        wasm_bindgen::JsCast,
+    };
+
+    use super::*;
+
+    fn mock_window(is_secure_context: bool) -> Window {
+        Function::new_with_args(
+            "isSecureContext",
+            r#"
+            return {
+                isSecureContext,
+                addEventListener: () => {},
+                dispatchEvent: () => true,
+            };
+            "#,
+        )
+        .call1(&JsValue::null(), &is_secure_context.into())
+        .unwrap()
+        .unchecked_into()
+    }
+
+    /// Initialize the adapter with the mocked window
+    /// and return the warnings logged to the console
+    fn init_with(window: Window) -> (WalletAdapter, Vec<String>) {
+        let console = Reflect::get(&web_sys::js_sys::global(), &"console".into()).unwrap();
+        let warn = Reflect::get(&console, &"warn".into()).unwrap();
+        let warnings = Array::new();
+        let record =
+            Function::new_with_args("warnings", "return (message) => warnings.push(message);")
+                .call1(&JsValue::null(), &warnings)
+                .unwrap();
+        Reflect::set(&console, &"warn".into(), &record).unwrap();
+
+        let adapter =
+            WalletAdapter::init_custom(window, web_sys::window().unwrap().document().unwrap());
+
+        Reflect::set(&console, &"warn".into(), &warn).unwrap();
+
+        let warnings = warnings
+            .iter()
+            .map(|warning| warning.as_string().unwrap())
+            .collect();
+
+        (adapter.unwrap(), warnings)
+    }
+
+    #[wasm_bindgen_test]
+    fn insecure_context_warning() {
+        let (adapter, warnings) = init_with(mock_window(false));
+        assert!(!adapter.is_secure_context());
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("secure context"));
+
+        let (adapter, warnings) = init_with(mock_window(true));
+        assert!(adapter.is_secure_context());
+        assert!(warnings.is_empty());
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod sign_transaction_single_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Function;
+
+    use super::*;
+    use crate::test_support::TestWallet;
+
+    // A wallet that returns `count` copies of the transaction with `1` appended
+    fn signing_wallet(count: u8) -> Wallet {
+        let sign_transaction = Function::new_with_args(
+            "count",
+            r#"
+            return {
+                version: "1.0.0",
+                supportedTransactionVersions: ["legacy", 0],
+                signTransaction: async ({ transaction }) =>
+                    Array.from({ length: count }, () => ({
+                        signedTransaction: new Uint8Array([...transaction, 1]),
+                    })),
+            };
+            "#,
+        )
+        .call1(&JsValue::null(), &count.into())
+        .unwrap();
+
+        TestWallet::new()
+            .add_feature("solana:signTransaction", &sign_transaction)
+            .build()
+    }
+
+    async fn sign_with(count: u8) -> WalletResult<Vec<u8>> {
+        let mut adapter = WalletAdapter::init().unwrap();
+        adapter.connect(signing_wallet(count)).await.unwrap();
+
+        adapter
+            .sign_transaction_single(&[7, 8], Some(Cluster::DevNet))
+            .await
+    }
+
+    #[wasm_bindgen_test]
+    async fn exactly_one_transaction() {
+        assert_eq!(Ok(vec![7, 8, 1]), sign_with(1).await);
+        assert_eq!(
+            Err(WalletError::ReceivedAnEmptySignedMessagesArray),
+            sign_with(0).await
+        );
+        assert_eq!(
+            Err(WalletError::UnexpectedSignedTransactionCount(2)),
+            sign_with(2).await
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod event_listener_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+    use crate::test_support::{mock_function, TestWallet};
+
+    #[wasm_bindgen_test]
+    async fn reconnect_unsubscribes_previous_listener() {
+        let wallet = TestWallet::new().build();
+        let subscribed = mock_function(&wallet, "mockSubscribed");
+        let subscribed = || subscribed.call0(&JsValue::null()).unwrap();
+
+        let mut adapter = WalletAdapter::init().unwrap();
+        adapter.connect(wallet.clone()).await.unwrap();
+        adapter.connect(wallet).await.unwrap();
+
+        // Let the previous listener task observe the closed signal
+        InnerUtils::sleep(Duration::from_millis(10)).await.unwrap();
+        assert_eq!(adapter.active_event_listeners(), 1);
+        assert_eq!(subscribed(), JsValue::from(1));
+
+        adapter.disconnect().await;
+        InnerUtils::sleep(Duration::from_millis(10)).await.unwrap();
+        assert_eq!(adapter.active_event_listeners(), 0);
+        assert_eq!(subscribed(), JsValue::from(0));
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod sign_message_verification_checks {
+    use ed25519_dalek::{Signer, SigningKey};
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Uint8Array;
+
+    use super::*;
+    use crate::test_support::{account_object, multi_account_wallet};
+
+    const MESSAGE: &[u8] = b"Sign in to example.com";
+
+    async fn sign_with(signature: [u8; 64]) -> WalletResult<[u8; 64]> {
+        let signing_key = SigningKey::from_bytes(&[3u8; 32]);
+        let accounts = Array::of1(&account_object(&signing_key));
+        let signatures = Array::of1(&Uint8Array::from(signature.as_slice()));
+
+        let mut adapter = WalletAdapter::init().unwrap();
+        adapter
+            .connect(multi_account_wallet(&accounts, &signatures))
+            .await
+            .unwrap();
+
+        adapter
+            .sign_message(MESSAGE)
+            .await
+            .map(|output| output.signature().to_bytes())
+    }
+
+    #[wasm_bindgen_test]
+    async fn valid_and_tampered() {
+        let signature = SigningKey::from_bytes(&[3u8; 32]).sign(MESSAGE).to_bytes();
+        assert_eq!(sign_with(signature).await, Ok(signature));
+
+        let mut tampered = signature;
+        tampered[0] ^= 1;
+        assert_eq!(
+            sign_with(tampered).await,
+            Err(WalletError::InvalidSignature)
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod account_ready_checks {
+    use std::cell::RefCell;
+
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Function;
+
+    use super::*;
+    use crate::test_support::{mock_function, TestWallet};
+
+    // Connect with [WalletAdapter::connect_and_wait_ready] and return the account,
+    // the number of connect requests and the warnings logged
+    async fn connect_ready(wallet: Wallet) -> (WalletAccount, JsValue, Vec<String>) {
+        let calls = mock_function(&wallet, "mockCalls");
+
+        let mut adapter = WalletAdapter::init().unwrap();
+        let warnings = Rc::new(RefCell::new(Vec::<String>::new()));
+        let warnings_writer = Rc::clone(&warnings);
+        adapter.set_logger(Rc::new(move |level: LogLevel, message: &str| {
+            if level == LogLevel::Warn {
+                warnings_writer.borrow_mut().push(message.to_string());
+            }
+        }));
+
+        let account = adapter.connect_and_wait_ready(wallet).await.unwrap();
+        assert_eq!(
+            adapter.connection_info().await.connected_account(),
+            Ok(&account)
+        );
+
+        let warnings = RefCell::borrow(&warnings).clone();
+        (account, calls.call0(&JsValue::null()).unwrap(), warnings)
+    }
+
+    #[wasm_bindgen_test]
+    async fn account_exposed_after_connect() {
+        let wallet = TestWallet::new().build();
+        // The wallet only exposes the account a while after the connect request resolved
+        Function::new_with_args("expose", "setTimeout(expose, 120);")
+            .call1(
+                &JsValue::null(),
+                &mock_function(&wallet, "mockExposeAccounts"),
+            )
+            .unwrap();
+
+        let (account, calls, warnings) = connect_ready(wallet).await;
+
+        assert_eq!(account.public_key(), [1u8; 32]);
+        assert_eq!(calls, JsValue::from(1));
+        assert!(warnings.is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    async fn gives_up_without_more_requests() {
+        let wallet = TestWallet::new().build();
+
+        let (account, calls, warnings) = connect_ready(wallet).await;
+
+        assert_eq!(account.public_key(), [1u8; 32]);
+        assert_eq!(calls, JsValue::from(1));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("DID NOT CONFIRM"));
+    }
+}
@@ -0,0 +1,22 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use web_sys::js_sys;
+
+/// The source of the current time used by the [crate::WalletAdapter] to validate
+/// the times of Sign In With Solana (SIWS) requests. Implement it with a fixed time
+/// to make the time checks deterministic in tests, see [crate::WalletAdapter::set_clock]
+pub trait Clock: core::fmt::Debug {
+    /// The current time
+    fn now(&self) -> SystemTime;
+}
+
+/// The default [Clock] fetching the time from [JavaScript Date Now](js_sys::Date::now()),
+/// since [SystemTime::now] is not available in the browser
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JsDateClock;
+
+impl Clock for JsDateClock {
+    fn now(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(js_sys::Date::now() as u64)
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{WalletError, WalletResult};
+
+/// The standard base64 alphabet of RFC 4648
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode `bytes` as padded standard base64, the encoding expected
+/// by the JSON-RPC methods of a Solana cluster for serialized transactions and messages
+pub fn to_base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let buffer = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |buffer, (index, byte)| {
+                buffer | (*byte as u32) << (16 - index * 8)
+            });
+
+        (0..4).for_each(|index| {
+            if index <= chunk.len() {
+                let sextet = (buffer >> (18 - index * 6)) & 0x3f;
+                encoded.push(BASE64_ALPHABET[sextet as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        });
+    }
+
+    encoded
+}
+
+/// Decode padded standard base64, for example a serialized transaction returned by a cluster
+pub fn from_base64(encoded: &str) -> WalletResult<Vec<u8>> {
+    let in_case_of_error = || WalletError::InvalidBase64(encoded.to_string());
+
+    if encoded.len() % 4 != 0 {
+        return Err(in_case_of_error());
+    }
+
+    let unpadded = encoded.trim_end_matches('=');
+    if encoded.len() - unpadded.len() > 2 {
+        return Err(in_case_of_error());
+    }
+
+    let mut decoded = Vec::with_capacity(encoded.len() / 4 * 3);
+
+    for chunk in unpadded.as_bytes().chunks(4) {
+        if chunk.len() == 1 {
+            return Err(in_case_of_error());
+        }
+
+        let mut buffer = 0u32;
+        for (index, character) in chunk.iter().enumerate() {
+            let sextet = BASE64_ALPHABET
+                .iter()
+                .position(|symbol| symbol == character)
+                .ok_or_else(in_case_of_error)?;
+
+            buffer |= (sextet as u32) << (18 - index * 6);
+        }
+
+        (0..chunk.len() - 1).for_each(|index| decoded.push((buffer >> (16 - index * 8)) as u8));
+    }
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod encoding_checks {
+    use super::*;
+
+    #[test]
+    fn known_vectors() {
+        for (bytes, encoded) in [
+            (b"".as_slice(), ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+            (b"fooba", "Zm9vYmE="),
+            (b"foobar", "Zm9vYmFy"),
+            (&[1, 2, 3], "AQID"),
+        ] {
+            assert_eq!(to_base64(bytes), encoded);
+            assert_eq!(from_base64(encoded).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn round_trip_non_ascii() {
+        let bytes = (0..=255u8).rev().collect::<Vec<u8>>();
+        assert_eq!(from_base64(&to_base64(&bytes)).unwrap(), bytes);
+
+        let utf8 = "Solana ☀️ ü".as_bytes();
+        assert_eq!(from_base64(&to_base64(utf8)).unwrap(), utf8);
+    }
+
+    #[test]
+    fn invalid_base64() {
+        for encoded in ["Zg=", "Zm9v!A==", "Z===", "Zg==Zg==", "A==="] {
+            assert_eq!(
+                Err(WalletError::InvalidBase64(encoded.to_string())),
+                from_base64(encoded)
+            );
+        }
+    }
+}
@@ -1,12 +1,19 @@
+use std::time::Duration;
+
 use thiserror::Error;
 use wallet_adapter_common::WalletUtilsError;
 use web_sys::js_sys::{wasm_bindgen::JsValue, Reflect};
 
-use crate::WalletEvent;
+use crate::{Reflection, WalletEvent};
 
 /// A Result<T, WalletError>
 pub type WalletResult<T> = Result<T, WalletError>;
 
+/// The `name` of the JavaScript error thrown by a wallet when the user rejects a request
+pub(crate) const USER_REJECTED_REQUEST_ERROR: &str = "UserRejectedRequestError";
+/// The EIP-1193 `code` of the JavaScript error thrown by a wallet when the user rejects a request
+pub(crate) const USER_REJECTED_REQUEST_CODE: u64 = 4001;
+
 impl From<async_channel::SendError<WalletEvent>> for WalletError {
     fn from(value: async_channel::SendError<WalletEvent>) -> Self {
         match value {
@@ -69,6 +76,9 @@ pub enum WalletError {
     /// The byte length should be equal to 64 bytes in length
     #[error("The byte length should be equal to 64 bytes in length")]
     Expected64ByteLength,
+    /// The wallet returned a signature that is not 64 bytes in length
+    #[error("Expected a signature of 64 bytes in length but the wallet returned `{0}` bytes")]
+    InvalidSignatureLength(usize),
     /// The version was not found
     #[error("The version was not found")]
     VersionNotFound,
@@ -96,9 +106,15 @@ pub enum WalletError {
         "Attempted to connect to an account that does not exist or might have been disconnected"
     )]
     AccountNotFound,
+    /// The account does not belong to the connected wallet
+    #[error("The account does not belong to the connected wallet")]
+    AccountNotInWallet,
     /// Unable to connect to a wallet. The user may have rejected the request
     #[error("Unable to connect to a wallet. Error `{0}` request")]
     WalletConnectError(String),
+    /// The user rejected the request in the browser wallet
+    #[error("The user rejected the request")]
+    UserRejected,
     /// The connect method did not return any accounts
     #[error("The connect method did not return any accounts")]
     ConnectHasNoAccounts,
@@ -138,12 +154,21 @@ pub enum WalletError {
     /// Invalid Base58 Address
     #[error("Invalid Base58 Address")]
     InvalidBase58Address,
+    /// The decoded public key is not 32 bytes in length
+    #[error("The public key length is expected to be 32 bytes but encountered public key of a length `{0}` bytes")]
+    InvalidEd25519PublicKeyLen(u8),
     /// The nonce is required to be at least 8 characters long
     #[error("The nonce is required to be at least 8 characters long")]
     NonceMustBeAtLeast8Characters,
+    /// The nonce is required to only contain alphanumeric characters
+    #[error("The nonce is required to only contain alphanumeric characters")]
+    NonceMustBeAlphanumeric,
     ///Expected a timestamp in the format specified by ISO8601
     #[error("Invalid ISO 8601 timestamp `{0}. Only timestamps in the format specified by ISO8601 are supported.")]
     InvalidISO8601Timestamp(String),
+    /// The SIWS resource is not a URI with a scheme
+    #[error("The resource `{0}` is not a URI with a scheme like `https://example.com`")]
+    InvalidResourceUri(String),
     /// The message signed by the wallet is not the same as the message sent to the wallet for signing
     #[error("The message signed by the wallet is not the same as the message sent to the wallet for signing")]
     MessageResponseMismatch,
@@ -159,18 +184,94 @@ pub enum WalletError {
     /// The function call to Sign A Message Is Missing
     #[error("The function call to Sign A Message Is Missing")]
     MissingSignMessageFunction,
+    /// The message is larger than the maximum size in bytes allowed by [crate::WalletAdapter::set_max_message_bytes]
+    #[error("The message of `{0}` bytes is larger than the maximum size allowed")]
+    MessageTooLarge(usize),
+    /// The message is required to be valid UTF-8
+    #[error("The message is not valid UTF-8")]
+    InvalidUtf8Message,
     /// The message sent to the wallet to be signed is different from the message the wallet responded with
     #[error("The message sent to the wallet to be signed is different from the message the wallet responded with")]
     SignedMessageMismatch,
     /// The Wallet returned an empty array of  signed messages
     #[error("The Wallet returned an empty array of  signed messages")]
     ReceivedAnEmptySignedMessagesArray,
+    /// The Wallet returned more than one signed transaction for a single transaction
+    #[error("The Wallet returned `{0}` signed transactions for a single transaction")]
+    UnexpectedSignedTransactionCount(usize),
     /// The `solana:signTransaction` function is missing in the provided wallet
     #[error("The `solana:signTransaction` function is missing in the provided wallet")]
     MissingSignTransactionFunction,
+    /// The `solana:signAllTransactions` function is missing in the provided wallet
+    #[error("The `solana:signAllTransactions` function is missing in the provided wallet")]
+    MissingSignAllTransactionsFunction,
+    /// The memo in [crate::SendOptions] is larger than the on-chain limit of 566 bytes
+    #[error("The memo of `{0}` bytes is larger than the maximum of 566 bytes")]
+    MemoTooLarge(usize),
     /// The `sendAndSignTransaction` method did not return any signature
     #[error("The `sendAndSignTransaction` method did not return any signature")]
     SendAndSignTransactionSignatureEmpty,
+    /// A URL of a [crate::MobileDeepLink] is not a valid `https` URL
+    #[error("The URL `{0}` is invalid, only `https` URLs are supported by mobile deep links")]
+    InvalidDeepLinkUrl(String),
+    /// A field required to build a [crate::MobileDeepLink] URI was not set
+    #[error("The `{0}` field of the mobile deep link is required")]
+    MissingDeepLinkField(String),
+    /// The bytes could not be parsed as a serialized transaction
+    #[error("Invalid transaction: {0}")]
+    InvalidTransaction(String),
+    /// The JSON-RPC request to a Solana cluster returned an error
+    #[error("The JSON-RPC request returned an error: `{0}`")]
+    RpcError(String),
+    /// The string is not valid padded standard base64
+    #[error("Invalid base64 string: `{0}`")]
+    InvalidBase64(String),
+    /// A connect request is already in progress, for example when the user double-clicks
+    /// a connect button
+    #[error("A connect request is already in progress")]
+    ConnectAlreadyInProgress,
+    /// The wallet did not respond to a sign in request before the deadline
+    #[error("The wallet did not respond to the sign in request within `{0:?}`")]
+    SignInTimeout(Duration),
+    /// The public key passed to a sign in request is not the public key of the connected account
+    #[error("The public key to sign in with is not the public key of the connected account")]
+    SignInKeyMismatch,
+    /// The address of a verification payload is not the Base58 encoded public key
+    #[error("The address `{0}` is not the Base58 encoded public key of the verification payload")]
+    AddressPublicKeyMismatch(String),
+    /// None of the bump seeds yielded a program derived address that is off the Ed25519 curve
+    #[error("No bump seed yielded a valid program derived address")]
+    ProgramAddressNotFound,
+    /// The string is not a Base58 encoded signature of 64 bytes
+    #[error("The string `{0}` is not a Base58 encoded signature of 64 bytes")]
+    InvalidBase58Signature(String),
+    /// The number of transactions per approval passed to
+    /// [crate::WalletAdapter::sign_all_transactions_chunked] is zero
+    #[error("The number of transactions signed per approval must be greater than zero")]
+    InvalidChunkSize,
+    /// The domain passed to [crate::WalletAdapter::sign_message_domain_separated] is longer
+    /// than the `32` bytes of the application domain of an off-chain message
+    #[error("The signing domain of `{0}` bytes is longer than the maximum of `32` bytes")]
+    SigningDomainTooLong(usize),
+    /// The transaction waited for with [crate::WalletAdapter::confirm_transaction]
+    /// failed with the error reported by the cluster
+    #[error("The transaction failed: {0}")]
+    TransactionFailed(String),
+    /// The transaction waited for with [crate::WalletAdapter::confirm_transaction]
+    /// did not reach the commitment within the timeout
+    #[error("The transaction was not confirmed within `{0:?}`")]
+    ConfirmationTimeout(Duration),
+    /// No event of the kind waited for with [crate::WalletAdapter::next_event_of] was
+    /// received before the deadline
+    #[error("No event of the kind `{0:?}` was received within `{1:?}`")]
+    EventWaitTimeout(crate::WalletEventKind, Duration),
+    /// The request was cancelled by the dapp using a [crate::CancelHandle]
+    #[error("The request was cancelled")]
+    Cancelled,
+    /// The JS number is not a valid unsigned integer, it is either fractional, negative
+    /// or larger than `Number.MAX_SAFE_INTEGER`
+    #[error("The JS number `{0}` is not a valid unsigned integer")]
+    InvalidNumber(String),
     /// Overflow during SystemTime::checked_add(expiration_time_milliseconds) overflow
     #[error("SystemTime::checked_add(expiration_time_milliseconds) overflow")]
     SystemTimeCheckedAddOverflow,
@@ -182,8 +283,164 @@ pub enum WalletError {
     Op(String),
 }
 
+impl WalletError {
+    /// Check whether the error was caused by the user rejecting the request in the browser wallet
+    pub fn is_user_rejection(&self) -> bool {
+        matches!(self, Self::UserRejected)
+    }
+
+    /// A stable numeric code of the error variant, for example to report errors to
+    /// JavaScript or analytics, or to look up a translated message, without matching on
+    /// the display string. Codes start at `1` and are never reused or changed,
+    /// a new variant gets the next unused code
+    pub fn code(&self) -> u32 {
+        match self {
+            Self::ChannelError => 1,
+            Self::JsError { .. } => 2,
+            Self::InternalError(..) => 3,
+            Self::ValueNotFound => 4,
+            Self::ExpectedValueNotFound(..) => 5,
+            Self::MissingAccessToBrowserWindow => 6,
+            Self::MissingAccessToBrowserDocument => 7,
+            Self::UnsupportedCommitment(..) => 8,
+            Self::InvalidWalletVersion(..) => 9,
+            Self::InvalidSemVerNumber(..) => 10,
+            Self::Expected32ByteLength => 11,
+            Self::Expected64ByteLength => 12,
+            Self::InvalidSignatureLength(..) => 13,
+            Self::VersionNotFound => 14,
+            Self::UnsupportedWalletFeature(..) => 15,
+            Self::UnsupportedTransactionVersion => 16,
+            Self::LegacyTransactionSupportRequired => 17,
+            Self::UnsupportedChain(..) => 18,
+            Self::MissingConnectFunction => 19,
+            Self::WalletNotFound => 20,
+            Self::AccountNotFound => 21,
+            Self::AccountNotInWallet => 22,
+            Self::WalletConnectError(..) => 23,
+            Self::UserRejected => 24,
+            Self::ConnectHasNoAccounts => 25,
+            Self::MissingDisconnectFunction => 26,
+            Self::MissingGetAccountsFunction(..) => 27,
+            Self::WalletDisconnectError(..) => 28,
+            Self::StandardEventsError(..) => 29,
+            Self::MissingStandardEventsFunction => 30,
+            Self::MissingSignInFunction => 31,
+            Self::ExpiryTimeEarlierThanIssuedTime => 32,
+            Self::NotBeforeTimeEarlierThanIssuedTime => 33,
+            Self::NotBeforeTimeLaterThanExpirationTime => 34,
+            Self::ExpirationTimeIsInThePast => 35,
+            Self::NotBeforeTimeIsInThePast => 36,
+            Self::InvalidBase58Address => 37,
+            Self::InvalidEd25519PublicKeyLen(..) => 38,
+            Self::NonceMustBeAtLeast8Characters => 39,
+            Self::NonceMustBeAlphanumeric => 40,
+            Self::InvalidISO8601Timestamp(..) => 41,
+            Self::InvalidResourceUri(..) => 42,
+            Self::MessageResponseMismatch => 43,
+            Self::InvalidSignature => 44,
+            Self::InvalidEd25519SignatureBytes => 45,
+            Self::InvalidEd25519PublicKeyBytes => 46,
+            Self::MissingSignMessageFunction => 47,
+            Self::MessageTooLarge(..) => 48,
+            Self::InvalidUtf8Message => 49,
+            Self::SignedMessageMismatch => 50,
+            Self::ReceivedAnEmptySignedMessagesArray => 51,
+            Self::UnexpectedSignedTransactionCount(..) => 52,
+            Self::MissingSignTransactionFunction => 53,
+            Self::MissingSignAllTransactionsFunction => 54,
+            Self::MemoTooLarge(..) => 55,
+            Self::SendAndSignTransactionSignatureEmpty => 56,
+            Self::InvalidDeepLinkUrl(..) => 57,
+            Self::MissingDeepLinkField(..) => 58,
+            Self::InvalidTransaction(..) => 59,
+            Self::RpcError(..) => 60,
+            Self::InvalidBase64(..) => 61,
+            Self::ConnectAlreadyInProgress => 62,
+            Self::SignInTimeout(..) => 63,
+            Self::SignInKeyMismatch => 64,
+            Self::AddressPublicKeyMismatch(..) => 65,
+            Self::ProgramAddressNotFound => 66,
+            Self::InvalidBase58Signature(..) => 67,
+            Self::EventWaitTimeout(..) => 68,
+            Self::Cancelled => 69,
+            Self::InvalidNumber(..) => 70,
+            Self::SystemTimeCheckedAddOverflow => 71,
+            Self::Op(..) => 72,
+            Self::InvalidChunkSize => 73,
+            Self::SigningDomainTooLong(..) => 74,
+            Self::TransactionFailed(..) => 75,
+            Self::ConfirmationTimeout(..) => 76,
+        }
+    }
+
+    /// Get the error variant with the `code`, see [Self::code]. Only variants without
+    /// data can be recovered, [None] is returned for the codes of the other variants
+    /// and for unknown codes
+    pub fn from_code(code: u32) -> Option<Self> {
+        let error = match code {
+            1 => Self::ChannelError,
+            4 => Self::ValueNotFound,
+            6 => Self::MissingAccessToBrowserWindow,
+            7 => Self::MissingAccessToBrowserDocument,
+            11 => Self::Expected32ByteLength,
+            12 => Self::Expected64ByteLength,
+            14 => Self::VersionNotFound,
+            16 => Self::UnsupportedTransactionVersion,
+            17 => Self::LegacyTransactionSupportRequired,
+            19 => Self::MissingConnectFunction,
+            20 => Self::WalletNotFound,
+            21 => Self::AccountNotFound,
+            22 => Self::AccountNotInWallet,
+            24 => Self::UserRejected,
+            25 => Self::ConnectHasNoAccounts,
+            26 => Self::MissingDisconnectFunction,
+            30 => Self::MissingStandardEventsFunction,
+            31 => Self::MissingSignInFunction,
+            32 => Self::ExpiryTimeEarlierThanIssuedTime,
+            33 => Self::NotBeforeTimeEarlierThanIssuedTime,
+            34 => Self::NotBeforeTimeLaterThanExpirationTime,
+            35 => Self::ExpirationTimeIsInThePast,
+            36 => Self::NotBeforeTimeIsInThePast,
+            37 => Self::InvalidBase58Address,
+            39 => Self::NonceMustBeAtLeast8Characters,
+            40 => Self::NonceMustBeAlphanumeric,
+            43 => Self::MessageResponseMismatch,
+            44 => Self::InvalidSignature,
+            45 => Self::InvalidEd25519SignatureBytes,
+            46 => Self::InvalidEd25519PublicKeyBytes,
+            47 => Self::MissingSignMessageFunction,
+            49 => Self::InvalidUtf8Message,
+            50 => Self::SignedMessageMismatch,
+            51 => Self::ReceivedAnEmptySignedMessagesArray,
+            53 => Self::MissingSignTransactionFunction,
+            54 => Self::MissingSignAllTransactionsFunction,
+            56 => Self::SendAndSignTransactionSignatureEmpty,
+            62 => Self::ConnectAlreadyInProgress,
+            64 => Self::SignInKeyMismatch,
+            66 => Self::ProgramAddressNotFound,
+            69 => Self::Cancelled,
+            71 => Self::SystemTimeCheckedAddOverflow,
+            73 => Self::InvalidChunkSize,
+            _ => return None,
+        };
+
+        Some(error)
+    }
+}
+
 impl From<JsValue> for WalletError {
     fn from(value: JsValue) -> Self {
+        // Wallets may throw or reject with a primitive like a string instead of an `Error`,
+        // which cannot be reflected
+        if !value.is_object() {
+            return Self::JsError {
+                name: "Error".to_string(),
+                message: value.as_string().unwrap_or(format!("{value:?}")),
+                stack: String::default(),
+            };
+        }
+
         let reflect = |key: &str| -> Result<String, Self> {
             Reflect::get(&value, &key.into())
                 .map_err(|error: JsValue| WalletError::InternalError(format!("{:?}", &error)))?
@@ -193,15 +450,22 @@ impl From<JsValue> for WalletError {
                 )))
         };
 
+        let code = Reflection::new(value.clone()).and_then(|reflection| reflection.opt_u64("code"));
+        if code == Ok(Some(USER_REJECTED_REQUEST_CODE)) {
+            return Self::UserRejected;
+        }
+
         let name = match reflect("name") {
             Ok(inner) => inner,
             Err(error) => return error,
         };
 
-        let stack = match reflect("stack") {
-            Ok(inner) => inner,
-            Err(error) => return error,
-        };
+        if name == USER_REJECTED_REQUEST_ERROR {
+            return Self::UserRejected;
+        }
+
+        // Not all errors have a stack, for example a `DOMException` in some browsers
+        let stack = reflect("stack").unwrap_or_default();
         let message = match reflect("message") {
             Ok(inner) => inner,
             Err(error) => return error,
@@ -226,20 +490,203 @@ impl From<WalletUtilsError> for WalletError {
             WalletUtilsError::NotBeforeTimeEarlierThanIssuedTime => {
                 Self::NotBeforeTimeEarlierThanIssuedTime
             }
-            WalletUtilsError::NotBeforeTimeIsInThePast => todo!(),
+            WalletUtilsError::NotBeforeTimeIsInThePast => Self::NotBeforeTimeIsInThePast,
             WalletUtilsError::NotBeforeTimeLaterThanExpirationTime => {
                 Self::NotBeforeTimeLaterThanExpirationTime
             }
             WalletUtilsError::InvalidISO8601Timestamp(value) => {
                 Self::InvalidISO8601Timestamp(value)
             }
+            WalletUtilsError::InvalidResourceUri(uri) => Self::InvalidResourceUri(uri),
             WalletUtilsError::InvalidBase58Address => Self::InvalidBase58Address,
+            WalletUtilsError::InvalidEd25519PublicKeyLen(len) => {
+                Self::InvalidEd25519PublicKeyLen(len)
+            }
             WalletUtilsError::InvalidEd25519PublicKeyBytes => Self::InvalidEd25519PublicKeyBytes,
             WalletUtilsError::InvalidSignature => Self::InvalidSignature,
             WalletUtilsError::Expected64ByteLength => Self::Expected64ByteLength,
             WalletUtilsError::Expected32ByteLength => Self::Expected32ByteLength,
             WalletUtilsError::NonceMustBeAtLeast8Characters => Self::NonceMustBeAtLeast8Characters,
+            WalletUtilsError::NonceMustBeAlphanumeric => Self::NonceMustBeAlphanumeric,
             WalletUtilsError::MessageResponseMismatch => Self::MessageResponseMismatch,
+            WalletUtilsError::UnsupportedChain(chain) => Self::UnsupportedChain(chain),
+            WalletUtilsError::AddressPublicKeyMismatch(address) => {
+                Self::AddressPublicKeyMismatch(address)
+            }
+            WalletUtilsError::ProgramAddressNotFound => Self::ProgramAddressNotFound,
+            WalletUtilsError::InvalidBase58Signature(signature) => {
+                Self::InvalidBase58Signature(signature)
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod utils_error_checks {
+    use std::time::{Duration, SystemTime};
+
+    use wallet_adapter_common::signin_standard::SigninInputBuilder;
+
+    use super::*;
+
+    #[test]
+    fn not_before_in_the_past() {
+        let now = SystemTime::now();
+        let build = || -> WalletResult<()> {
+            SigninInputBuilder::new()
+                .set_not_before(now - Duration::from_secs(60))
+                .build(now)?;
+
+            Ok(())
+        };
+
+        assert_eq!(Err(WalletError::NotBeforeTimeIsInThePast), build());
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod user_rejection_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::{self, Object};
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn rejection_to_user_rejected() {
+        let by_name = js_sys::Error::new("User rejected the request.");
+        by_name.set_name(USER_REJECTED_REQUEST_ERROR);
+        let by_name: WalletError = JsValue::from(by_name).into();
+        assert_eq!(by_name, WalletError::UserRejected);
+        assert!(by_name.is_user_rejection());
+
+        let by_code = Object::new();
+        Reflect::set(&by_code, &"code".into(), &4001.into()).unwrap();
+        Reflect::set(&by_code, &"message".into(), &"User rejected".into()).unwrap();
+        let by_code: WalletError = JsValue::from(by_code).into();
+        assert_eq!(by_code, WalletError::UserRejected);
+
+        let other: WalletError = JsValue::from(js_sys::Error::new("Extension crashed")).into();
+        assert!(matches!(other, WalletError::JsError { .. }));
+        assert!(!other.is_user_rejection());
+    }
+}
+
+#[cfg(test)]
+mod error_code_checks {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn unique_and_round_trip() {
+        let errors = [
+            WalletError::ChannelError,
+            WalletError::JsError {
+                name: String::default(),
+                message: String::default(),
+                stack: String::default(),
+            },
+            WalletError::InternalError(String::default()),
+            WalletError::ValueNotFound,
+            WalletError::ExpectedValueNotFound(String::default()),
+            WalletError::MissingAccessToBrowserWindow,
+            WalletError::MissingAccessToBrowserDocument,
+            WalletError::UnsupportedCommitment(String::default()),
+            WalletError::InvalidWalletVersion(String::default()),
+            WalletError::InvalidSemVerNumber(String::default()),
+            WalletError::Expected32ByteLength,
+            WalletError::Expected64ByteLength,
+            WalletError::InvalidSignatureLength(0),
+            WalletError::VersionNotFound,
+            WalletError::UnsupportedWalletFeature(String::default()),
+            WalletError::UnsupportedTransactionVersion,
+            WalletError::LegacyTransactionSupportRequired,
+            WalletError::UnsupportedChain(String::default()),
+            WalletError::MissingConnectFunction,
+            WalletError::WalletNotFound,
+            WalletError::AccountNotFound,
+            WalletError::AccountNotInWallet,
+            WalletError::WalletConnectError(String::default()),
+            WalletError::UserRejected,
+            WalletError::ConnectHasNoAccounts,
+            WalletError::MissingDisconnectFunction,
+            WalletError::MissingGetAccountsFunction(String::default()),
+            WalletError::WalletDisconnectError(String::default()),
+            WalletError::StandardEventsError(String::default()),
+            WalletError::MissingStandardEventsFunction,
+            WalletError::MissingSignInFunction,
+            WalletError::ExpiryTimeEarlierThanIssuedTime,
+            WalletError::NotBeforeTimeEarlierThanIssuedTime,
+            WalletError::NotBeforeTimeLaterThanExpirationTime,
+            WalletError::ExpirationTimeIsInThePast,
+            WalletError::NotBeforeTimeIsInThePast,
+            WalletError::InvalidBase58Address,
+            WalletError::InvalidEd25519PublicKeyLen(0),
+            WalletError::NonceMustBeAtLeast8Characters,
+            WalletError::NonceMustBeAlphanumeric,
+            WalletError::InvalidISO8601Timestamp(String::default()),
+            WalletError::InvalidResourceUri(String::default()),
+            WalletError::MessageResponseMismatch,
+            WalletError::InvalidSignature,
+            WalletError::InvalidEd25519SignatureBytes,
+            WalletError::InvalidEd25519PublicKeyBytes,
+            WalletError::MissingSignMessageFunction,
+            WalletError::MessageTooLarge(0),
+            WalletError::InvalidUtf8Message,
+            WalletError::SignedMessageMismatch,
+            WalletError::ReceivedAnEmptySignedMessagesArray,
+            WalletError::UnexpectedSignedTransactionCount(0),
+            WalletError::MissingSignTransactionFunction,
+            WalletError::MissingSignAllTransactionsFunction,
+            WalletError::MemoTooLarge(0),
+            WalletError::SendAndSignTransactionSignatureEmpty,
+            WalletError::InvalidDeepLinkUrl(String::default()),
+            WalletError::MissingDeepLinkField(String::default()),
+            WalletError::InvalidTransaction(String::default()),
+            WalletError::RpcError(String::default()),
+            WalletError::InvalidBase64(String::default()),
+            WalletError::ConnectAlreadyInProgress,
+            WalletError::SignInTimeout(Duration::ZERO),
+            WalletError::SignInKeyMismatch,
+            WalletError::AddressPublicKeyMismatch(String::default()),
+            WalletError::ProgramAddressNotFound,
+            WalletError::InvalidBase58Signature(String::default()),
+            WalletError::EventWaitTimeout(crate::WalletEventKind::Connected, Duration::ZERO),
+            WalletError::Cancelled,
+            WalletError::InvalidNumber(String::default()),
+            WalletError::SystemTimeCheckedAddOverflow,
+            WalletError::Op(String::default()),
+            WalletError::InvalidChunkSize,
+            WalletError::SigningDomainTooLong(0),
+            WalletError::TransactionFailed(String::default()),
+            WalletError::ConfirmationTimeout(Duration::ZERO),
+        ];
+
+        let codes = errors
+            .iter()
+            .map(WalletError::code)
+            .collect::<HashSet<u32>>();
+        assert_eq!(codes.len(), errors.len());
+
+        assert_eq!(codes, (1..=errors.len() as u32).collect());
+
+        // Only variants without data are recovered and they must round-trip
+        let recovered = errors
+            .iter()
+            .filter_map(|error| WalletError::from_code(error.code()).map(|code| (code, error)))
+            .inspect(|(recovered, error)| assert_eq!(recovered, *error))
+            .count();
+        assert_eq!(recovered, 43);
+
+        assert_eq!(
+            WalletError::from_code(WalletError::UserRejected.code()),
+            Some(WalletError::UserRejected)
+        );
+        assert_eq!(
+            WalletError::from_code(WalletError::RpcError(String::default()).code()),
+            None
+        );
+        assert_eq!(WalletError::from_code(0), None);
+    }
+}
@@ -1,6 +1,12 @@
-use std::rc::Rc;
+use std::{
+    rc::Rc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
 
-use async_channel::{Receiver, Sender};
+use async_channel::{bounded, unbounded, Receiver, SendError, Sender};
 use wallet_adapter_common::standardized_events::{
     WINDOW_APP_READY_EVENT_TYPE, WINDOW_REGISTER_WALLET_EVENT_TYPE,
 };
@@ -10,19 +16,132 @@ use web_sys::{
     CustomEvent, CustomEventInit, Window,
 };
 
-use sha3::{Digest, Sha3_256};
-
 use crate::{
-    InnerUtils, Reflection, StorageType, Wallet, WalletAccount, WalletAdapter, WalletError,
-    WalletResult,
+    send_wallet_event, ConnectionInfoInner, InnerUtils, Logger, Reflection, SemverVersion,
+    StorageType, Wallet, WalletAccount, WalletAdapter, WalletError, WalletResult, WalletStorage,
 };
 
-/// The `Sender` part of an [async_channel::bounded] channel
-pub type WalletEventSender = Sender<WalletEvent>;
-
 /// The `Receiver` part of an [async_channel::bounded] channel
 pub type WalletEventReceiver = Receiver<WalletEvent>;
 
+/// The `Receiver` part of an [async_channel::bounded] channel
+/// of [WalletEventEnvelope]s
+pub type WalletEventEnvelopeReceiver = Receiver<WalletEventEnvelope>;
+
+/// A [WalletEvent] tagged with a monotonically increasing sequence number.
+/// Consumers can discard an event whose `seq` is lower than
+/// that of an event they have already processed.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Clone)]
+pub struct WalletEventEnvelope {
+    /// The sequence number of the event, starting at `1`
+    pub seq: u64,
+    /// The event
+    pub event: WalletEvent,
+}
+
+/// The `Sender` part of the [async_channel::bounded] channels used to emit [WalletEvent]s.
+/// Every event sent is assigned the next sequence number and is delivered
+/// to both the [WalletEventReceiver] and the [WalletEventEnvelopeReceiver]
+#[derive(Debug, Clone)]
+pub struct WalletEventSender {
+    events: Sender<WalletEvent>,
+    sequenced_events: Sender<WalletEventEnvelope>,
+    seq: Arc<AtomicU64>,
+    subscribers: Arc<Mutex<Vec<Sender<WalletEvent>>>>,
+}
+
+impl WalletEventSender {
+    /// Instantiate a [WalletEventSender] from the sender of the [WalletEvent] channel
+    /// and the sender of the [WalletEventEnvelope] channel
+    pub fn new(events: Sender<WalletEvent>, sequenced_events: Sender<WalletEventEnvelope>) -> Self {
+        Self {
+            events,
+            sequenced_events,
+            seq: Arc::default(),
+            subscribers: Arc::default(),
+        }
+    }
+
+    /// Get a receiver of its own for the events sent from now on. Unlike clones of the
+    /// [WalletEventReceiver], which compete for each event, every subscriber receives a copy
+    /// of every event. The subscription ends when the receiver is dropped
+    pub(crate) fn subscribe(&self) -> WalletEventReceiver {
+        let (sender, receiver) = unbounded::<WalletEvent>();
+
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.push(sender);
+        }
+
+        receiver
+    }
+
+    /// Send an event. The sequenced channel never blocks, when it is full
+    /// the oldest [WalletEventEnvelope] is dropped so that consumers
+    /// which do not listen for sequenced events are not affected
+    pub async fn send(&self, event: WalletEvent) -> Result<(), SendError<WalletEvent>> {
+        let seq = self.seq.fetch_add(1, Ordering::SeqCst) + 1;
+
+        let _ = self.sequenced_events.force_send(WalletEventEnvelope {
+            seq,
+            event: event.clone(),
+        });
+
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.retain(|subscriber| subscriber.try_send(event.clone()).is_ok());
+        }
+
+        self.events.send(event).await
+    }
+
+    /// The sequence number of the last event sent
+    pub fn last_seq(&self) -> u64 {
+        self.seq.load(Ordering::SeqCst)
+    }
+}
+
+impl From<Sender<WalletEvent>> for WalletEventSender {
+    /// Wrap the sender of a [WalletEvent] channel, which [WalletEventSender] was an alias of
+    /// before version 2.0.0. The [WalletEventEnvelope]s are discarded
+    fn from(events: Sender<WalletEvent>) -> Self {
+        Self::new(events, unbounded().0)
+    }
+}
+
+/// The state of the [WalletAdapter] updated when a wallet registers itself
+#[derive(Debug, Clone)]
+pub struct Registration {
+    registered: Sender<()>,
+    connection_info: ConnectionInfoInner,
+    sender: WalletEventSender,
+    logger: Logger,
+}
+
+impl Registration {
+    /// Instantiate [Registration] with the channels and connection of the `adapter`
+    pub fn new(adapter: &WalletAdapter) -> Self {
+        Self {
+            registered: adapter.wallet_registered_sender(),
+            connection_info: adapter.connection_info_inner(),
+            sender: adapter.wallet_events_sender(),
+            logger: adapter.logger(),
+        }
+    }
+
+    /// A [Registration] not attached to any [WalletAdapter], registered wallets
+    /// are only added to the storage
+    fn detached() -> Self {
+        let logger = Logger::default();
+        logger.set(Rc::new(|_, _| {}));
+
+        Self {
+            registered: bounded(1).0,
+            connection_info: ConnectionInfoInner::default(),
+            sender: WalletEventSender::new(bounded(1).0, bounded(1).0),
+            logger,
+        }
+    }
+}
+
 /// Used to initialize the `Register` and `AppReady` events to the browser window
 #[derive(Debug, PartialEq, Eq)]
 pub struct InitEvents<'a> {
@@ -39,19 +158,37 @@ impl<'a> InitEvents<'a> {
     /// all registered wallets
     pub fn init(&self, adapter: &mut WalletAdapter) -> WalletResult<()> {
         let storage = adapter.storage();
-        self.register_wallet_event(storage.clone_inner())?;
-        self.dispatch_app_event(storage.clone_inner());
+        let registration = Registration::new(adapter);
+        self.register_wallet_event_with_registration(storage.clone_inner(), registration.clone())?;
+        self.dispatch_app_event_with_registration(storage.clone_inner(), registration);
 
         Ok(())
     }
 
     /// An App Ready event registered to the browser window
+    #[deprecated(
+        since = "2.0.0",
+        note = "use `dispatch_app_event_with_registration` to also update the adapter"
+    )]
     pub fn dispatch_app_event(&self, storage: StorageType) {
+        self.dispatch_app_event_with_registration(storage, Registration::detached())
+    }
+
+    /// An App Ready event registered to the browser window, wallets registering
+    /// in response update the [Registration]
+    pub fn dispatch_app_event_with_registration(
+        &self,
+        storage: StorageType,
+        registration: Registration,
+    ) {
         let app_ready_init = CustomEventInit::new();
         app_ready_init.set_bubbles(false);
         app_ready_init.set_cancelable(false);
         app_ready_init.set_composed(false);
-        app_ready_init.set_detail(&Self::register_object(storage));
+        app_ready_init.set_detail(&Self::register_object_with_registration(
+            storage,
+            registration,
+        ));
 
         let app_ready_ev =
             CustomEvent::new_with_event_init_dict(WINDOW_APP_READY_EVENT_TYPE, &app_ready_init)
@@ -61,7 +198,21 @@ impl<'a> InitEvents<'a> {
     }
 
     /// The register wallet event registered to the browser window
+    #[deprecated(
+        since = "2.0.0",
+        note = "use `register_wallet_event_with_registration` to also update the adapter"
+    )]
     pub fn register_wallet_event(&self, storage: StorageType) -> WalletResult<()> {
+        self.register_wallet_event_with_registration(storage, Registration::detached())
+    }
+
+    /// The register wallet event registered to the browser window, wallets registering
+    /// through it update the [Registration]
+    pub fn register_wallet_event_with_registration(
+        &self,
+        storage: StorageType,
+        registration: Registration,
+    ) -> WalletResult<()> {
         let inner_storage = Rc::clone(&storage);
 
         let listener_closure = Closure::wrap(Box::new(move |custom_event: CustomEvent| {
@@ -71,7 +222,10 @@ impl<'a> InitEvents<'a> {
 
             InnerUtils::jsvalue_to_error(detail.call1(
                 &JsValue::null(),
-                &Self::register_object(inner_storage.clone()),
+                &Self::register_object_with_registration(
+                    inner_storage.clone(),
+                    registration.clone(),
+                ),
             ))
             .unwrap()
         }) as Box<dyn Fn(_)>);
@@ -88,18 +242,51 @@ impl<'a> InitEvents<'a> {
     }
 
     /// Sets the object to be passed to the register function
+    #[deprecated(
+        since = "2.0.0",
+        note = "use `register_object_with_registration` to also update the adapter"
+    )]
     pub fn register_object(storage: StorageType) -> Object {
+        Self::register_object_with_registration(storage, Registration::detached())
+    }
+
+    /// Sets the object to be passed to the register function.
+    /// The `registered` channel of the [Registration] is notified every time a wallet
+    /// is registered and a re-registered connected wallet is checked for a downgrade
+    pub fn register_object_with_registration(
+        storage: StorageType,
+        registration: Registration,
+    ) -> Object {
         // The `register` function that logs and returns a closure like in your JS code
         let register =
             Closure::wrap(
                 Box::new(move |value: JsValue| match Wallet::from_jsvalue(value) {
                     Ok(wallet) => {
-                        let inner_outcome = storage.clone();
+                        let connection_info = registration.connection_info.clone();
+                        let sender = registration.sender.clone();
+                        let logger = registration.logger.clone();
+                        let registered_wallet = wallet.clone();
+
+                        let is_duplicate = WalletStorage::register(&storage, wallet);
+
+                        wasm_bindgen_futures::spawn_local(async move {
+                            if is_duplicate {
+                                let duplicate = WalletEvent::DuplicateWalletName(
+                                    registered_wallet.name().to_string(),
+                                );
+
+                                send_wallet_event(duplicate, sender, &logger).await
+                            } else {
+                                connection_info
+                                    .write()
+                                    .await
+                                    .on_version_change(&registered_wallet, sender)
+                                    .await
+                            }
+                        });
 
-                        inner_outcome.borrow_mut().insert(
-                            Sha3_256::digest(wallet.name().to_lowercase().as_bytes()).into(),
-                            wallet,
-                        );
+                        // A full channel already has a pending notification
+                        let _ = registration.registered.try_send(());
                     }
                     Err(error) => {
                         let error = error.to_string();
@@ -150,11 +337,82 @@ pub enum WalletEvent {
     /// `on` method from the `[standard:events]` namespace
     /// (when an account is connected, changed or disconnected)
     BackgroundTaskError(WalletError),
+    /// The wallet does not support `solana:signAllTransactions` so each of the
+    /// transactions is signed separately and the user will be asked to approve
+    /// this number of requests.
+    MultipleApprovalsRequired(usize),
+    /// A different wallet registered under the same name as an already registered wallet,
+    /// for example two extensions both registering as `Solana`. Both wallets are kept,
+    /// see [crate::WalletStorage::wallets_by_name]
+    DuplicateWalletName(String),
+    /// The connected wallet registered itself again with a lower version,
+    /// for example after the user downgraded the browser extension,
+    /// which may break features the dapp relies on
+    WalletDowngraded {
+        /// The version of the wallet when it was connected
+        from: SemverVersion,
+        /// The version of the re-registered wallet
+        to: SemverVersion,
+    },
     /// An event was emitted by a wallet that is not connected.
     #[default]
     Skip,
 }
 
+impl WalletEvent {
+    /// Get the [WalletEventKind] of the event, discarding the data it contains
+    pub fn kind(&self) -> WalletEventKind {
+        match self {
+            Self::Connected(_) => WalletEventKind::Connected,
+            Self::Reconnected(_) => WalletEventKind::Reconnected,
+            Self::Disconnected => WalletEventKind::Disconnected,
+            Self::AccountChanged(_) => WalletEventKind::AccountChanged,
+            Self::BackgroundTaskError(_) => WalletEventKind::BackgroundTaskError,
+            Self::MultipleApprovalsRequired(_) => WalletEventKind::MultipleApprovalsRequired,
+            Self::DuplicateWalletName(_) => WalletEventKind::DuplicateWalletName,
+            Self::WalletDowngraded { .. } => WalletEventKind::WalletDowngraded,
+            Self::Skip => WalletEventKind::Skip,
+        }
+    }
+
+    /// Check whether the event changes the connection state of the wallet adapter,
+    /// that is [Self::Connected], [Self::Reconnected], [Self::Disconnected] or [Self::AccountChanged]
+    pub fn is_connection_event(&self) -> bool {
+        matches!(
+            self,
+            Self::Connected(_)
+                | Self::Reconnected(_)
+                | Self::Disconnected
+                | Self::AccountChanged(_)
+        )
+    }
+}
+
+/// The kind of a [WalletEvent] without the data it contains,
+/// for example to wait for the next event of a kind using [crate::WalletAdapter::next_event_of]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Clone, Copy)]
+pub enum WalletEventKind {
+    /// See [WalletEvent::Connected]
+    Connected,
+    /// See [WalletEvent::Reconnected]
+    Reconnected,
+    /// See [WalletEvent::Disconnected]
+    Disconnected,
+    /// See [WalletEvent::AccountChanged]
+    AccountChanged,
+    /// See [WalletEvent::BackgroundTaskError]
+    BackgroundTaskError,
+    /// See [WalletEvent::MultipleApprovalsRequired]
+    MultipleApprovalsRequired,
+    /// See [WalletEvent::DuplicateWalletName]
+    DuplicateWalletName,
+    /// See [WalletEvent::WalletDowngraded]
+    WalletDowngraded,
+    /// See [WalletEvent::Skip]
+    #[default]
+    Skip,
+}
+
 impl core::fmt::Display for WalletEvent {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let as_str = match self {
@@ -163,6 +421,13 @@ impl core::fmt::Display for WalletEvent {
             Self::Disconnected => "Disconnected",
             Self::AccountChanged(_) => "Account Changed",
             Self::BackgroundTaskError(error) => &format!("Task error: {error:?}"),
+            Self::MultipleApprovalsRequired(count) => {
+                &format!("Multiple Approvals Required: {count}")
+            }
+            Self::DuplicateWalletName(name) => &format!("Duplicate Wallet Name: {name}"),
+            Self::WalletDowngraded { from, to } => {
+                &format!("Wallet Downgraded from {} to {}", from.0, to.0)
+            }
             Self::Skip => "Skipped",
         };
         write!(f, "{as_str}")
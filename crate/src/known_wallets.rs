@@ -0,0 +1,96 @@
+use crate::{WalletName, WalletStorage};
+
+/// A well-known Solana wallet that can be suggested to the user when it is not installed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KnownWallet {
+    name: &'static str,
+    download_url: &'static str,
+    icon: &'static str,
+}
+
+impl KnownWallet {
+    /// The name the wallet registers itself with
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The URL of the page where the user can install the wallet
+    pub fn download_url(&self) -> &'static str {
+        self.download_url
+    }
+
+    /// The URL of the icon of the wallet
+    pub fn icon(&self) -> &'static str {
+        self.icon
+    }
+
+    /// Check if the wallet registered itself with the `storage`
+    pub fn is_installed(&self, storage: &WalletStorage) -> bool {
+        storage.get_wallet(self.name).is_some()
+    }
+
+    /// Check if `wallet_name` is the name of this wallet, ignoring case and surrounding whitespace
+    pub fn is_named(&self, wallet_name: &str) -> bool {
+        WalletName::new(self.name) == WalletName::new(wallet_name)
+    }
+}
+
+const KNOWN_WALLETS: &[KnownWallet] = &[
+    KnownWallet {
+        name: "Phantom",
+        download_url: "https://phantom.com/download",
+        icon: "https://phantom.com/favicon.ico",
+    },
+    KnownWallet {
+        name: "Solflare",
+        download_url: "https://solflare.com/download",
+        icon: "https://solflare.com/favicon.ico",
+    },
+    KnownWallet {
+        name: "Backpack",
+        download_url: "https://backpack.app/download",
+        icon: "https://backpack.app/favicon.ico",
+    },
+];
+
+/// The curated list of well-known Solana wallets, for example to show
+/// an "Install Phantom" prompt for the wallets that are not installed
+pub fn known_wallets() -> &'static [KnownWallet] {
+    KNOWN_WALLETS
+}
+
+impl WalletStorage {
+    /// Get the [known wallets](known_wallets) that have not registered themselves
+    pub fn missing_known_wallets(&self) -> Vec<KnownWallet> {
+        known_wallets()
+            .iter()
+            .filter(|known_wallet| !known_wallet.is_installed(self))
+            .copied()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod known_wallets_checks {
+    use super::*;
+
+    #[test]
+    fn missing_from_storage() {
+        let storage = WalletStorage::default();
+
+        let missing = storage.missing_known_wallets();
+        assert_eq!(missing.len(), known_wallets().len());
+
+        let phantom = missing
+            .iter()
+            .find(|known_wallet| known_wallet.is_named("phantom"))
+            .unwrap();
+        assert_eq!(phantom.name(), "Phantom");
+        assert!(phantom.download_url().starts_with("https://"));
+        assert!(!phantom.is_installed(&storage));
+
+        assert!(!known_wallets()
+            .iter()
+            .any(|known_wallet| known_wallet.is_named("Missing Wallet")));
+    }
+}
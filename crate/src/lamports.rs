@@ -0,0 +1,98 @@
+/// The number of lamports in one SOL
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// The number of decimal places of SOL
+const SOL_DECIMALS: usize = 9;
+
+/// An amount of lamports, the smallest unit of SOL, for example the balance
+/// returned by [crate::WalletAdapter::get_balance]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lamports(pub u64);
+
+impl Lamports {
+    /// Convert the lamports to SOL. Amounts larger than `2^53` lamports
+    /// lose precision as an [f64], use [Self::to_sol_string] to display them exactly
+    pub fn to_sol(&self) -> f64 {
+        self.0 as f64 / LAMPORTS_PER_SOL as f64
+    }
+
+    /// Format the lamports as SOL with at most `decimals` decimal places, for example `1.5`.
+    /// The digits beyond `decimals` are truncated instead of rounded so a balance
+    /// is never overstated, and trailing zeros are removed.
+    /// The conversion uses integer arithmetic so it is exact for any amount
+    pub fn to_sol_string(&self, decimals: usize) -> String {
+        let whole = self.0 / LAMPORTS_PER_SOL;
+        let fraction = format!("{:0SOL_DECIMALS$}", self.0 % LAMPORTS_PER_SOL);
+        let fraction = fraction[..decimals.min(SOL_DECIMALS)].trim_end_matches('0');
+
+        if fraction.is_empty() {
+            whole.to_string()
+        } else {
+            format!("{whole}.{fraction}")
+        }
+    }
+
+    /// Convert an amount of SOL to lamports, rounded to the nearest lamport.
+    /// Negative amounts and `NaN` convert to zero lamports
+    /// and amounts too large for a [u64] to [u64::MAX] lamports
+    pub fn from_sol(sol: f64) -> Lamports {
+        Lamports((sol * LAMPORTS_PER_SOL as f64).round() as u64)
+    }
+}
+
+impl core::fmt::Display for Lamports {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<u64> for Lamports {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+#[cfg(test)]
+mod lamports_checks {
+    use super::*;
+
+    #[test]
+    fn exact_conversions() {
+        assert_eq!(Lamports(LAMPORTS_PER_SOL).to_sol(), 1.0);
+        assert_eq!(Lamports(3 * LAMPORTS_PER_SOL).to_sol_string(4), "3");
+        assert_eq!(Lamports(0).to_sol_string(9), "0");
+        assert_eq!(Lamports::from_sol(2.0), Lamports(2 * LAMPORTS_PER_SOL));
+        assert_eq!(Lamports::from_sol(0.0), Lamports(0));
+    }
+
+    #[test]
+    fn fractional_conversions() {
+        assert_eq!(Lamports(1_500_000_000).to_sol(), 1.5);
+        assert_eq!(Lamports(1_500_000_000).to_sol_string(9), "1.5");
+        assert_eq!(Lamports(1).to_sol_string(9), "0.000000001");
+        assert_eq!(Lamports(1).to_sol_string(8), "0");
+        assert_eq!(Lamports(1_234_567_891).to_sol_string(4), "1.2345");
+        assert_eq!(Lamports(1_999_999_999).to_sol_string(2), "1.99");
+        assert_eq!(Lamports(1_234_567_891).to_sol_string(20), "1.234567891");
+
+        assert_eq!(Lamports::from_sol(0.000000001), Lamports(1));
+        assert_eq!(Lamports::from_sol(1.1), Lamports(1_100_000_000));
+        assert_eq!(Lamports::from_sol(-1.0), Lamports(0));
+        assert_eq!(Lamports::from_sol(f64::NAN), Lamports(0));
+    }
+
+    #[test]
+    fn beyond_f64_precision() {
+        // Not exactly representable as an `f64`
+        let lamports = Lamports(9_007_199_254_740_993);
+
+        assert_ne!(
+            (lamports.to_sol() * LAMPORTS_PER_SOL as f64) as u64,
+            lamports.0
+        );
+        assert_eq!(lamports.to_sol_string(9), "9007199.254740993");
+
+        assert_eq!(Lamports(u64::MAX).to_sol_string(9), "18446744073.709551615");
+        assert_eq!(Lamports::from_sol(f64::MAX), Lamports(u64::MAX));
+    }
+}
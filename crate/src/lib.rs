@@ -17,6 +17,15 @@ pub use commitment::*;
 mod utils;
 pub(crate) use utils::*;
 
+mod rpc;
+pub use rpc::*;
+
+mod logger;
+pub use logger::*;
+
+mod clock;
+pub use clock::*;
+
 mod events;
 pub use events::*;
 
@@ -26,12 +35,48 @@ pub use wallet_ser_der::*;
 mod storage;
 pub use storage::*;
 
+mod mobile;
+pub use mobile::*;
+
+mod known_wallets;
+pub use known_wallets::*;
+
+mod lamports;
+pub use lamports::*;
+
+mod simulation;
+pub use simulation::*;
+
+mod subscription;
+pub use subscription::*;
+
+#[cfg(feature = "dev")]
+mod mock_wallet;
+#[cfg(feature = "dev")]
+pub use mock_wallet::*;
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+pub(crate) mod test_support;
+
+/// Helpers to inspect serialized Solana transactions
+pub mod transaction;
+
+/// Base64 helpers to encode serialized transactions for JSON-RPC requests
+pub mod encoding;
+
 // Re-export common crate
 pub use wallet_adapter_common::chains::*;
 pub use wallet_adapter_common::clusters::*;
 pub use wallet_adapter_common::feature_support::*;
+pub use wallet_adapter_common::signin_standard::{
+    verify_payload, ResourceUri, VerificationPayload,
+};
 pub use wallet_adapter_common::standardized_events::*;
 pub use wallet_adapter_common::WalletCommonUtils as Utils;
+pub use wallet_adapter_common::{
+    associated_token_address, find_program_address, ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
 
 // Re-export of crates
 pub use async_channel;
@@ -0,0 +1,73 @@
+use std::{cell::RefCell, rc::Rc};
+
+/// The severity of a diagnostic message logged by the wallet adapter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    /// Information useful when debugging the interaction with a wallet
+    Debug,
+    /// Errors that were recovered from, for example a failed attempt that is retried
+    Warn,
+    /// Errors that could not be returned to the caller, for example in background tasks
+    Error,
+}
+
+/// A hook receiving the diagnostics of the wallet adapter,
+/// see [crate::WalletAdapter::set_logger]
+pub type LoggerFn = Rc<dyn Fn(LogLevel, &str)>;
+
+/// Routes the internal diagnostics of the wallet adapter to a hook, logging to the
+/// browser console if no hook was set. Clones share the hook so that setting it on the
+/// [crate::WalletAdapter] also applies to the connected wallet and background tasks.
+/// All loggers are considered equal so that types containing it can still be compared.
+#[derive(Clone, Default)]
+pub(crate) struct Logger(Rc<RefCell<Option<LoggerFn>>>);
+
+impl Logger {
+    /// Replace the hook of this logger and all its clones
+    pub(crate) fn set(&self, logger: LoggerFn) {
+        self.0.replace(Some(logger));
+    }
+
+    /// Log the `message` with the hook or to the browser console if no hook was set
+    pub(crate) fn log(&self, level: LogLevel, message: &str) {
+        // Clone the hook so that it can replace itself without a double borrow
+        let logger = self.0.borrow().clone();
+
+        match logger {
+            Some(logger) => logger(level, message),
+            None => match level {
+                LogLevel::Debug => web_sys::console::log_1(&message.into()),
+                LogLevel::Warn => web_sys::console::warn_1(&message.into()),
+                LogLevel::Error => web_sys::console::error_1(&message.into()),
+            },
+        }
+    }
+}
+
+impl core::fmt::Debug for Logger {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Logger")
+            .field(&self.0.borrow().is_some())
+            .finish()
+    }
+}
+
+impl PartialEq for Logger {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Logger {}
+
+impl PartialOrd for Logger {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Logger {
+    fn cmp(&self, _other: &Self) -> core::cmp::Ordering {
+        core::cmp::Ordering::Equal
+    }
+}
@@ -0,0 +1,284 @@
+use crate::{Cluster, WalletError, WalletResult};
+
+/// The universal link Phantom answers connect requests on
+pub const PHANTOM_CONNECT_URI: &str = "https://phantom.app/ul/v1/connect";
+
+/// The universal link Solflare answers connect requests on
+pub const SOLFLARE_CONNECT_URI: &str = "https://solflare.com/ul/v1/connect";
+
+/// Builds the universal link used to request a connection from a mobile wallet
+/// implementing the Phantom deep link protocol, like Phantom and Solflare.
+/// Mobile wallets are not injected into the browser window, instead the dapp
+/// renders the URI, for example as a QR code, and the wallet redirects to the
+/// `redirect_link` once the user approves the connection.
+///
+/// The wallet fetches the name and icon of the dapp from the metadata of the page at
+/// the `app_url`. The response is encrypted with a shared secret derived from the
+/// `dapp_encryption_public_key`, the dapp keeps the x25519 secret key of that
+/// public key to decrypt it.
+///
+/// #### Example
+/// ```rust
+/// use wallet_adapter::{Cluster, MobileDeepLink};
+///
+/// let mut deep_link = MobileDeepLink::new();
+/// deep_link
+///     .set_app_url("https://foo.example")
+///     .unwrap()
+///     .set_dapp_encryption_public_key([1u8; 32])
+///     .set_cluster(Cluster::MainNet)
+///     .set_redirect_link("https://foo.example/connected")
+///     .unwrap();
+///
+/// assert!(deep_link
+///     .uri()
+///     .unwrap()
+///     .contains("app_url=https%3A%2F%2Ffoo.example"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MobileDeepLink {
+    base_uri: String,
+    app_url: Option<String>,
+    dapp_encryption_public_key: Option<[u8; 32]>,
+    cluster: Cluster,
+    redirect_link: Option<String>,
+}
+
+impl MobileDeepLink {
+    /// Instantiate a [MobileDeepLink] using the [PHANTOM_CONNECT_URI]
+    /// and the default [Cluster]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the base URI, for example [SOLFLARE_CONNECT_URI]
+    pub fn set_base_uri(&mut self, base_uri: &str) -> &mut Self {
+        self.base_uri = base_uri.to_string();
+
+        self
+    }
+
+    /// Set the URL of the dapp the wallet fetches the name and icon from.
+    /// Only `https` URLs are accepted
+    pub fn set_app_url(&mut self, app_url: &str) -> WalletResult<&mut Self> {
+        self.app_url.replace(Self::https_url(app_url)?);
+
+        Ok(self)
+    }
+
+    /// Set the x25519 public key the wallet uses to encrypt its response to the dapp
+    pub fn set_dapp_encryption_public_key(&mut self, public_key: [u8; 32]) -> &mut Self {
+        self.dapp_encryption_public_key.replace(public_key);
+
+        self
+    }
+
+    /// Set the [Cluster] the dapp requests the wallet to connect to.
+    /// [Cluster::LocalNet] is not supported by mobile wallets
+    pub fn set_cluster(&mut self, cluster: Cluster) -> &mut Self {
+        self.cluster = cluster;
+
+        self
+    }
+
+    /// Set the URL the wallet redirects to after the user approves the connection.
+    /// Only `https` URLs are accepted
+    pub fn set_redirect_link(&mut self, redirect_link: &str) -> WalletResult<&mut Self> {
+        self.redirect_link.replace(Self::https_url(redirect_link)?);
+
+        Ok(self)
+    }
+
+    /// Get the base URI
+    pub fn base_uri(&self) -> &str {
+        self.base_uri.as_str()
+    }
+
+    /// Get the URL of the dapp
+    pub fn app_url(&self) -> Option<&str> {
+        self.app_url.as_deref()
+    }
+
+    /// Get the x25519 public key of the dapp
+    pub fn dapp_encryption_public_key(&self) -> Option<[u8; 32]> {
+        self.dapp_encryption_public_key
+    }
+
+    /// Get the [Cluster]
+    pub fn cluster(&self) -> Cluster {
+        self.cluster
+    }
+
+    /// Get the redirect link
+    pub fn redirect_link(&self) -> Option<&str> {
+        self.redirect_link.as_deref()
+    }
+
+    /// Build the URI with all parameters percent-encoded and the
+    /// `dapp_encryption_public_key` Base58 encoded.
+    /// The app URL, the public key and the redirect link are required
+    pub fn uri(&self) -> WalletResult<String> {
+        let app_url = self
+            .app_url
+            .as_ref()
+            .ok_or(WalletError::MissingDeepLinkField("app_url".to_string()))?;
+        let public_key =
+            self.dapp_encryption_public_key
+                .ok_or(WalletError::MissingDeepLinkField(
+                    "dapp_encryption_public_key".to_string(),
+                ))?;
+        let redirect_link =
+            self.redirect_link
+                .as_ref()
+                .ok_or(WalletError::MissingDeepLinkField(
+                    "redirect_link".to_string(),
+                ))?;
+
+        let cluster = match self.cluster {
+            Cluster::MainNet => "mainnet-beta",
+            Cluster::DevNet => "devnet",
+            Cluster::TestNet => "testnet",
+            Cluster::LocalNet => {
+                return Err(WalletError::UnsupportedChain(
+                    self.cluster.chain().to_string(),
+                ))
+            }
+        };
+
+        let public_key = bs58::encode(public_key).into_string();
+
+        let query = [
+            ("app_url", app_url.as_str()),
+            ("dapp_encryption_public_key", public_key.as_str()),
+            ("cluster", cluster),
+            ("redirect_link", redirect_link.as_str()),
+        ]
+        .into_iter()
+        .map(|(key, value)| key.to_string() + "=" + &Self::encode(value))
+        .collect::<Vec<String>>()
+        .join("&");
+
+        Ok(self.base_uri.clone() + "?" + &query)
+    }
+
+    /// Check that the `url` uses the `https` scheme and has a host
+    fn https_url(url: &str) -> WalletResult<String> {
+        let host = url
+            .strip_prefix("https://")
+            .map(|rest| rest.split(['/', '?', '#']).next().unwrap_or_default())
+            .unwrap_or_default();
+
+        if host.is_empty() {
+            return Err(WalletError::InvalidDeepLinkUrl(url.to_string()));
+        }
+
+        Ok(url.to_string())
+    }
+
+    /// Percent-encode all characters apart from the unreserved characters of RFC 3986
+    fn encode(value: &str) -> String {
+        value
+            .bytes()
+            .map(|byte| match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                    (byte as char).to_string()
+                }
+                _ => format!("%{byte:02X}"),
+            })
+            .collect()
+    }
+}
+
+impl Default for MobileDeepLink {
+    fn default() -> Self {
+        Self {
+            base_uri: PHANTOM_CONNECT_URI.to_string(),
+            app_url: Option::default(),
+            dapp_encryption_public_key: Option::default(),
+            cluster: Cluster::default(),
+            redirect_link: Option::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod mobile_deep_link_checks {
+    use super::*;
+
+    #[test]
+    fn encoded_uri() {
+        let mut deep_link = MobileDeepLink::new();
+        deep_link
+            .set_app_url("https://foo.example/?ref=a b")
+            .unwrap()
+            .set_dapp_encryption_public_key([1u8; 32])
+            .set_cluster(Cluster::MainNet)
+            .set_redirect_link("https://foo.example/connected?session=1")
+            .unwrap();
+
+        assert_eq!(
+            deep_link.uri().unwrap(),
+            "https://phantom.app/ul/v1/connect\
+            ?app_url=https%3A%2F%2Ffoo.example%2F%3Fref%3Da%20b\
+            &dapp_encryption_public_key=4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi\
+            &cluster=mainnet-beta\
+            &redirect_link=https%3A%2F%2Ffoo.example%2Fconnected%3Fsession%3D1"
+        );
+
+        deep_link
+            .set_base_uri(SOLFLARE_CONNECT_URI)
+            .set_cluster(Cluster::DevNet);
+        let uri = deep_link.uri().unwrap();
+        assert!(uri.starts_with("https://solflare.com/ul/v1/connect?app_url="));
+        assert!(uri.contains("&cluster=devnet&"));
+
+        deep_link.set_cluster(Cluster::LocalNet);
+        assert_eq!(
+            Some(WalletError::UnsupportedChain("solana:localnet".to_string())),
+            deep_link.uri().err()
+        );
+    }
+
+    #[test]
+    fn required_fields_and_invalid_urls() {
+        let mut deep_link = MobileDeepLink::new();
+
+        assert_eq!(
+            Some(WalletError::MissingDeepLinkField("app_url".to_string())),
+            deep_link.uri().err()
+        );
+        deep_link.set_app_url("https://foo.example").unwrap();
+        assert_eq!(
+            Some(WalletError::MissingDeepLinkField(
+                "dapp_encryption_public_key".to_string()
+            )),
+            deep_link.uri().err()
+        );
+        deep_link.set_dapp_encryption_public_key([1u8; 32]);
+        assert_eq!(
+            Some(WalletError::MissingDeepLinkField(
+                "redirect_link".to_string()
+            )),
+            deep_link.uri().err()
+        );
+
+        for url in [
+            "http://foo.example",
+            "javascript:alert(1)",
+            "https://",
+            "https:///path",
+        ] {
+            assert_eq!(
+                Some(WalletError::InvalidDeepLinkUrl(url.to_string())),
+                deep_link.set_redirect_link(url).err()
+            );
+            assert_eq!(
+                Some(WalletError::InvalidDeepLinkUrl(url.to_string())),
+                deep_link.set_app_url(url).err()
+            );
+        }
+
+        assert!(deep_link.set_redirect_link("https://foo.example").is_ok());
+        assert!(deep_link.uri().is_ok());
+    }
+}
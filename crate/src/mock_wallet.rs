@@ -0,0 +1,389 @@
+use std::time::SystemTime;
+
+use ed25519_dalek::{Signer, SigningKey};
+use rand_chacha::ChaCha12Rng;
+use rand_core::{RngCore, SeedableRng};
+use wallet_adapter_common::{
+    clusters::Cluster, signin_standard::SigninInput as SiwsInput, WalletCommonUtils,
+};
+use web_sys::{
+    js_sys::{Array, Function, Promise, Uint8Array},
+    wasm_bindgen::{prelude::Closure, JsValue},
+};
+
+use crate::{
+    transaction::{self, TransactionParts},
+    InnerUtils, Reflection, Wallet, WalletError, WalletResult, SOLANA_SIGN_IN_IDENTIFIER,
+    SOLANA_SIGN_MESSAGE_IDENTIFIER, SOLANA_SIGN_TRANSACTION_IDENTIFIER,
+    STANDARD_CONNECT_IDENTIFIER, STANDARD_DISCONNECT_IDENTIFIER,
+};
+
+/// The default name of a [MockWallet]
+pub const MOCK_WALLET_NAME: &str = "Mock Wallet";
+
+/// The version of the mock wallet and its features
+const MOCK_WALLET_VERSION: &str = "1.0.0";
+
+/// Builds a [Wallet] for local development without a browser extension.
+/// The wallet approves every request by signing with an in-memory keypair,
+/// it supports connecting, disconnecting, signing messages and transactions
+/// and Sign In With Solana. Requires the `dev` feature.
+///
+/// #### Example
+/// ```rust,no_run
+/// use wallet_adapter::{MockWallet, WalletAdapter};
+///
+/// # async fn connect() -> wallet_adapter::WalletResult<()> {
+/// let mut adapter = WalletAdapter::init()?;
+/// adapter.register_mock_wallet(MockWallet::new())?;
+/// adapter.connect_by_name("Mock Wallet").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockWallet {
+    name: String,
+    signing_key: SigningKey,
+    clusters: Vec<Cluster>,
+}
+
+impl MockWallet {
+    /// Instantiate a [MockWallet] named [MOCK_WALLET_NAME] supporting all clusters
+    /// with a random keypair
+    pub fn new() -> Self {
+        let mut seed = [0u8; 32];
+        ChaCha12Rng::from_os_rng().fill_bytes(&mut seed);
+
+        Self::from_seed(seed)
+    }
+
+    /// Instantiate a [MockWallet] with the keypair derived from the `seed`
+    /// so that the address is the same across page reloads
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        Self {
+            name: MOCK_WALLET_NAME.to_string(),
+            signing_key: SigningKey::from_bytes(&seed),
+            clusters: vec![
+                Cluster::MainNet,
+                Cluster::DevNet,
+                Cluster::TestNet,
+                Cluster::LocalNet,
+            ],
+        }
+    }
+
+    /// Set the name of the wallet
+    pub fn set_name(&mut self, name: &str) -> &mut Self {
+        self.name = name.to_string();
+
+        self
+    }
+
+    /// Set the clusters supported by the wallet and its account
+    pub fn set_clusters(&mut self, clusters: &[Cluster]) -> &mut Self {
+        self.clusters = clusters.to_vec();
+
+        self
+    }
+
+    /// Get the name of the wallet
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Get the clusters supported by the wallet
+    pub fn clusters(&self) -> &[Cluster] {
+        self.clusters.as_slice()
+    }
+
+    /// Get the public key of the account of the wallet
+    pub fn public_key(&self) -> [u8; 32] {
+        self.signing_key.verifying_key().to_bytes()
+    }
+
+    /// Get the Base58 address of the account of the wallet
+    pub fn address(&self) -> String {
+        WalletCommonUtils::address(self.signing_key.verifying_key())
+    }
+
+    /// Build the [Wallet] as it would be registered by a browser extension
+    pub fn build(&self) -> WalletResult<Wallet> {
+        let chains = self
+            .clusters
+            .iter()
+            .map(|cluster| JsValue::from(cluster.chain()))
+            .collect::<Array>();
+
+        let mut account = Reflection::new_object();
+        account.set_object_str("address", &self.address())?;
+        account.set_object(
+            &"publicKey".into(),
+            &Uint8Array::from(self.public_key().as_slice()),
+        )?;
+        account.set_object(&"chains".into(), &chains)?;
+        account.set_object(
+            &"features".into(),
+            &[
+                SOLANA_SIGN_MESSAGE_IDENTIFIER,
+                SOLANA_SIGN_TRANSACTION_IDENTIFIER,
+                SOLANA_SIGN_IN_IDENTIFIER,
+            ]
+            .into_iter()
+            .map(JsValue::from)
+            .collect::<Array>(),
+        )?;
+        let account = account.take();
+
+        let mut connect_output = Reflection::new_object();
+        connect_output.set_object(&"accounts".into(), &Array::of1(&account))?;
+        let connect_output = connect_output.take();
+
+        let signing_key = self.signing_key.clone();
+        let sign_message = move |input: Reflection| {
+            let message = input.reflect_bytes("message")?;
+            let signature = signing_key.sign(&message);
+
+            let mut output = Reflection::new_object();
+            output.set_object(
+                &"signedMessage".into(),
+                &Uint8Array::from(message.as_slice()),
+            )?;
+            output.set_object(
+                &"signature".into(),
+                &Uint8Array::from(signature.to_bytes().as_slice()),
+            )?;
+
+            Ok(output.take())
+        };
+
+        let signing_key = self.signing_key.clone();
+        let sign_transaction = move |input: Reflection| {
+            let transaction = input.reflect_bytes("transaction")?;
+            let signature = signing_key.sign(TransactionParts::parse(&transaction)?.message);
+            let signed = transaction::merge_signatures(
+                &transaction,
+                &[(signing_key.verifying_key().to_bytes(), signature.to_bytes())],
+            )?;
+
+            let mut output = Reflection::new_object();
+            output.set_object(
+                &"signedTransaction".into(),
+                &Uint8Array::from(signed.as_slice()),
+            )?;
+
+            Ok(output.take())
+        };
+
+        let signing_key = self.signing_key.clone();
+        let address = self.address();
+        let signin_account = account.clone();
+        let sign_in = move |input: Reflection| {
+            let message = Self::siws_message(&input, &address)?;
+            let signature = signing_key.sign(message.as_bytes());
+
+            let mut output = Reflection::new_object();
+            output.set_object(&"account".into(), &signin_account)?;
+            output.set_object(
+                &"signedMessage".into(),
+                &Uint8Array::from(message.as_bytes()),
+            )?;
+            output.set_object(
+                &"signature".into(),
+                &Uint8Array::from(signature.to_bytes().as_slice()),
+            )?;
+
+            Ok(output.take())
+        };
+
+        let mut features = Reflection::new_object();
+        features.set_object(
+            &STANDARD_CONNECT_IDENTIFIER.into(),
+            &Self::feature(
+                "connect",
+                Self::handler(move |_| Ok(connect_output.clone()))?,
+            )?,
+        )?;
+        features.set_object(
+            &STANDARD_DISCONNECT_IDENTIFIER.into(),
+            &Self::feature("disconnect", Self::handler(|_| Ok(JsValue::undefined()))?)?,
+        )?;
+        features.set_object(
+            &SOLANA_SIGN_MESSAGE_IDENTIFIER.into(),
+            &Self::feature("signMessage", Self::map_handler(sign_message)?)?,
+        )?;
+
+        let mut sign_transaction_feature = Reflection::new(Self::feature(
+            "signTransaction",
+            Self::map_handler(sign_transaction)?,
+        )?)?;
+        sign_transaction_feature.set_object(
+            &"supportedTransactionVersions".into(),
+            &Array::of2(&"legacy".into(), &0u8.into()),
+        )?;
+        features.set_object(
+            &SOLANA_SIGN_TRANSACTION_IDENTIFIER.into(),
+            sign_transaction_feature.get_inner(),
+        )?;
+
+        features.set_object(
+            &SOLANA_SIGN_IN_IDENTIFIER.into(),
+            &Self::feature("signIn", Self::map_handler(sign_in)?)?,
+        )?;
+
+        let mut wallet = Reflection::new_object();
+        wallet.set_object_str("name", &self.name)?;
+        wallet.set_object_str("version", MOCK_WALLET_VERSION)?;
+        wallet.set_object(&"chains".into(), &chains)?;
+        wallet.set_object(&"accounts".into(), &Array::of1(&account))?;
+        wallet.set_object(&"features".into(), &features.take())?;
+
+        Wallet::from_jsvalue(wallet.take())
+    }
+
+    /// A feature object with the `version` and the `handler` function under the `name` key
+    fn feature(name: &str, handler: Function) -> WalletResult<JsValue> {
+        let mut feature = Reflection::new_object();
+        feature.set_object_str("version", MOCK_WALLET_VERSION)?;
+        feature.set_object(&name.into(), &handler)?;
+
+        Ok(feature.take())
+    }
+
+    /// Wrap the `callback` in a variadic JS function returning a promise, the callback
+    /// receives all the arguments as an [Array] and an error rejects the promise
+    fn handler(
+        callback: impl Fn(Array) -> WalletResult<JsValue> + 'static,
+    ) -> WalletResult<Function> {
+        let callback = Closure::wrap(Box::new(move |inputs: Array| {
+            InnerUtils::into_promise(
+                callback(inputs).map_err(|error| JsValue::from(error.to_string())),
+            )
+        }) as Box<dyn Fn(Array) -> Promise>);
+
+        let variadic = Function::new_with_args("handler", "return (...inputs) => handler(inputs);");
+
+        Reflection::new(variadic.call1(&JsValue::null(), &callback.into_js_value())?)?
+            .into_function()
+    }
+
+    /// Same as [Self::handler] but the `callback` is called for every input
+    /// and the outputs are returned as an array in the same order
+    fn map_handler(
+        callback: impl Fn(Reflection) -> WalletResult<JsValue> + 'static,
+    ) -> WalletResult<Function> {
+        Self::handler(move |inputs| {
+            inputs
+                .iter()
+                .map(|input| callback(Reflection::new(input)?))
+                .collect::<WalletResult<Array>>()
+                .map(JsValue::from)
+        })
+    }
+
+    /// Build the Sign In With Solana message of the `input` for the account with the `address`
+    fn siws_message(input: &Reflection, address: &str) -> WalletResult<String> {
+        let parse_time = |key: &str| -> WalletResult<Option<SystemTime>> {
+            input
+                .string_optional(key)?
+                .map(|time| {
+                    humantime::parse_rfc3339(&time)
+                        .or(Err(WalletError::InvalidISO8601Timestamp(time)))
+                })
+                .transpose()
+        };
+
+        let mut siws = SiwsInput::new();
+        siws.set_address(address)?;
+
+        if let Some(domain) = input.string_optional("domain")? {
+            siws.set_domain(&domain);
+        }
+        if let Some(statement) = input.string_optional("statement")? {
+            siws.set_statement(&statement);
+        }
+        if let Some(uri) = input.string_optional("uri")? {
+            siws.set_uri(&uri);
+        }
+        if let Some(version) = input.string_optional("version")? {
+            siws.set_version(&version);
+        }
+        if let Some(chain_id) = input.string_optional("chainId")? {
+            siws.set_chain_id(chain_id.as_str().into());
+        }
+        if let Some(nonce) = input.string_optional("nonce")? {
+            siws.set_custom_nonce(&nonce)?;
+        }
+        if let Some(issued_at) = parse_time("issuedAt")? {
+            siws.set_issued_at(issued_at);
+        }
+        // The mock approves every request so only the order of the timestamps is checked
+        if let Some(expiration_time) = parse_time("expirationTime")? {
+            siws.set_expiration_time(SystemTime::UNIX_EPOCH, expiration_time)?;
+        }
+        if let Some(not_before) = parse_time("notBefore")? {
+            siws.set_not_before_time(SystemTime::UNIX_EPOCH, not_before)?;
+        }
+        if let Some(request_id) = input.string_optional("requestId")? {
+            siws.set_request_id(&request_id);
+        }
+        input
+            .vec_string_accept_undefined("resources")?
+            .iter()
+            .for_each(|resource| {
+                siws.add_resource(resource);
+            });
+
+        Ok(siws.to_message())
+    }
+}
+
+impl Default for MockWallet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod mock_wallet_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+    use crate::{SigninInput, WalletAdapter};
+
+    #[wasm_bindgen_test]
+    async fn connect_and_sign() {
+        let mock = MockWallet::from_seed([3u8; 32]);
+
+        let mut adapter = WalletAdapter::init().unwrap();
+        adapter.register_mock_wallet(mock.clone()).unwrap();
+        adapter.connect_by_name(MOCK_WALLET_NAME).await.unwrap();
+
+        let connection_info = adapter.connection_info().await;
+        let account = connection_info.connected_account().unwrap();
+        assert_eq!(account.public_key(), mock.public_key());
+        assert_eq!(account.address(), mock.address().as_str());
+        drop(connection_info);
+
+        let message = b"Sign with the mock wallet";
+        let output = adapter.sign_message(message).await.unwrap();
+        assert!(WalletCommonUtils::verify(
+            &mock.public_key(),
+            message,
+            &output.signature().to_bytes()
+        )
+        .is_ok());
+
+        let mut signin_input = SigninInput::new();
+        signin_input
+            .set_custom_domain("localhost")
+            .set_statement("Sign in to the dapp")
+            .custom_nonce("0123456789abcdef")
+            .unwrap();
+        let output = adapter
+            .sign_in(&signin_input, mock.public_key())
+            .await
+            .unwrap();
+        assert_eq!(output.public_key, mock.public_key());
+    }
+}
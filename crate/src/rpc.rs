@@ -0,0 +1,313 @@
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    js_sys,
+    wasm_bindgen::{JsCast, JsValue},
+    Headers, RequestInit, Response,
+};
+
+use crate::{Commitment, Reflection, WalletError, WalletResult};
+
+/// A minimal JSON-RPC client issuing requests to a Solana cluster using the `fetch` API
+/// of the global scope, so it can be used in a browser window or a web worker,
+/// see [crate::WalletAdapter::rpc_client].
+/// The values of the headers are redacted from the [Debug](core::fmt::Debug) output
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RpcClient {
+    endpoint: String,
+    headers: Vec<(String, String)>,
+}
+
+impl RpcClient {
+    /// Instantiate a client sending requests to the `endpoint` URL
+    pub fn new(endpoint: &str) -> Self {
+        Self {
+            endpoint: endpoint.to_string(),
+            headers: Vec::default(),
+        }
+    }
+
+    /// Add a header sent with every request, for example an `Authorization`
+    /// or `x-api-key` header required by a private RPC provider
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+
+        self
+    }
+
+    /// Get the headers added with [Self::with_header] as `(name, value)` pairs
+    pub fn headers(&self) -> &[(String, String)] {
+        self.headers.as_slice()
+    }
+
+    /// Get the endpoint URL
+    pub fn endpoint(&self) -> &str {
+        self.endpoint.as_str()
+    }
+
+    /// Send a JSON-RPC request for `method` with the `params`, usually a JS array,
+    /// and return the `result` field of the response, which may be `null`.
+    /// A response with an HTTP status outside of `200-299` and an `error` in the response
+    /// are returned as [WalletError::RpcError] and a response without a `result`
+    /// as [WalletError::ValueNotFound]
+    pub async fn call(&self, method: &str, params: JsValue) -> WalletResult<JsValue> {
+        // The `fetch` of the global scope, which is also available in web workers
+        let global = js_sys::global();
+        let fetch = Reflection::new(global.clone().into())?.get_function("fetch")?;
+
+        let body = Self::payload(1, method, &params)?;
+
+        let headers = Headers::new()?;
+        headers.set("Content-Type", "application/json")?;
+        self.headers
+            .iter()
+            .try_for_each(|(name, value)| headers.set(name, value))?;
+
+        let request_init = RequestInit::new();
+        request_init.set_method("POST");
+        request_init.set_headers(&headers);
+        request_init.set_body(&body.into());
+
+        let request = fetch
+            .call2(&global, &self.endpoint.as_str().into(), &request_init)?
+            .dyn_into::<js_sys::Promise>()?;
+        let response = JsFuture::from(request).await?.dyn_into::<Response>()?;
+
+        // Rate limited or failing providers respond with a body that is not a JSON-RPC response
+        if !response.ok() {
+            return Err(WalletError::RpcError(format!(
+                "HTTP status `{} {}`",
+                response.status(),
+                response.status_text()
+            )));
+        }
+
+        let response = Reflection::new(JsFuture::from(response.json()?).await?)?;
+
+        if let Ok(error) = response.reflect_inner("error") {
+            let message = Reflection::new(error)?
+                .string_optional("message")?
+                .unwrap_or_default();
+
+            return Err(WalletError::RpcError(message));
+        }
+
+        // The `result` of some methods is `null` when the cluster has no data
+        let result = js_sys::Reflect::get(response.get_inner(), &"result".into())?;
+
+        if result.is_undefined() {
+            return Err(WalletError::ValueNotFound);
+        }
+
+        Ok(result)
+    }
+
+    /// Serialize a JSON-RPC request for `method` with the `params` and the request `id`
+    pub(crate) fn payload(id: u8, method: &str, params: &JsValue) -> WalletResult<String> {
+        let mut payload = Reflection::new_object();
+        payload.set_object_str("jsonrpc", "2.0")?;
+        payload.set_object(&"id".into(), &JsValue::from(id))?;
+        payload.set_object_str("method", method)?;
+        payload.set_object(&"params".into(), params)?;
+
+        Reflection::get_string(&js_sys::JSON::stringify(payload.get_inner())?.into())
+    }
+
+    /// The `{ commitment }` configuration object accepted by most JSON-RPC methods
+    pub(crate) fn commitment_config(commitment: Commitment) -> WalletResult<JsValue> {
+        let mut config = Reflection::new_object();
+        config.set_object_str("commitment", commitment.as_str())?;
+
+        Ok(config.take())
+    }
+}
+
+impl core::fmt::Debug for RpcClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RpcClient")
+            .field("endpoint", &self.endpoint)
+            .field("headers", &RedactedHeaders(&self.headers))
+            .finish()
+    }
+}
+
+/// Formats the names of `(name, value)` headers, which may carry API keys,
+/// without their values
+pub(crate) struct RedactedHeaders<'a>(pub(crate) &'a [(String, String)]);
+
+impl core::fmt::Debug for RedactedHeaders<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(self.0.iter().map(|(name, _)| (name, "<redacted>")))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod debug_checks {
+    use super::*;
+
+    #[test]
+    fn redacts_header_values() {
+        let client = RpcClient::new("https://api.devnet.solana.com")
+            .with_header("Authorization", "Bearer secret-token");
+        let formatted = format!("{client:?}");
+
+        assert!(formatted.contains("Authorization"));
+        assert!(formatted.contains("<redacted>"));
+        assert!(!formatted.contains("secret-token"));
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+pub(crate) mod rpc_checks {
+    use web_sys::js_sys::{Array, Function, Reflect, JSON};
+
+    use super::*;
+
+    /// Replaces the global `fetch` with a mock that records the parsed body of every request
+    /// and responds with the `response` JSON. The original `fetch` is restored on drop.
+    pub(crate) struct MockFetch(JsValue);
+
+    impl MockFetch {
+        pub(crate) fn new(response: &str) -> Self {
+            Self::with_status(response, 200, "OK")
+        }
+
+        /// Respond with the `response` and the HTTP `status` and `status_text`
+        pub(crate) fn with_status(response: &str, status: u16, status_text: &str) -> Self {
+            let install = Function::new_with_args(
+                "response, status, statusText",
+                r#"
+                const original = globalThis.fetch;
+                globalThis.mockRpcRequests = [];
+                globalThis.mockRpcHeaders = [];
+                globalThis.fetch = async (_url, init) => {
+                    globalThis.mockRpcRequests.push(JSON.parse(init.body));
+                    globalThis.mockRpcHeaders.push(new Headers(init.headers));
+
+                    return new Response(response, { status, statusText });
+                };
+
+                return original;
+                "#,
+            );
+
+            Self(
+                install
+                    .call3(
+                        &JsValue::null(),
+                        &response.into(),
+                        &status.into(),
+                        &status_text.into(),
+                    )
+                    .unwrap(),
+            )
+        }
+
+        /// Get the header `name` of the request at `index`
+        pub(crate) fn header(&self, index: u32, name: &str) -> Option<String> {
+            let headers = Reflect::get(&js_sys::global(), &"mockRpcHeaders".into())
+                .unwrap()
+                .dyn_into::<Array>()
+                .unwrap()
+                .get(index)
+                .dyn_into::<Headers>()
+                .unwrap();
+
+            headers.get(name).unwrap()
+        }
+
+        /// Respond with a JSON-RPC response containing the `result`
+        pub(crate) fn with_result(result: &str) -> Self {
+            Self::new(&format!(r#"{{"jsonrpc":"2.0","id":1,"result":{result}}}"#))
+        }
+
+        /// The parsed bodies of all the requests sent so far
+        pub(crate) fn requests(&self) -> Vec<Reflection> {
+            Reflect::get(&js_sys::global(), &"mockRpcRequests".into())
+                .unwrap()
+                .dyn_into::<Array>()
+                .unwrap()
+                .iter()
+                .map(|request| Reflection::new(request).unwrap())
+                .collect()
+        }
+    }
+
+    impl Drop for MockFetch {
+        fn drop(&mut self) {
+            Reflect::set(&js_sys::global(), &"fetch".into(), &self.0).unwrap();
+        }
+    }
+
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    async fn envelope_and_error() {
+        let client = RpcClient::new("https://rpc.example");
+
+        let mock = MockFetch::with_result("42");
+        let params = Array::of1(&"foo".into());
+        let result = client.call("getFoo", params.clone().into()).await.unwrap();
+        assert_eq!(result, JsValue::from(42u8));
+
+        let request = &mock.requests()[0];
+        assert_eq!(request.string("jsonrpc").unwrap(), "2.0");
+        assert_eq!(request.string("method").unwrap(), "getFoo");
+        assert_eq!(
+            JSON::stringify(&request.reflect_inner("params").unwrap()).unwrap(),
+            r#"["foo"]"#
+        );
+        drop(mock);
+
+        let mock = MockFetch::new(
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32602,"message":"Invalid params"}}"#,
+        );
+        assert_eq!(
+            Err(WalletError::RpcError("Invalid params".to_string())),
+            client.call("getFoo", params.clone().into()).await
+        );
+        drop(mock);
+
+        let mock = MockFetch::with_result("null");
+        assert_eq!(
+            Ok(JsValue::NULL),
+            client.call("getFoo", params.clone().into()).await
+        );
+        drop(mock);
+
+        let mock = MockFetch::new(r#"{"jsonrpc":"2.0","id":1}"#);
+        assert_eq!(
+            Err(WalletError::ValueNotFound),
+            client.call("getFoo", params.clone().into()).await
+        );
+        drop(mock);
+
+        let _mock = MockFetch::with_status("Too Many Requests", 429, "Too Many Requests");
+        assert_eq!(
+            Err(WalletError::RpcError(
+                "HTTP status `429 Too Many Requests`".to_string()
+            )),
+            client.call("getFoo", params.into()).await
+        );
+    }
+
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    async fn custom_headers() {
+        let client = RpcClient::new("https://rpc.example")
+            .with_header("Authorization", "Bearer foo")
+            .with_header("x-api-key", "bar");
+
+        let mock = MockFetch::with_result("42");
+        client.call("getFoo", Array::new().into()).await.unwrap();
+
+        assert_eq!(
+            mock.header(0, "authorization").as_deref(),
+            Some("Bearer foo")
+        );
+        assert_eq!(mock.header(0, "x-api-key").as_deref(), Some("bar"));
+        assert_eq!(
+            mock.header(0, "content-type").as_deref(),
+            Some("application/json")
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use web_sys::{js_sys::JSON, wasm_bindgen::JsValue};
+
+use crate::{Reflection, WalletResult};
+
+/// The outcome of simulating a transaction using the `simulateTransaction` JSON-RPC method.
+/// A transaction that would fail is still a successful simulation, the failure is
+/// reported in [Self::err] so that the dapp can decide whether to ask the user to sign it
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct SimulationResult {
+    err: Option<String>,
+    logs: Vec<String>,
+    units_consumed: Option<u64>,
+}
+
+impl SimulationResult {
+    /// Parse the `value` of a `simulateTransaction` response
+    pub(crate) fn parse(value: JsValue) -> WalletResult<Self> {
+        let value = Reflection::new(value)?;
+
+        let err = match value.reflect_inner("err") {
+            Ok(err) if !err.is_null() => err.as_string().or(JSON::stringify(&err)?.as_string()),
+            _ => None,
+        };
+
+        let logs = match value.reflect_inner("logs") {
+            Ok(logs) if !logs.is_null() => value.vec_string_accept_undefined("logs")?,
+            _ => Vec::default(),
+        };
+
+        Ok(Self {
+            err,
+            logs,
+            units_consumed: value.opt_u64("unitsConsumed")?,
+        })
+    }
+
+    /// The error the transaction would fail with, serialized as JSON for structured
+    /// errors like `{"InstructionError":[0,{"Custom":1}]}`
+    pub fn err(&self) -> Option<&str> {
+        self.err.as_deref()
+    }
+
+    /// Check whether the transaction would succeed
+    pub fn is_ok(&self) -> bool {
+        self.err.is_none()
+    }
+
+    /// The log messages emitted by the transaction instructions
+    pub fn logs(&self) -> &[String] {
+        self.logs.as_slice()
+    }
+
+    /// The number of compute units consumed by the transaction if reported by the cluster
+    pub fn units_consumed(&self) -> Option<u64> {
+        self.units_consumed
+    }
+}
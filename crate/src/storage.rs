@@ -10,6 +10,72 @@ pub type StorageSchema = HashMap<[u8; 32], Wallet>;
 /// Convenience type for `Rc<RefCell<StorageSchema>>;`
 pub type StorageType = Rc<RefCell<StorageSchema>>;
 
+/// The name of a wallet normalized by trimming surrounding whitespace and lowercasing,
+/// so that names reported with different casing by a wallet compare as equal.
+/// Wallets are keyed in [WalletStorage] by the [Self::storage_key] of their name
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WalletName(String);
+
+impl WalletName {
+    /// Normalize the `name`
+    pub fn new(name: &str) -> Self {
+        Self(name.trim().to_lowercase())
+    }
+
+    /// Get the normalized name
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// The [Sha3_256] hash of the normalized name used to key the wallet in [WalletStorage]
+    pub fn storage_key(&self) -> [u8; 32] {
+        Sha3_256::digest(self.0.as_bytes()).into()
+    }
+
+    /// The key of the wallet registered at position `index` among the different wallets
+    /// that registered under this name. The first wallet uses [Self::storage_key] and
+    /// the others the hash of the name, a `0xff` separator and the `index` as little-endian
+    /// `u64` bytes. `0xff` never occurs in UTF-8 so the key cannot collide with another name
+    pub fn storage_key_at(&self, index: usize) -> [u8; 32] {
+        if index == 0 {
+            self.storage_key()
+        } else {
+            Sha3_256::new()
+                .chain_update(self.0.as_bytes())
+                .chain_update([0xff])
+                .chain_update((index as u64).to_le_bytes())
+                .finalize()
+                .into()
+        }
+    }
+}
+
+impl From<&str> for WalletName {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl core::fmt::Display for WalletName {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Whether a wallet can be used by the dapp, mirroring the `readyState`
+/// of the wallet adapters of the JavaScript ecosystem
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WalletReadyState {
+    /// The wallet registered itself with the browser and can be connected
+    Installed,
+    /// The wallet has not registered itself with the browser
+    #[default]
+    NotDetected,
+    /// The wallet is not installed but is one of the [known wallets](crate::known_wallets)
+    /// that the user can be prompted to install
+    Loadable,
+}
+
 /// Storage used by the [crate::WalletAdapter]
 #[derive(Default, PartialEq, Eq, Clone)]
 pub struct WalletStorage(StorageType);
@@ -25,11 +91,61 @@ impl WalletStorage {
         self.0.borrow().values().cloned().collect::<Vec<Wallet>>()
     }
 
+    /// Get the names of all the wallets in storage sorted alphabetically
+    pub fn wallet_names(&self) -> Vec<String> {
+        let mut names = self
+            .0
+            .borrow()
+            .values()
+            .map(|wallet| wallet.name().to_string())
+            .collect::<Vec<String>>();
+        names.sort();
+
+        names
+    }
+
     /// Get a certain wallet by name from storage
     pub fn get_wallet(&self, wallet_name: &str) -> Option<Wallet> {
         let storage_ref = self.0.borrow();
-        let hash: [u8; 32] = Sha3_256::digest(wallet_name.to_lowercase().as_bytes()).into();
-        storage_ref.get(&hash).cloned()
+        storage_ref
+            .get(&WalletName::new(wallet_name).storage_key())
+            .cloned()
+    }
+
+    /// Get all the different wallets that registered under the same name in the order
+    /// they registered. [Self::get_wallet] only returns the first one
+    pub fn wallets_by_name(&self, wallet_name: &str) -> Vec<Wallet> {
+        let name = WalletName::new(wallet_name);
+        let storage_ref = self.0.borrow();
+
+        (0..)
+            .map_while(|index| storage_ref.get(&name.storage_key_at(index)).cloned())
+            .collect()
+    }
+
+    /// Insert a wallet that registered itself. Wallets with the same name are told apart
+    /// by the identity of the JS object they registered, so the same wallet registering
+    /// again replaces itself even if its data changed, while a different wallet is stored
+    /// under the next [WalletName::storage_key_at]. Returns `true` if a different wallet
+    /// had already registered under the same name
+    pub(crate) fn register(storage: &StorageType, mut wallet: Wallet) -> bool {
+        let name = WalletName::new(wallet.name());
+        let mut storage_ref = storage.borrow_mut();
+
+        let mut index = 0usize;
+        while storage_ref
+            .get(&name.storage_key_at(index))
+            .is_some_and(|stored| !stored.js_object.is_same(&wallet.js_object))
+        {
+            index += 1;
+        }
+
+        let storage_key = name.storage_key_at(index);
+        wallet.storage_key.replace(storage_key);
+
+        let is_new = storage_ref.insert(storage_key, wallet).is_none();
+
+        index > 0 && is_new
     }
 }
 
@@ -38,3 +154,33 @@ impl core::fmt::Debug for WalletStorage {
         write!(f, "{:?}", &*self.0.borrow())
     }
 }
+
+#[cfg(test)]
+mod wallet_name_checks {
+    use super::*;
+
+    #[test]
+    fn normalized() {
+        assert_eq!(WalletName::new("Phantom"), WalletName::new("phantom"));
+        assert_eq!(WalletName::new("  Foo Wallet "), "foo wallet".into());
+        assert_eq!(WalletName::new("Foo Wallet").as_str(), "foo wallet");
+        assert_eq!(
+            WalletName::new("PHANTOM").storage_key(),
+            <[u8; 32]>::from(Sha3_256::digest(b"phantom"))
+        );
+        assert_ne!(WalletName::new("Phantom"), WalletName::new("Phantom 2"));
+    }
+
+    #[test]
+    fn storage_keys_do_not_collide() {
+        let solana = WalletName::new("Solana");
+
+        assert_eq!(solana.storage_key_at(0), solana.storage_key());
+        assert_ne!(solana.storage_key_at(1), solana.storage_key());
+        assert_ne!(solana.storage_key_at(1), solana.storage_key_at(2));
+        assert_ne!(
+            solana.storage_key_at(1),
+            WalletName::new("solana#1").storage_key()
+        );
+    }
+}
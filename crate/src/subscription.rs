@@ -0,0 +1,329 @@
+use std::{cell::Cell, rc::Rc};
+
+use async_channel::{Receiver, Sender};
+use web_sys::{
+    js_sys::{Array, JSON},
+    wasm_bindgen::{prelude::Closure, JsValue},
+    WebSocket,
+};
+
+use crate::{
+    encoding, Commitment, Lamports, LogLevel, Logger, Reflection, RpcClient, WalletError,
+    WalletResult,
+};
+
+/// The JSON-RPC request id of the subscription request
+const SUBSCRIBE_REQUEST_ID: u8 = 1;
+
+/// The JSON-RPC request id of the unsubscription request
+const UNSUBSCRIBE_REQUEST_ID: u8 = 2;
+
+/// The state of an account pushed by an `accountSubscribe` websocket subscription
+/// every time the account changes, see [crate::WalletAdapter::subscribe_account]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AccountUpdate {
+    /// The balance of the account
+    pub lamports: Lamports,
+    /// The size in bytes of the data stored in the account
+    pub data_len: usize,
+    /// The public key of the program owning the account
+    pub owner: [u8; 32],
+}
+
+impl AccountUpdate {
+    /// Parse the `value` of an `accountNotification` requested with the `base64` encoding
+    pub(crate) fn parse(value: JsValue) -> WalletResult<Self> {
+        let value = Reflection::new(value)?;
+
+        // The data is encoded as `[data, "base64"]`
+        let data = Reflection::new(value.reflect_inner("data")?)?.into_array()?;
+        let data = encoding::from_base64(&Reflection::get_string(&data.get(0))?)?;
+
+        let owner = bs58::decode(value.string("owner")?)
+            .into_vec()
+            .ok()
+            .and_then(|owner| owner.try_into().ok())
+            .ok_or(WalletError::InvalidBase58Address)?;
+
+        Ok(Self {
+            lamports: Lamports(value.u64("lamports")?),
+            data_len: data.len(),
+            owner,
+        })
+    }
+}
+
+/// Open a websocket to the `endpoint` subscribing to the changes of the account with the
+/// `public_key` and push every change to the returned [Receiver]. Closing or dropping the
+/// receiver unsubscribes and closes the websocket, while closing the websocket,
+/// for example by the cluster, closes the receiver
+pub(crate) fn open_account_subscription(
+    endpoint: &str,
+    public_key: &[u8; 32],
+    commitment: Commitment,
+    logger: Logger,
+) -> WalletResult<Receiver<AccountUpdate>> {
+    let mut config = Reflection::new(RpcClient::commitment_config(commitment)?)?;
+    config.set_object_str("encoding", "base64")?;
+
+    let request = RpcClient::payload(
+        SUBSCRIBE_REQUEST_ID,
+        "accountSubscribe",
+        &Array::of2(
+            &bs58::encode(public_key).into_string().into(),
+            config.get_inner(),
+        )
+        .into(),
+    )?;
+
+    let socket = WebSocket::new(endpoint)?;
+    let (sender, receiver) = async_channel::unbounded::<AccountUpdate>();
+    let subscription_id = Rc::new(Cell::new(Option::<u64>::None));
+
+    let open_socket = socket.clone();
+    let open_sender = sender.clone();
+    let open_logger = logger.clone();
+    let on_open = Closure::wrap(Box::new(move |_: JsValue| {
+        if let Err(error) = open_socket.send_with_str(&request) {
+            open_logger.log(
+                LogLevel::Error,
+                &format!("Unable to subscribe to the account: {error:?}"),
+            );
+            open_sender.close();
+        }
+    }) as Box<dyn Fn(_)>);
+
+    let message_sender = sender.clone();
+    let message_subscription_id = Rc::clone(&subscription_id);
+    let on_message = Closure::wrap(Box::new(move |event: JsValue| {
+        if let Err(error) =
+            on_subscription_message(event, &message_sender, &message_subscription_id)
+        {
+            logger.log(
+                LogLevel::Warn,
+                &format!("Invalid account subscription message: {error}"),
+            );
+        }
+    }) as Box<dyn Fn(_)>);
+
+    let close_sender = sender.clone();
+    let on_close = Closure::wrap(Box::new(move |_: JsValue| {
+        close_sender.close();
+    }) as Box<dyn Fn(_)>);
+
+    socket.set_onopen(Some(
+        &Reflection::new(on_open.into_js_value())?.into_function()?,
+    ));
+    socket.set_onmessage(Some(
+        &Reflection::new(on_message.into_js_value())?.into_function()?,
+    ));
+    socket.set_onclose(Some(
+        &Reflection::new(on_close.into_js_value())?.into_function()?,
+    ));
+
+    wasm_bindgen_futures::spawn_local(async move {
+        sender.closed().await;
+
+        if let Some(id) = subscription_id.get() {
+            if socket.ready_state() == WebSocket::OPEN {
+                let unsubscribe = RpcClient::payload(
+                    UNSUBSCRIBE_REQUEST_ID,
+                    "accountUnsubscribe",
+                    &Array::of1(&JsValue::from(id as f64)).into(),
+                );
+
+                if let Ok(unsubscribe) = unsubscribe {
+                    let _ = socket.send_with_str(&unsubscribe);
+                }
+            }
+        }
+
+        let _ = socket.close();
+    });
+
+    Ok(receiver)
+}
+
+/// Handle a message of the websocket, either the response to the subscription request
+/// containing the subscription id or an `accountNotification`.
+/// An error response closes the `sender` since no notification will follow
+fn on_subscription_message(
+    event: JsValue,
+    sender: &Sender<AccountUpdate>,
+    subscription_id: &Cell<Option<u64>>,
+) -> WalletResult<()> {
+    let message = Reflection::new(JSON::parse(&Reflection::new(event)?.string("data")?)?)?;
+
+    if let Ok(error) = message.reflect_inner("error") {
+        sender.close();
+
+        let message = Reflection::new(error)?
+            .string_optional("message")?
+            .unwrap_or_default();
+
+        return Err(WalletError::RpcError(message));
+    }
+
+    if let Ok(params) = message.reflect_inner("params") {
+        let value = Reflection::new_from_str(&params, "result")?.reflect_inner("value")?;
+
+        // A closed receiver is unsubscribed by the background task
+        let _ = sender.try_send(AccountUpdate::parse(value)?);
+    } else if message.opt_u64("id")? == Some(SUBSCRIBE_REQUEST_ID as u64) {
+        subscription_id.set(message.opt_u64("result")?);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod subscription_checks {
+    use std::time::Duration;
+
+    use web_sys::js_sys::{self, Function, Reflect};
+
+    use super::*;
+    use crate::InnerUtils;
+
+    /// Replaces the global `WebSocket` with a mock that answers the `accountSubscribe` request
+    /// with one account notification and records every request sent and whether it was closed.
+    /// The original `WebSocket` is restored on drop
+    struct MockWebSocket(JsValue);
+
+    impl MockWebSocket {
+        fn new() -> Self {
+            let install = Function::new_no_args(
+                r#"
+                const original = globalThis.WebSocket;
+                globalThis.mockWsState = { url: null, requests: [], closed: false };
+
+                class MockWebSocket {
+                    static OPEN = 1;
+
+                    constructor(url) {
+                        globalThis.mockWsState.url = url;
+                        this.readyState = 0;
+                        setTimeout(() => {
+                            this.readyState = 1;
+                            this.onopen({});
+                        }, 0);
+                    }
+
+                    send(data) {
+                        const request = JSON.parse(data);
+                        globalThis.mockWsState.requests.push(request);
+
+                        if (request.method !== "accountSubscribe") {
+                            return;
+                        }
+
+                        const reply = (message) => this.onmessage({ data: JSON.stringify(message) });
+                        setTimeout(() => {
+                            reply({ jsonrpc: "2.0", result: 7, id: request.id });
+                            reply({
+                                jsonrpc: "2.0",
+                                method: "accountNotification",
+                                params: {
+                                    subscription: 7,
+                                    result: {
+                                        context: { slot: 5 },
+                                        value: {
+                                            data: ["AQID", "base64"],
+                                            executable: false,
+                                            lamports: 1500000000,
+                                            owner: "11111111111111111111111111111111",
+                                            rentEpoch: 0,
+                                            space: 3,
+                                        },
+                                    },
+                                },
+                            });
+                        }, 0);
+                    }
+
+                    close() {
+                        this.readyState = 3;
+                        globalThis.mockWsState.closed = true;
+                    }
+                }
+
+                globalThis.WebSocket = MockWebSocket;
+
+                return original;
+                "#,
+            );
+
+            Self(install.call0(&JsValue::null()).unwrap())
+        }
+
+        fn state() -> Reflection {
+            Reflection::new(Reflect::get(&js_sys::global(), &"mockWsState".into()).unwrap())
+                .unwrap()
+        }
+    }
+
+    impl Drop for MockWebSocket {
+        fn drop(&mut self) {
+            Reflect::set(&js_sys::global(), &"WebSocket".into(), &self.0).unwrap();
+        }
+    }
+
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    async fn account_notification() {
+        let _mock = MockWebSocket::new();
+
+        let receiver = open_account_subscription(
+            "wss://rpc.example",
+            &[1u8; 32],
+            Commitment::Confirmed,
+            Logger::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            receiver.recv().await.unwrap(),
+            AccountUpdate {
+                lamports: Lamports(1_500_000_000),
+                data_len: 3,
+                owner: [0u8; 32],
+            }
+        );
+
+        let state = MockWebSocket::state();
+        assert_eq!(state.string("url").unwrap(), "wss://rpc.example");
+
+        let requests = Reflection::new(state.reflect_inner("requests").unwrap())
+            .unwrap()
+            .into_array()
+            .unwrap();
+        let subscribe = Reflection::new(requests.get(0)).unwrap();
+        assert_eq!(subscribe.string("method").unwrap(), "accountSubscribe");
+        assert_eq!(
+            JSON::stringify(&subscribe.reflect_inner("params").unwrap()).unwrap(),
+            r#"["4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi",{"commitment":"confirmed","encoding":"base64"}]"#
+        );
+
+        receiver.close();
+        InnerUtils::sleep(Duration::from_millis(10)).await.unwrap();
+
+        let state = MockWebSocket::state();
+        let unsubscribe = Reflection::new(
+            Reflection::new(state.reflect_inner("requests").unwrap())
+                .unwrap()
+                .into_array()
+                .unwrap()
+                .get(1),
+        )
+        .unwrap();
+        assert_eq!(unsubscribe.string("method").unwrap(), "accountUnsubscribe");
+        assert_eq!(
+            JSON::stringify(&unsubscribe.reflect_inner("params").unwrap()).unwrap(),
+            "[7]"
+        );
+        assert_eq!(
+            Reflect::get(state.get_inner(), &"closed".into()).unwrap(),
+            JsValue::TRUE
+        );
+    }
+}
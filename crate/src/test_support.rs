@@ -0,0 +1,287 @@
+use ed25519_dalek::SigningKey;
+use wallet_adapter_common::WalletCommonUtils;
+use web_sys::{
+    js_sys::{Array, Function, Object, Reflect, Uint8Array},
+    wasm_bindgen::JsValue,
+};
+
+use crate::Wallet;
+
+/// Builds the mock wallet the tests connect to. The wallet supports mainnet, devnet and testnet
+/// and `standard:connect` returns an account that only supports devnet.
+///
+/// The JavaScript object of the wallet exposes these `mock*` members, see [mock_function]:
+/// - `mockCalls()` the number of `standard:connect` calls
+/// - `mockLastInput()` the input of the last `standard:connect` call
+/// - `mockEmit(change)` calls the `change` listeners registered with `standard:events`
+/// - `mockSubscribed()` the number of registered listeners
+/// - `mockExposeAccounts()` exposes the account in the `accounts` of the wallet,
+///   which stay empty until it is called
+/// - `mockAccount` the account object
+#[derive(Debug, Clone)]
+pub(crate) struct TestWallet {
+    name: String,
+    failures: u32,
+    error_name: String,
+    silent_authorized: bool,
+    expose_accounts: bool,
+    features: Vec<(String, JsValue)>,
+}
+
+impl TestWallet {
+    /// A wallet named `Mock Wallet` that connects on the first call
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fail `standard:connect` `failures` times with an error named `error_name`
+    /// before returning the account
+    pub(crate) fn set_connect_failures(&mut self, failures: u32, error_name: &str) -> &mut Self {
+        self.failures = failures;
+        self.error_name = error_name.to_string();
+
+        self
+    }
+
+    /// Whether silent `standard:connect` requests return the account,
+    /// otherwise they return no accounts
+    pub(crate) fn set_silent_authorized(&mut self, authorized: bool) -> &mut Self {
+        self.silent_authorized = authorized;
+
+        self
+    }
+
+    /// Support the `feature` under the `identifier` and add the `identifier`
+    /// to the features of the account
+    pub(crate) fn add_feature(&mut self, identifier: &str, feature: &JsValue) -> &mut Self {
+        self.features
+            .push((identifier.to_string(), feature.clone()));
+
+        self
+    }
+
+    /// Expose the account in the `accounts` of the wallet from the start
+    pub(crate) fn expose_accounts(&mut self) -> &mut Self {
+        self.expose_accounts = true;
+
+        self
+    }
+
+    /// Build the JavaScript object of the wallet as it would be registered by a browser extension
+    pub(crate) fn build_object(&self) -> JsValue {
+        let create_wallet = Function::new_with_args(
+            "name, failures, errorName, silentAuthorized",
+            r#"
+            let calls = 0;
+            let lastInput = null;
+            let exposed = [];
+            const listeners = new Set();
+            const account = {
+                address: "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi",
+                publicKey: new Uint8Array(32).fill(1),
+                chains: ["solana:devnet"],
+                features: ["standard:connect"],
+            };
+
+            return {
+                name,
+                version: "1.0.0",
+                chains: ["solana:mainnet", "solana:devnet", "solana:testnet"],
+                get accounts() {
+                    return exposed;
+                },
+                mockAccount: account,
+                mockCalls: () => calls,
+                mockLastInput: () => lastInput,
+                mockExposeAccounts: () => {
+                    exposed = [account];
+                },
+                mockEmit: (change) => listeners.forEach((listener) => listener(change)),
+                mockSubscribed: () => listeners.size,
+                features: {
+                    "standard:connect": {
+                        version: "1.0.0",
+                        connect: async (input) => {
+                            calls += 1;
+                            lastInput = input;
+
+                            if (calls <= failures) {
+                                const error = new Error("Wallet is not ready");
+                                error.name = errorName;
+                                throw error;
+                            }
+
+                            if (input && input.silent && !silentAuthorized) {
+                                return { accounts: [] };
+                            }
+
+                            return { accounts: [account] };
+                        },
+                    },
+                    "standard:events": {
+                        version: "1.0.0",
+                        on: (_event, listener) => {
+                            listeners.add(listener);
+
+                            return () => listeners.delete(listener);
+                        },
+                    },
+                },
+            };
+            "#,
+        );
+
+        let wallet_object = create_wallet
+            .apply(
+                &JsValue::null(),
+                &Array::of4(
+                    &self.name.as_str().into(),
+                    &self.failures.into(),
+                    &self.error_name.as_str().into(),
+                    &self.silent_authorized.into(),
+                ),
+            )
+            .unwrap();
+
+        let features = Reflect::get(&wallet_object, &"features".into()).unwrap();
+        let account = Reflect::get(&wallet_object, &"mockAccount".into()).unwrap();
+        let account_features = Array::from(&Reflect::get(&account, &"features".into()).unwrap());
+        self.features.iter().for_each(|(identifier, feature)| {
+            Reflect::set(&features, &identifier.into(), feature).unwrap();
+            account_features.push(&identifier.into());
+        });
+
+        if self.expose_accounts {
+            Function::from(Reflect::get(&wallet_object, &"mockExposeAccounts".into()).unwrap())
+                .call0(&JsValue::null())
+                .unwrap();
+        }
+
+        wallet_object
+    }
+
+    /// Build the [Wallet]
+    pub(crate) fn build(&self) -> Wallet {
+        Wallet::from_jsvalue(self.build_object()).unwrap()
+    }
+}
+
+impl Default for TestWallet {
+    fn default() -> Self {
+        Self {
+            name: "Mock Wallet".to_string(),
+            failures: 0,
+            error_name: "Error".to_string(),
+            silent_authorized: true,
+            expose_accounts: false,
+            features: Vec::default(),
+        }
+    }
+}
+
+/// Get a `mock*` member of a wallet created by [TestWallet]
+pub(crate) fn mock_function(wallet: &Wallet, name: &str) -> Function {
+    Reflect::get(wallet.js_object.0.as_ref().unwrap(), &name.into())
+        .unwrap()
+        .into()
+}
+
+/// Call a `mock*` function of a wallet created by [TestWallet] that returns a number
+pub(crate) fn mock_count(wallet: &Wallet, name: &str) -> f64 {
+    mock_function(wallet, name)
+        .call0(&JsValue::null())
+        .unwrap()
+        .as_f64()
+        .unwrap()
+}
+
+/// The account object of the `signing_key` supporting devnet,
+/// `solana:signMessage` and `solana:signTransaction`
+pub(crate) fn account_object(signing_key: &SigningKey) -> Object {
+    let account = Object::new();
+    let public_key = signing_key.verifying_key();
+
+    Reflect::set(
+        &account,
+        &"address".into(),
+        &WalletCommonUtils::address(public_key).into(),
+    )
+    .unwrap();
+    Reflect::set(
+        &account,
+        &"publicKey".into(),
+        &Uint8Array::from(public_key.as_bytes().as_slice()),
+    )
+    .unwrap();
+    Reflect::set(
+        &account,
+        &"chains".into(),
+        &Array::of1(&"solana:devnet".into()),
+    )
+    .unwrap();
+    Reflect::set(
+        &account,
+        &"features".into(),
+        &Array::of2(
+            &"solana:signMessage".into(),
+            &"solana:signTransaction".into(),
+        ),
+    )
+    .unwrap();
+
+    account
+}
+
+/// A wallet with two accounts that signs messages with the pre-computed signature
+/// of the requested account and appends the index of the requested account
+/// to the transaction bytes when signing transactions. Sending a transaction returns
+/// the pre-computed signature and records the options in `globalThis.mockSendOptions`
+pub(crate) fn multi_account_wallet(accounts: &Array, signatures: &Array) -> Wallet {
+    let create_wallet = Function::new_with_args(
+        "accounts, signatures",
+        r#"
+        const index = (account) => accounts.findIndex((inner) => inner.address === account.address);
+
+        return {
+            name: "Multi Account Wallet",
+            version: "1.0.0",
+            chains: ["solana:devnet"],
+            accounts,
+            features: {
+                "standard:connect": {
+                    version: "1.0.0",
+                    connect: async () => ({ accounts }),
+                },
+                "solana:signMessage": {
+                    version: "1.0.0",
+                    signMessage: async ({ account, message }) => [
+                        { signedMessage: message, signature: signatures[index(account)] },
+                    ],
+                },
+                "solana:signTransaction": {
+                    version: "1.0.0",
+                    supportedTransactionVersions: ["legacy", 0],
+                    signTransaction: async (...inputs) => inputs.map(({ account, transaction }) => ({
+                        signedTransaction: new Uint8Array([...transaction, index(account)]),
+                    })),
+                },
+                "solana:signAndSendTransaction": {
+                    version: "1.0.0",
+                    supportedTransactionVersions: ["legacy", 0],
+                    signAndSendTransaction: async (...inputs) => inputs.map(({ account, options }) => {
+                        globalThis.mockSendOptions = options;
+
+                        return { signature: signatures[index(account)] };
+                    }),
+                },
+            },
+        };
+        "#,
+    );
+
+    let wallet_object = create_wallet
+        .call2(&JsValue::null(), accounts, signatures)
+        .unwrap();
+
+    Wallet::from_jsvalue(wallet_object).unwrap()
+}
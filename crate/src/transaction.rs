@@ -0,0 +1,489 @@
+use ed25519_dalek::{Signature, VerifyingKey};
+
+use crate::{WalletError, WalletResult};
+
+/// The byte that marks a versioned message, the remaining bits are the version
+const VERSION_PREFIX_MASK: u8 = 0x80;
+
+/// A serialized transaction split into its signatures,
+/// the serialized message that was signed and the signer account keys
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct TransactionParts<'a> {
+    pub(crate) signatures: Vec<[u8; 64]>,
+    pub(crate) message: &'a [u8],
+    pub(crate) signers: Vec<[u8; 32]>,
+}
+
+impl<'a> TransactionParts<'a> {
+    /// Parse a serialized legacy or v0 transaction
+    pub(crate) fn parse(bytes: &'a [u8]) -> WalletResult<Self> {
+        let mut offset = 0usize;
+
+        let signatures_len = Self::compact_u16(bytes, &mut offset)?;
+        let signatures = (0..signatures_len)
+            .map(|_| Self::take_array::<64>(bytes, &mut offset))
+            .collect::<WalletResult<Vec<[u8; 64]>>>()?;
+
+        let message = &bytes[offset..];
+
+        Ok(Self {
+            signatures,
+            message,
+            signers: Self::message_signers(message)?,
+        })
+    }
+
+    /// Parse the account keys of the required signers from the header and
+    /// the static account keys of a serialized legacy or v0 message
+    fn message_signers(message: &[u8]) -> WalletResult<Vec<[u8; 32]>> {
+        let mut offset = 0usize;
+
+        if message
+            .first()
+            .is_some_and(|byte| byte & VERSION_PREFIX_MASK != 0)
+        {
+            offset += 1;
+        }
+
+        let num_required_signatures =
+            *Self::take(message, &mut offset, 3)?.first().unwrap_or(&0) as usize;
+
+        let account_keys_len = Self::compact_u16(message, &mut offset)?;
+
+        if num_required_signatures > account_keys_len {
+            return Err(WalletError::InvalidTransaction(format!(
+                "`{num_required_signatures}` signatures are required but the message only contains `{account_keys_len}` account keys"
+            )));
+        }
+
+        (0..num_required_signatures)
+            .map(|_| Self::take_array::<32>(message, &mut offset))
+            .collect::<WalletResult<Vec<[u8; 32]>>>()
+    }
+
+    /// Parse the account keys of the address lookup tables referenced by a serialized v0 message
+    fn message_lookup_tables(message: &[u8]) -> WalletResult<Vec<[u8; 32]>> {
+        if message
+            .first()
+            .is_none_or(|byte| byte & VERSION_PREFIX_MASK == 0)
+        {
+            return Err(WalletError::LegacyTransactionSupportRequired);
+        }
+
+        let mut offset = 0usize;
+
+        // Skip the version prefix and the header
+        Self::take(message, &mut offset, 4)?;
+
+        let account_keys_len = Self::compact_u16(message, &mut offset)?;
+        Self::take(message, &mut offset, account_keys_len * 32)?;
+
+        // The recent blockhash
+        Self::take(message, &mut offset, 32)?;
+
+        let instructions_len = Self::compact_u16(message, &mut offset)?;
+        for _ in 0..instructions_len {
+            // The program ID index followed by the account indexes and the data
+            Self::take(message, &mut offset, 1)?;
+            let accounts_len = Self::compact_u16(message, &mut offset)?;
+            Self::take(message, &mut offset, accounts_len)?;
+            let data_len = Self::compact_u16(message, &mut offset)?;
+            Self::take(message, &mut offset, data_len)?;
+        }
+
+        let lookups_len = Self::compact_u16(message, &mut offset)?;
+        (0..lookups_len)
+            .map(|_| {
+                let account_key = Self::take_array::<32>(message, &mut offset)?;
+
+                let writable_len = Self::compact_u16(message, &mut offset)?;
+                Self::take(message, &mut offset, writable_len)?;
+                let readonly_len = Self::compact_u16(message, &mut offset)?;
+                Self::take(message, &mut offset, readonly_len)?;
+
+                Ok(account_key)
+            })
+            .collect()
+    }
+
+    /// Get the signature in the slot of the signer with the `public_key`
+    pub(crate) fn signature_of(&self, public_key: &[u8; 32]) -> Option<&[u8; 64]> {
+        self.signers
+            .iter()
+            .position(|signer| signer == public_key)
+            .and_then(|index| self.signatures.get(index))
+    }
+
+    /// Decode a `compact-u16` (ShortVec) length prefix
+    fn compact_u16(bytes: &[u8], offset: &mut usize) -> WalletResult<usize> {
+        let mut value = 0usize;
+
+        for index in 0..3 {
+            let byte = *Self::take(bytes, offset, 1)?.first().unwrap_or(&0) as usize;
+
+            value |= (byte & 0x7f) << (index * 7);
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(WalletError::InvalidTransaction(
+            "The compact-u16 length prefix is longer than 3 bytes".to_string(),
+        ))
+    }
+
+    fn take_array<const N: usize>(bytes: &[u8], offset: &mut usize) -> WalletResult<[u8; N]> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(Self::take(bytes, offset, N)?);
+
+        Ok(array)
+    }
+
+    fn take<'b>(bytes: &'b [u8], offset: &mut usize, len: usize) -> WalletResult<&'b [u8]> {
+        let end = offset.saturating_add(len);
+
+        let taken = bytes
+            .get(*offset..end)
+            .ok_or(WalletError::InvalidTransaction(format!(
+                "Expected `{len}` bytes at offset `{offset}` of `{}` bytes",
+                bytes.len()
+            )))?;
+
+        *offset = end;
+
+        Ok(taken)
+    }
+}
+
+/// Get the public keys of the accounts required to sign the serialized legacy or v0 `message`,
+/// in the order of their signatures, for example to route the transaction to the right account.
+/// Only the static account keys are read since signers are never loaded from lookup tables
+pub fn required_signers(message: &[u8]) -> WalletResult<Vec<[u8; 32]>> {
+    TransactionParts::message_signers(message)
+}
+
+/// Get the account keys of the address lookup tables referenced by the serialized v0 `message`,
+/// for example to fetch the tables and resolve the accounts of the transaction for display.
+/// Legacy messages cannot reference lookup tables and return the error
+/// [WalletError::LegacyTransactionSupportRequired]
+pub fn lookup_table_keys(message: &[u8]) -> WalletResult<Vec<[u8; 32]>> {
+    TransactionParts::message_lookup_tables(message)
+}
+
+/// Check whether every required signer of the serialized legacy or v0 `transaction`
+/// has signed it, that is none of the signature slots contains the all-zero default signature,
+/// for example to avoid sending a transaction that still needs to be signed by another party
+pub fn is_fully_signed(transaction: &[u8]) -> WalletResult<bool> {
+    let parts = TransactionParts::parse(transaction)?;
+
+    Ok(parts.signatures.len() >= parts.signers.len()
+        && parts
+            .signatures
+            .iter()
+            .all(|signature| signature != &[0u8; 64]))
+}
+
+/// Write each of the `signatures` into the signature slot of its public key
+/// in a copy of the serialized `transaction`.
+/// Every public key must be one of the required signers of the transaction
+pub(crate) fn merge_signatures(
+    transaction: &[u8],
+    signatures: &[([u8; 32], [u8; 64])],
+) -> WalletResult<Vec<u8>> {
+    let parts = TransactionParts::parse(transaction)?;
+    let signatures_start = transaction.len() - parts.message.len() - parts.signatures.len() * 64;
+
+    let mut merged = transaction.to_vec();
+
+    for (public_key, signature) in signatures {
+        let slot = parts
+            .signers
+            .iter()
+            .position(|signer| signer == public_key)
+            .filter(|slot| *slot < parts.signatures.len())
+            .ok_or(WalletError::InvalidTransaction(format!(
+                "`{}` is not a required signer of the transaction",
+                bs58::encode(public_key).into_string()
+            )))?;
+
+        let offset = signatures_start + slot * 64;
+        merged[offset..offset + 64].copy_from_slice(signature);
+    }
+
+    Ok(merged)
+}
+
+/// Verify the signature of the account with `public_key` in each of the `signed` transactions.
+/// Each signature is verified with [VerifyingKey::verify_strict], which rejects weak public keys
+/// and non-canonical signatures that cofactored verification would accept.
+/// A transaction that the account is not required to sign is reported as `false`.
+pub(crate) fn verify_signed_transactions(
+    signed: &[Vec<u8>],
+    public_key: &[u8; 32],
+) -> WalletResult<Vec<bool>> {
+    let verifying_key =
+        VerifyingKey::from_bytes(public_key).or(Err(WalletError::InvalidEd25519PublicKeyBytes))?;
+
+    signed
+        .iter()
+        .map(|transaction| {
+            let transaction_parts = TransactionParts::parse(transaction)?;
+
+            Ok(transaction_parts
+                .signature_of(public_key)
+                .is_some_and(|signature| {
+                    verifying_key
+                        .verify_strict(transaction_parts.message, &Signature::from_bytes(signature))
+                        .is_ok()
+                }))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod transaction_checks {
+    use ed25519_dalek::Verifier;
+    use solana_sdk::{
+        hash::Hash,
+        message::{v0, AddressLookupTableAccount, Message, VersionedMessage},
+        pubkey::Pubkey,
+        signature::Keypair,
+        signer::Signer,
+        transaction::Transaction,
+    };
+    use solana_system_interface::instruction::transfer;
+
+    use super::*;
+
+    fn signed_transaction(payer: &Keypair, cosigner: &Keypair, lamports: u64) -> Vec<u8> {
+        let instruction = transfer(&cosigner.pubkey(), &payer.pubkey(), lamports);
+        let message = Message::new(&[instruction], Some(&payer.pubkey()));
+        let transaction = Transaction::new(&[payer, cosigner], message, Hash::default());
+
+        bincode::serialize(&transaction).unwrap()
+    }
+
+    #[test]
+    fn parse_multiple_signers() {
+        let payer = Keypair::new();
+        let cosigner = Keypair::new();
+        let transaction = signed_transaction(&payer, &cosigner, 1);
+
+        let parts = TransactionParts::parse(&transaction).unwrap();
+
+        assert_eq!(
+            parts.signers,
+            vec![payer.pubkey().to_bytes(), cosigner.pubkey().to_bytes()]
+        );
+        assert_eq!(parts.signatures.len(), 2);
+        assert!(parts.signature_of(&cosigner.pubkey().to_bytes()).is_some());
+        assert!(parts.signature_of(&[1u8; 32]).is_none());
+
+        assert!(TransactionParts::parse(&transaction[..100]).is_err());
+    }
+
+    #[test]
+    fn required_signers_of_message() {
+        let payer = Keypair::new();
+        let cosigner = Keypair::new();
+
+        let instruction = transfer(&payer.pubkey(), &cosigner.pubkey(), 1);
+        let single = Message::new(&[instruction], Some(&payer.pubkey()));
+        assert_eq!(
+            required_signers(&single.serialize()).unwrap(),
+            vec![payer.pubkey().to_bytes()]
+        );
+
+        let instruction = transfer(&cosigner.pubkey(), &payer.pubkey(), 1);
+        let multiple = Message::new(&[instruction], Some(&payer.pubkey()));
+        assert_eq!(
+            required_signers(&multiple.serialize()).unwrap(),
+            vec![payer.pubkey().to_bytes(), cosigner.pubkey().to_bytes()]
+        );
+
+        let v0 = VersionedMessage::V0(
+            v0::Message::try_compile(
+                &payer.pubkey(),
+                &[transfer(&cosigner.pubkey(), &payer.pubkey(), 1)],
+                &[],
+                Hash::default(),
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            required_signers(&v0.serialize()).unwrap(),
+            vec![payer.pubkey().to_bytes(), cosigner.pubkey().to_bytes()]
+        );
+
+        assert!(required_signers(&[1, 0]).is_err());
+    }
+
+    #[test]
+    fn lookup_table_of_v0_message() {
+        let payer = Keypair::new();
+        let recipient = Pubkey::new_unique();
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: vec![recipient],
+        };
+
+        let v0 = VersionedMessage::V0(
+            v0::Message::try_compile(
+                &payer.pubkey(),
+                &[transfer(&payer.pubkey(), &recipient, 1)],
+                std::slice::from_ref(&table),
+                Hash::default(),
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            lookup_table_keys(&v0.serialize()).unwrap(),
+            vec![table.key.to_bytes()]
+        );
+
+        let without_tables = VersionedMessage::V0(
+            v0::Message::try_compile(
+                &payer.pubkey(),
+                &[transfer(&payer.pubkey(), &recipient, 1)],
+                &[],
+                Hash::default(),
+            )
+            .unwrap(),
+        );
+        assert!(lookup_table_keys(&without_tables.serialize())
+            .unwrap()
+            .is_empty());
+
+        let legacy = Message::new(
+            &[transfer(&payer.pubkey(), &recipient, 1)],
+            Some(&payer.pubkey()),
+        );
+        assert_eq!(
+            Err(WalletError::LegacyTransactionSupportRequired),
+            lookup_table_keys(&legacy.serialize())
+        );
+
+        let serialized = v0.serialize();
+        assert!(lookup_table_keys(&serialized[..serialized.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn merge_external_signature() {
+        let payer = Keypair::new();
+        let cosigner = Keypair::new();
+
+        let instruction = transfer(&cosigner.pubkey(), &payer.pubkey(), 1);
+        let message = Message::new(&[instruction], Some(&payer.pubkey()));
+        let mut transaction = Transaction::new_unsigned(message);
+        transaction.partial_sign(&[&payer], Hash::default());
+
+        let cosigner_signature = cosigner.sign_message(&transaction.message_data());
+        let partially_signed = bincode::serialize(&transaction).unwrap();
+
+        let merged = merge_signatures(
+            &partially_signed,
+            &[(
+                cosigner.pubkey().to_bytes(),
+                cosigner_signature.as_ref().try_into().unwrap(),
+            )],
+        )
+        .unwrap();
+
+        transaction.partial_sign(&[&cosigner], Hash::default());
+        assert_eq!(merged, bincode::serialize(&transaction).unwrap());
+        assert_eq!(
+            verify_signed_transactions(&[merged], &cosigner.pubkey().to_bytes()).unwrap(),
+            vec![true]
+        );
+
+        assert!(matches!(
+            merge_signatures(&partially_signed, &[([1u8; 32], [0u8; 64])]),
+            Err(WalletError::InvalidTransaction(_))
+        ));
+    }
+
+    #[test]
+    fn fully_signed() {
+        let payer = Keypair::new();
+        let cosigner = Keypair::new();
+
+        let instruction = transfer(&cosigner.pubkey(), &payer.pubkey(), 1);
+        let message = Message::new(&[instruction], Some(&payer.pubkey()));
+
+        let mut transaction = Transaction::new_unsigned(message);
+        assert!(!is_fully_signed(&bincode::serialize(&transaction).unwrap()).unwrap());
+
+        transaction.partial_sign(&[&payer], Hash::default());
+        assert!(!is_fully_signed(&bincode::serialize(&transaction).unwrap()).unwrap());
+
+        transaction.partial_sign(&[&cosigner], Hash::default());
+        assert!(is_fully_signed(&bincode::serialize(&transaction).unwrap()).unwrap());
+
+        assert!(is_fully_signed(&[1]).is_err());
+    }
+
+    #[test]
+    fn invalid_signature() {
+        let payer = Keypair::new();
+        let cosigner = Keypair::new();
+
+        let mut transactions = (1..=4)
+            .map(|lamports| signed_transaction(&payer, &cosigner, lamports))
+            .collect::<Vec<Vec<u8>>>();
+
+        let cosigner_key = cosigner.pubkey().to_bytes();
+        assert_eq!(
+            verify_signed_transactions(&transactions, &cosigner_key).unwrap(),
+            vec![true; 4]
+        );
+
+        // Corrupt the cosigner's signature, the second signature slot, of the third transaction
+        transactions[2][1 + 64] ^= 1;
+
+        assert_eq!(
+            verify_signed_transactions(&transactions, &cosigner_key).unwrap(),
+            vec![true, true, false, true]
+        );
+        assert_eq!(
+            verify_signed_transactions(&transactions, &payer.pubkey().to_bytes()).unwrap(),
+            vec![true; 4]
+        );
+        assert_eq!(
+            verify_signed_transactions(&transactions, &Keypair::new().pubkey().to_bytes()).unwrap(),
+            vec![false; 4]
+        );
+    }
+
+    #[test]
+    fn weak_key_is_rejected() {
+        let payer = Keypair::new();
+        let cosigner = Keypair::new();
+        let mut transaction = signed_transaction(&payer, &cosigner, 1);
+
+        // The identity point is a weak public key, the signature with the identity point
+        // as `R` and a zero scalar passes cofactored verification for any message
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        let mut signature = [0u8; 64];
+        signature[0] = 1;
+
+        // Two signatures followed by the three bytes of the message header and the number of
+        // accounts, the cosigner is the second account
+        let cosigner_key_offset = 1 + 2 * 64 + 3 + 1 + 32;
+        transaction[cosigner_key_offset..cosigner_key_offset + 32].copy_from_slice(&identity);
+        transaction[1 + 64..1 + 2 * 64].copy_from_slice(&signature);
+
+        let parts = TransactionParts::parse(&transaction).unwrap();
+        assert!(VerifyingKey::from_bytes(&identity)
+            .unwrap()
+            .verify(parts.message, &Signature::from_bytes(&signature))
+            .is_ok());
+
+        assert_eq!(
+            verify_signed_transactions(&[transaction], &identity).unwrap(),
+            vec![false]
+        );
+    }
+}
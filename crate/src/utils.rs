@@ -1,3 +1,6 @@
+use core::{future::Future, task::Poll};
+use std::rc::Rc;
+
 use wallet_adapter_common::WalletCommonUtils;
 use web_sys::{
     js_sys::{self, Array, Function, Object, Reflect},
@@ -9,7 +12,65 @@ use crate::{WalletError, WalletResult};
 /// Helper utilities
 pub struct InnerUtils;
 
+/// An optional hook invoked with the raw [JsValue] response of a wallet before it is parsed.
+/// All inspectors are considered equal so that types containing it can still be compared.
+#[derive(Clone, Default)]
+pub(crate) struct ResponseInspector(Option<InspectorFn>);
+
+/// The hook of a [ResponseInspector]
+pub(crate) type InspectorFn = Rc<dyn Fn(&JsValue)>;
+
+impl ResponseInspector {
+    /// Instantiate [ResponseInspector] with the hook `inspector`
+    pub(crate) fn new(inspector: InspectorFn) -> Self {
+        Self(Some(inspector))
+    }
+
+    /// Invoke the hook with the `response`. This is a no-op if no hook was set
+    pub(crate) fn inspect(&self, response: &JsValue) {
+        if let Some(inspector) = self.0.as_ref() {
+            inspector(response)
+        }
+    }
+}
+
+impl core::fmt::Debug for ResponseInspector {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("ResponseInspector")
+            .field(&self.0.is_some())
+            .finish()
+    }
+}
+
+impl PartialEq for ResponseInspector {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for ResponseInspector {}
+
+impl PartialOrd for ResponseInspector {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ResponseInspector {
+    fn cmp(&self, _other: &Self) -> core::cmp::Ordering {
+        core::cmp::Ordering::Equal
+    }
+}
+
 impl InnerUtils {
+    /// Convert the bytes of a signature returned by a wallet into a 64 byte array.
+    /// Returns [WalletError::InvalidSignatureLength] containing the length of `bytes` otherwise
+    pub fn signature_bytes(bytes: &[u8]) -> WalletResult<[u8; 64]> {
+        bytes
+            .try_into()
+            .or(Err(WalletError::InvalidSignatureLength(bytes.len())))
+    }
+
     /// Convert a [JsValue] error to a [WalletError]
     pub fn jsvalue_to_error<T: core::fmt::Debug>(
         value: Result<T, JsValue>,
@@ -21,6 +82,51 @@ impl InnerUtils {
         }
     }
 
+    /// Convert the `outcome` of calling a wallet function into a promise, so that a function
+    /// that throws synchronously is handled like one that returns a rejected promise
+    /// and both surface as the same [WalletError]
+    pub(crate) fn into_promise(outcome: Result<JsValue, JsValue>) -> js_sys::Promise {
+        match outcome {
+            Ok(value) => js_sys::Promise::resolve(&value),
+            Err(error) => js_sys::Promise::reject(&error),
+        }
+    }
+
+    /// Wait for the given [Duration](core::time::Duration) using the JavaScript `setTimeout` function
+    /// of the global object, this works in browser windows and web workers
+    pub async fn sleep(duration: core::time::Duration) -> WalletResult<()> {
+        let set_timeout = Reflection::new(js_sys::global().into())?.get_function("setTimeout")?;
+        let timeout = duration.as_millis().min(i32::MAX as u128) as i32;
+
+        // A failure to schedule the timeout rejects the promise so the caller gets the error
+        let mut on_timeout = |resolve: Function, reject: Function| {
+            if let Err(error) = set_timeout.call2(&JsValue::null(), &resolve, &timeout.into()) {
+                let _ = reject.call1(&JsValue::null(), &error);
+            }
+        };
+        let promise = js_sys::Promise::new(&mut on_timeout);
+
+        wasm_bindgen_futures::JsFuture::from(promise).await?;
+
+        Ok(())
+    }
+
+    /// Poll both futures and return the output of the one that completes first.
+    /// The other future is dropped
+    pub async fn race<T>(first: impl Future<Output = T>, second: impl Future<Output = T>) -> T {
+        let mut first = core::pin::pin!(first);
+        let mut second = core::pin::pin!(second);
+
+        core::future::poll_fn(|cx| {
+            if let Poll::Ready(output) = first.as_mut().poll(cx) {
+                return Poll::Ready(output);
+            }
+
+            second.as_mut().poll(cx)
+        })
+        .await
+    }
+
     /// Convert a [JsValue] to a [ed25519_dalek::Signature]
     pub fn jsvalue_to_signature(
         value: JsValue,
@@ -46,6 +152,9 @@ impl InnerUtils {
 pub struct Reflection(JsValue);
 
 impl Reflection {
+    /// `Number.MAX_SAFE_INTEGER` the largest integer a JS number can represent exactly
+    const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
     /// Initialize [Reflection] and check if the value is null or undefined
     pub fn new(value: JsValue) -> WalletResult<Self> {
         Reflection::check_is_undefined(&value)?;
@@ -136,6 +245,39 @@ impl Reflection {
         Ok(name.as_string())
     }
 
+    /// Reflect the `key` from the value of [Self](Reflection) and return the
+    /// reflected JS number as a [u64]
+    pub fn u64(&self, key: &str) -> WalletResult<u64> {
+        self.opt_u64(key)?
+            .ok_or(WalletError::ExpectedValueNotFound(key.to_string()))
+    }
+
+    /// Reflect the `key` from the value of [Self](Reflection) and return the
+    /// reflected JS number as a [u64] or [None] if the value is `undefined` or `null`.
+    /// Fractional, negative and numbers larger than `Number.MAX_SAFE_INTEGER`
+    /// return a [WalletError::InvalidNumber] error
+    pub fn opt_u64(&self, key: &str) -> WalletResult<Option<u64>> {
+        Self::number_to_u64(&Reflect::get(&self.0, &key.into())?, key)
+    }
+
+    /// Convert the JS number `value` to a [u64] or [None] if the value is `undefined` or `null`
+    /// with the same range checks as [Self::opt_u64]. The `key` is used in error messages
+    pub(crate) fn number_to_u64(value: &JsValue, key: &str) -> WalletResult<Option<u64>> {
+        if value.is_undefined() || value.is_null() {
+            return Ok(None);
+        }
+
+        let number = value.as_f64().ok_or(WalletError::InternalError(format!(
+            "Reflecting {key:?} did not yield a JsNumber"
+        )))?;
+
+        if number.fract() != 0.0 || !(0.0..=Self::MAX_SAFE_INTEGER).contains(&number) {
+            return Err(WalletError::InvalidNumber(format!("{key}: {number}")));
+        }
+
+        Ok(Some(number as u64))
+    }
+
     /// Reflect the `key` from the value of [Self](Reflection) and return the
     /// reflected value as a [Vec of Vec of bytes](Vec<Vec<u8>>)
     pub fn get_bytes_from_vec(&self, key: &str) -> WalletResult<Vec<Vec<u8>>> {
@@ -190,6 +332,26 @@ impl Reflection {
         Ok(byte32array)
     }
 
+    /// Reflect the `key` from the value of [Self](Reflection) and return the
+    /// reflected value as a 32 byte array, decoding it from Base58 if the value
+    /// is a string instead of bytes
+    pub fn byte32array_or_base58(&self, key: &str) -> WalletResult<[u8; 32]> {
+        let js_value = Reflect::get(&self.0, &key.into())?;
+
+        // Check for a string before reading bytes since a string is coerced
+        // into bytes of its characters, which is 32 bytes for some addresses
+        match js_value.as_string() {
+            Some(base58) => {
+                let bytes = bs58::decode(&base58)
+                    .into_vec()
+                    .or(Err(WalletError::InvalidBase58Address))?;
+
+                bytes.try_into().or(Err(WalletError::Expected32ByteLength))
+            }
+            None => self.byte32array(key),
+        }
+    }
+
     /// Return the value of [Self](Reflection) as a [js_sys::Array]
     /// without consuming `Self`
     pub fn get_array(&self) -> WalletResult<Array> {
@@ -342,3 +504,63 @@ impl Clone for Reflection {
         Reflection(self.0.clone())
     }
 }
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod reflection_number_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn reflect_u64() {
+        let object = Object::new();
+        Reflect::set(&object, &"lamports".into(), &5_000.into()).unwrap();
+        Reflect::set(&object, &"fractional".into(), &1.5.into()).unwrap();
+        Reflect::set(&object, &"negative".into(), &(-1).into()).unwrap();
+        Reflect::set(&object, &"tooLarge".into(), &1e300.into()).unwrap();
+        Reflect::set(&object, &"empty".into(), &JsValue::null()).unwrap();
+        let reflection = Reflection::new(object.into()).unwrap();
+
+        assert_eq!(Ok(5_000), reflection.u64("lamports"));
+        assert_eq!(Ok(Some(5_000)), reflection.opt_u64("lamports"));
+
+        assert!(matches!(
+            reflection.u64("fractional"),
+            Err(WalletError::InvalidNumber(_))
+        ));
+        assert!(matches!(
+            reflection.opt_u64("negative"),
+            Err(WalletError::InvalidNumber(_))
+        ));
+        assert!(matches!(
+            reflection.opt_u64("tooLarge"),
+            Err(WalletError::InvalidNumber(_))
+        ));
+
+        assert_eq!(Ok(None), reflection.opt_u64("missing"));
+        assert_eq!(Ok(None), reflection.opt_u64("empty"));
+        assert_eq!(
+            Err(WalletError::ExpectedValueNotFound("missing".to_string())),
+            reflection.u64("missing")
+        );
+    }
+}
+
+#[cfg(test)]
+mod signature_length_checks {
+    use super::*;
+
+    #[test]
+    fn signature_bytes() {
+        assert_eq!(
+            Err(WalletError::InvalidSignatureLength(63)),
+            InnerUtils::signature_bytes(&[1u8; 63])
+        );
+        assert_eq!(
+            Err(WalletError::InvalidSignatureLength(0)),
+            InnerUtils::signature_bytes(&[])
+        );
+        assert_eq!(Ok([1u8; 64]), InnerUtils::signature_bytes(&[1u8; 64]));
+    }
+}
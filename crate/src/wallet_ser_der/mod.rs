@@ -2,7 +2,7 @@ mod version;
 pub use version::*;
 
 mod wallet_icon;
-pub(crate) use wallet_icon::*;
+pub use wallet_icon::*;
 
 mod wallet;
 pub use wallet::*;
@@ -2,7 +2,10 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::{Reflection, WalletError, WalletResult};
 
-use wallet_adapter_common::{clusters::Cluster, signin_standard::SigninInput as SigninInputLib};
+use wallet_adapter_common::{
+    clusters::Cluster,
+    signin_standard::{ResourceUri, SigninInput as SigninInputLib},
+};
 use web_sys::{
     js_sys::{self, Array},
     wasm_bindgen::JsValue,
@@ -249,6 +252,17 @@ impl SigninInput {
         Ok(signin_input_object.take())
     }
 
+    /// Builds the Sign In With Solana (SIWS) message text
+    /// as it is expected to be signed by the wallet
+    pub fn to_message(&self) -> String {
+        self.0.to_message()
+    }
+
+    /// The [Sha3_256](sha3::Sha3_256) hash of the message generated by [Self::to_message()]
+    pub fn message_hash(&self) -> [u8; 32] {
+        self.0.message_hash()
+    }
+
     /// An EIP-4361 Request ID.
     /// In addition to using nonce to avoid replay attacks,
     /// dapps can also choose to include a unique signature in the requestId .
@@ -278,6 +292,15 @@ impl SigninInput {
         self
     }
 
+    /// Same as [Self::add_resource()] but the resource must be a URI with a scheme,
+    /// like `https://example.com/terms`, otherwise the error
+    /// [WalletError::InvalidResourceUri] is returned
+    pub fn add_resource_uri(&mut self, uri: &str) -> WalletResult<&mut Self> {
+        self.0.add_resource_uri(uri)?;
+
+        Ok(self)
+    }
+
     /// Get the `domain` field
     pub fn domain(&self) -> Option<&String> {
         self.0.domain()
@@ -352,6 +375,11 @@ impl SigninInput {
     pub fn resources(&self) -> &[String] {
         self.0.resources()
     }
+
+    /// Get the `resources` that are URIs split into their components
+    pub fn resource_uris(&self) -> Vec<ResourceUri<'_>> {
+        self.0.resource_uris()
+    }
 }
 
 #[cfg(test)]
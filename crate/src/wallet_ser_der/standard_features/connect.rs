@@ -1,7 +1,8 @@
-use web_sys::{js_sys, wasm_bindgen::JsValue};
+use web_sys::wasm_bindgen::JsValue;
 
 use crate::{
-    Reflection, SemverVersion, StandardFunction, WalletAccount, WalletError, WalletResult,
+    InnerUtils, Reflection, ResponseInspector, SemverVersion, StandardFunction, WalletAccount,
+    WalletError, WalletResult,
 };
 
 /// The `standard:connect` struct containing a `version` and `callback`
@@ -17,35 +18,60 @@ impl Connect {
         )?))
     }
 
-    /// Connect to a wallet by calling the callback function
-    pub(crate) async fn call_connect(&self) -> WalletResult<WalletAccount> {
-        let outcome = self.0.callback.call0(&JsValue::from_bool(false))?;
+    /// Connect to a wallet by calling the callback function with the `{ silent }` input
+    /// and return all the accounts the user authorized, which are never empty.
+    /// A silent request only succeeds without prompting the user if the dapp was
+    /// previously authorized, otherwise the wallet returns no accounts
+    pub(crate) async fn call_connect(
+        &self,
+        inspector: &ResponseInspector,
+        silent: bool,
+    ) -> WalletResult<Vec<WalletAccount>> {
+        let accounts = self.call_connect_accounts(inspector, silent).await?;
 
-        let outcome = js_sys::Promise::resolve(&outcome);
+        if accounts.is_empty() {
+            Err(WalletError::ConnectHasNoAccounts)
+        } else {
+            Ok(accounts)
+        }
+    }
+
+    /// Connect to a wallet by calling the callback function with the `{ silent }` input
+    /// and return all the accounts the user authorized
+    pub(crate) async fn call_connect_accounts(
+        &self,
+        inspector: &ResponseInspector,
+        silent: bool,
+    ) -> WalletResult<Vec<WalletAccount>> {
+        let mut input = Reflection::new_object();
+        input.set_object(&"silent".into(), &JsValue::from_bool(silent))?;
+
+        let outcome = InnerUtils::into_promise(
+            self.0
+                .callback
+                .call1(&JsValue::from_bool(false), input.get_inner()),
+        );
 
         wasm_bindgen_futures::JsFuture::from(outcome)
             .await
             .map(|success| {
+                inspector.inspect(&success);
+
                 let get_accounts = Reflection::new(success)?.reflect_js_array("accounts")?;
 
-                let wallet_account = get_accounts
+                get_accounts
                     .into_iter()
                     .map(|raw_account| WalletAccount::parse(Reflection::new(raw_account)?))
                     .collect::<WalletResult<Vec<WalletAccount>>>()
-                    .map(|mut accounts| {
-                        if accounts.is_empty() {
-                            Err(WalletError::ConnectHasNoAccounts)
-                        } else {
-                            Ok(accounts.remove(0))
-                        }
-                    })??;
-
-                Ok(wallet_account)
             })
             .map_err(|error| {
                 let value: WalletError = error.into();
 
-                WalletError::WalletConnectError(value.to_string())
+                if value.is_user_rejection() {
+                    value
+                } else {
+                    WalletError::WalletConnectError(value.to_string())
+                }
             })?
     }
 }
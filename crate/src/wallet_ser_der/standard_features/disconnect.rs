@@ -1,6 +1,9 @@
-use web_sys::{js_sys, wasm_bindgen::JsValue};
+use web_sys::wasm_bindgen::JsValue;
 
-use crate::{Reflection, SemverVersion, StandardFunction, WalletError, WalletResult};
+use crate::{
+    InnerUtils, Reflection, ResponseInspector, SemverVersion, StandardFunction, WalletError,
+    WalletResult,
+};
 
 /// `standard:disconnect` struct containing the `version` and `callback`
 /// in the field [StandardFunction]
@@ -20,16 +23,20 @@ impl Disconnect {
 
     /// Calling this method disconnects the wallet by internally calling the
     /// callback function
-    pub(crate) async fn call_disconnect(&self) -> WalletResult<()> {
-        let outcome = self.0.callback.call0(&JsValue::null())?;
+    pub(crate) async fn call_disconnect(&self, inspector: &ResponseInspector) -> WalletResult<()> {
+        let outcome = InnerUtils::into_promise(self.0.callback.call0(&JsValue::null()));
 
-        let outcome = js_sys::Promise::resolve(&outcome);
+        match wasm_bindgen_futures::JsFuture::from(outcome).await {
+            Ok(success) => {
+                inspector.inspect(&success);
 
-        if let Some(error) = wasm_bindgen_futures::JsFuture::from(outcome).await.err() {
-            let value: WalletError = error.into();
-            return Err(WalletError::WalletDisconnectError(value.to_string()));
-        }
+                Ok(())
+            }
+            Err(error) => {
+                let value: WalletError = error.into();
 
-        Ok(())
+                Err(WalletError::WalletDisconnectError(value.to_string()))
+            }
+        }
     }
 }
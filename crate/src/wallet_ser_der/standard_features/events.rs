@@ -1,11 +1,21 @@
-use std::{future::Future, pin::Pin};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
 use async_channel::Receiver;
-use web_sys::wasm_bindgen::{prelude::Closure, JsValue};
+use web_sys::{
+    js_sys::Reflect,
+    wasm_bindgen::{prelude::Closure, JsValue},
+};
 
 use crate::{
-    ConnectionInfoInner, Reflection, SemverVersion, StandardFunction, WalletAccount, WalletError,
-    WalletEvent, WalletEventSender, WalletResult,
+    ConnectionInfoInner, LogLevel, Logger, Reflection, SemverVersion, StandardFunction,
+    WalletAccount, WalletError, WalletEvent, WalletEventSender, WalletResult,
 };
 
 /// `standard:events` struct containing the `version` and `callback`
@@ -24,105 +34,198 @@ impl StandardEvents {
         }))
     }
 
+    /// Register a `change` listener with `[standard:events].on`. The listener is
+    /// unsubscribed once the `stop_signal` channel is closed, which happens when the
+    /// adapter disconnects, reconnects or is dropped. The `active_listeners` count
+    /// is incremented while the listener is registered
     pub(crate) async fn call_on_event(
         &self,
         connection_info: ConnectionInfoInner,
         wallet_name: String,
         sender: WalletEventSender,
         stop_signal: Receiver<()>,
+        active_listeners: Arc<AtomicUsize>,
+        logger: Logger,
     ) -> WalletResult<()> {
         let sender2 = sender.clone();
+        let logger2 = logger.clone();
+        let stop_signal2 = stop_signal.clone();
 
         let on_account_change = Closure::wrap(Box::new(move |value: JsValue| {
             let wallet_name = wallet_name.clone();
-            web_sys::console::log_3(
-                &"CALLED ON EV for ".into(),
-                &wallet_name.clone().into(),
-                &value,
+            logger2.log(
+                LogLevel::Debug,
+                &format!("CALLED ON EV for {wallet_name}: {value:?}"),
             );
 
             let connection_info_inner = connection_info.clone();
             let sender_inner = sender2.clone();
+            let logger_inner = logger2.clone();
+            let stop_signal_inner = stop_signal2.clone();
 
             wasm_bindgen_futures::spawn_local(async move {
-                let reflect_accounts =
-                    send_wallet_event_error()(Reflection::new(value), sender_inner.clone())
-                        .await
-                        .unwrap(); // Never fails
-                let mut get_accounts = send_wallet_event_error()(
-                    reflect_accounts.reflect_js_array("accounts"),
-                    sender_inner.clone(),
+                on_change(
+                    value,
+                    &wallet_name,
+                    connection_info_inner,
+                    sender_inner,
+                    stop_signal_inner,
+                    logger_inner,
                 )
                 .await
-                .unwrap()
-                .to_vec(); // Never fails
-
-                let processed_wallet_account = if !get_accounts.is_empty() {
-                    let first_account = send_wallet_event_error()(
-                        Reflection::new(get_accounts.remove(0)),
-                        sender_inner.clone(),
-                    )
-                    .await
-                    .unwrap(); // Never fails
-
-                    let account_processing = send_wallet_event_error()(
-                        WalletAccount::parse(first_account),
-                        sender_inner.clone(),
-                    )
-                    .await
-                    .unwrap(); //Never fails
-                    web_sys::console::error_2(
-                        &"PRE ACCOUNT PROCESSING".into(),
-                        &format!("{account_processing:?}").into(),
-                    );
-
-                    Some(account_processing)
-                } else {
-                    Option::None
-                };
-
-                connection_info_inner
-                    .write()
-                    .await
-                    .emit_wallet_event(&wallet_name, processed_wallet_account, sender_inner.clone())
-                    .await
             });
         }) as Box<dyn Fn(_)>);
 
         let on_account_change_fn =
             Reflection::new(on_account_change.into_js_value())?.into_function()?;
 
-        let on_event_fn = self.0.callback.clone();
+        let invoke_outcome = self
+            .0
+            .callback
+            .call2(
+                &JsValue::null(),
+                &"change".into(),
+                &on_account_change_fn.into(),
+            )
+            .map_err(|error| {
+                let into_error: WalletError = error.into();
+
+                into_error
+            });
+
+        let Ok(unsubscribe) =
+            send_wallet_event_error(logger.clone())(invoke_outcome, sender.clone()).await
+        else {
+            return Ok(());
+        };
+
+        active_listeners.fetch_add(1, Ordering::SeqCst);
 
         wasm_bindgen_futures::spawn_local(async move {
-            while (stop_signal.recv().await).is_ok() {
-                let invoke_outcome = on_event_fn
-                    .call2(
-                        &JsValue::null(),
-                        &"change".into(),
-                        &on_account_change_fn.clone().into(),
-                    )
-                    .map_err(|error| {
-                        let into_error: WalletError = error.into();
-
-                        into_error
-                    });
-
-                send_wallet_event_error()(invoke_outcome, sender.clone())
-                    .await
-                    .unwrap();
+            // Resolves with an error once the channel is closed
+            let _ = stop_signal.recv().await;
+
+            match Reflection::new(unsubscribe).and_then(|unsubscribe| unsubscribe.into_function()) {
+                Ok(unsubscribe) => {
+                    if let Err(error) = unsubscribe.call0(&JsValue::null()) {
+                        logger.log(
+                            LogLevel::Warn,
+                            &format!("UNSUBSCRIBE FROM [standard:events] ERROR: {error:?}"),
+                        );
+                    }
+                }
+                Err(error) => logger.log(
+                    LogLevel::Warn,
+                    &format!(
+                        "[standard:events]on() DID NOT RETURN AN UNSUBSCRIBE FUNCTION: {error:?}"
+                    ),
+                ),
             }
+
+            active_listeners.fetch_sub(1, Ordering::SeqCst);
         });
 
         Ok(())
     }
 }
 
-pub(crate) async fn send_wallet_event(wallet_event: WalletEvent, sender: WalletEventSender) {
+/// Process the properties of the wallet passed to the `change` event listener.
+/// A change without `accounts`, for example when only the `chains` or `features` changed,
+/// does not affect the connection. A change with an empty `accounts` array means the user
+/// logged out of the wallet, the connected wallet and account are cleared and
+/// [WalletEvent::Disconnected] is emitted. A change with `accounts: null` is an explicit disconnect initiated from the wallet,
+/// which also clears the connection and closes the `stop_signal` of the listener like
+/// [crate::WalletAdapter::disconnect]. Errors are emitted as [WalletEvent::BackgroundTaskError]
+pub(crate) async fn on_change(
+    value: JsValue,
+    wallet_name: &str,
+    connection_info: ConnectionInfoInner,
+    sender: WalletEventSender,
+    stop_signal: Receiver<()>,
+    logger: Logger,
+) {
+    let Ok(reflect_accounts) =
+        send_wallet_event_error(logger.clone())(Reflection::new(value), sender.clone()).await
+    else {
+        return;
+    };
+
+    if is_disconnect_notification(&reflect_accounts) {
+        connection_info
+            .write()
+            .await
+            .emit_wallet_disconnect(wallet_name, sender)
+            .await;
+        // Unsubscribes this listener, the wallet will not emit changes for this connection
+        stop_signal.close();
+
+        return;
+    }
+
+    if reflect_accounts.reflect_inner("accounts").is_err() {
+        logger.log(
+            LogLevel::Debug,
+            &format!("CHANGE EVENT WITHOUT ACCOUNTS for {wallet_name}"),
+        );
+
+        return;
+    }
+
+    let Ok(get_accounts) = send_wallet_event_error(logger.clone())(
+        reflect_accounts.reflect_js_array("accounts"),
+        sender.clone(),
+    )
+    .await
+    else {
+        return;
+    };
+
+    let Ok(changed_accounts) = send_wallet_event_error(logger.clone())(
+        get_accounts
+            .iter()
+            .map(|account| Reflection::new(account).and_then(WalletAccount::parse))
+            .collect::<WalletResult<Vec<WalletAccount>>>(),
+        sender.clone(),
+    )
+    .await
+    else {
+        return;
+    };
+
+    logger.log(
+        LogLevel::Debug,
+        &format!("PRE ACCOUNT PROCESSING {changed_accounts:?}"),
+    );
+
+    let mut connection_info = connection_info.write().await;
+
+    connection_info.update_authorized_accounts(
+        wallet_name,
+        changed_accounts
+            .iter()
+            .map(|account| account.public_key())
+            .collect(),
+    );
+
+    connection_info
+        .emit_wallet_event(wallet_name, changed_accounts.into_iter().next(), sender)
+        .await
+}
+
+/// Whether the change is a disconnect notification, with `accounts` explicitly set to `null`
+fn is_disconnect_notification(change: &Reflection) -> bool {
+    Reflect::get(change.get_inner(), &"accounts".into()).is_ok_and(|accounts| accounts.is_null())
+}
+
+pub(crate) async fn send_wallet_event(
+    wallet_event: WalletEvent,
+    sender: WalletEventSender,
+    logger: &Logger,
+) {
     if let Err(error) = sender.clone().send(wallet_event).await {
-        web_sys::console::log_2(
-            &"BACKGROUND TASK ERROR: [standard:events]on() > ".into(),
-            &format!("{error:?}").into(),
+        logger.log(
+            LogLevel::Error,
+            &format!("BACKGROUND TASK ERROR: [standard:events]on() > {error:?}"),
         );
     }
 }
@@ -130,27 +233,32 @@ pub(crate) async fn send_wallet_event(wallet_event: WalletEvent, sender: WalletE
 type SendWalletEventErrorOutput<T> = Pin<Box<dyn Future<Output = Result<T, ()>>>>;
 
 pub(crate) fn send_wallet_event_error<T>(
+    logger: Logger,
 ) -> impl Fn(WalletResult<T>, WalletEventSender) -> SendWalletEventErrorOutput<T> + 'static
 where
     T: core::fmt::Debug + 'static,
 {
     move |outcome: WalletResult<T>, sender: WalletEventSender| {
+        let logger = logger.clone();
+
         Box::pin(async move {
             match outcome {
                 Ok(value) => Ok(value),
                 Err(error) => {
-                    web_sys::console::log_2(
-                        &"BACKGROUND TASK ERROR: [standard:events]on() > ".into(),
-                        &format!("{error:?}").into(),
+                    logger.log(
+                        LogLevel::Error,
+                        &format!("BACKGROUND TASK ERROR: [standard:events]on() > {error:?}"),
                     );
 
                     if let Err(channel_error) = sender
                         .send(WalletEvent::BackgroundTaskError(error.clone()))
                         .await
                     {
-                        web_sys::console::log_2(
-                            &"Encountered error while sending a wallet event: ".into(),
-                            &format!("{channel_error:?}").into(),
+                        logger.log(
+                            LogLevel::Error,
+                            &format!(
+                                "Encountered error while sending a wallet event: {channel_error:?}"
+                            ),
                         );
                     }
 
@@ -160,3 +268,139 @@ where
         })
     }
 }
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod change_event_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::JSON;
+
+    use super::*;
+    use crate::{test_support::TestWallet, WalletAdapter};
+
+    #[wasm_bindgen_test]
+    async fn empty_accounts_is_logout() {
+        let mut adapter = WalletAdapter::init().unwrap();
+        let events = adapter.events();
+        let wallet = TestWallet::new().build();
+        adapter.connect(wallet).await.unwrap();
+        assert!(matches!(events.recv().await, Ok(WalletEvent::Connected(_))));
+
+        let change = |value: &str| {
+            on_change(
+                JSON::parse(value).unwrap(),
+                "Mock Wallet",
+                adapter.connection_info_inner(),
+                adapter.wallet_events_sender(),
+                async_channel::bounded(1).1,
+                adapter.logger(),
+            )
+        };
+
+        change(r#"{ "chains": ["solana:devnet"] }"#).await;
+        assert!(events.is_empty());
+        assert!(adapter.is_connected().await);
+
+        change(r#"{ "accounts": [] }"#).await;
+        assert_eq!(Ok(WalletEvent::Disconnected), events.recv().await);
+        assert!(!adapter.is_connected().await);
+        assert_eq!(
+            Err(WalletError::AccountNotFound),
+            adapter.connection_info().await.connected_account().cloned()
+        );
+        assert_eq!(
+            Err(WalletError::WalletNotFound),
+            adapter.connection_info().await.connected_wallet().cloned()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn wallet_initiated_disconnect() {
+        use crate::test_support::mock_function;
+
+        let mut adapter = WalletAdapter::init().unwrap();
+        let events = adapter.events();
+        let wallet = TestWallet::new().build();
+        let emit = mock_function(&wallet, "mockEmit");
+        let subscribed = mock_function(&wallet, "mockSubscribed");
+
+        adapter.connect(wallet).await.unwrap();
+        assert!(matches!(events.recv().await, Ok(WalletEvent::Connected(_))));
+        assert_eq!(adapter.active_event_listeners(), 1);
+
+        emit.call1(
+            &JsValue::null(),
+            &JSON::parse(r#"{ "accounts": null }"#).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(Ok(WalletEvent::Disconnected), events.recv().await);
+        assert!(!adapter.is_connected().await);
+        let connection_info = adapter.connection_info().await;
+        assert!(connection_info.connected_wallet().is_err());
+        assert!(connection_info.previous_accounts().is_empty());
+        drop(connection_info);
+
+        // Let the listener task observe the closed signal
+        crate::InnerUtils::sleep(core::time::Duration::from_millis(10))
+            .await
+            .unwrap();
+        assert_eq!(adapter.active_event_listeners(), 0);
+        assert_eq!(
+            subscribed.call0(&JsValue::null()).unwrap(),
+            JsValue::from(0)
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn change_updates_authorized_accounts() {
+        use ed25519_dalek::SigningKey;
+        use web_sys::js_sys::Array;
+
+        use crate::test_support::{account_object, multi_account_wallet};
+
+        let first = SigningKey::from_bytes(&[1u8; 32]);
+        let second = SigningKey::from_bytes(&[2u8; 32]);
+        let accounts = Array::of2(&account_object(&first), &account_object(&second));
+
+        let mut adapter = WalletAdapter::init().unwrap();
+        adapter
+            .connect(multi_account_wallet(&accounts, &Array::new()))
+            .await
+            .unwrap();
+
+        let snapshot = adapter
+            .connection_info()
+            .await
+            .authorized_accounts()
+            .to_vec();
+        assert_eq!(snapshot.len(), 2);
+
+        let change = web_sys::js_sys::Object::new();
+        Reflect::set(
+            &change,
+            &"accounts".into(),
+            &Array::of1(&account_object(&first)),
+        )
+        .unwrap();
+
+        on_change(
+            change.into(),
+            "Multi Account Wallet",
+            adapter.connection_info_inner(),
+            adapter.wallet_events_sender(),
+            async_channel::bounded(1).1,
+            adapter.logger(),
+        )
+        .await;
+
+        assert_eq!(
+            adapter.connection_info().await.authorized_accounts(),
+            [first.verifying_key().to_bytes()]
+        );
+        assert_eq!(
+            adapter.accounts_removed_since(&snapshot).await,
+            vec![second.verifying_key().to_bytes()]
+        );
+    }
+}
@@ -1,12 +1,15 @@
 use wallet_adapter_common::{
-    feature_support::FeatureSupport,
+    feature_support::{FeatureSupport, WalletFeature},
     standardized_events::{
-        SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER, SOLANA_SIGN_IN_IDENTIFIER,
-        SOLANA_SIGN_MESSAGE_IDENTIFIER, SOLANA_SIGN_TRANSACTION_IDENTIFIER,
-        STANDARD_CONNECT_IDENTIFIER, STANDARD_DISCONNECT_IDENTIFIER, STANDARD_EVENTS_IDENTIFIER,
+        SOLANA_SIGN_ALL_TRANSACTIONS_IDENTIFIER, SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER,
+        SOLANA_SIGN_IN_IDENTIFIER, SOLANA_SIGN_MESSAGE_IDENTIFIER,
+        SOLANA_SIGN_TRANSACTION_IDENTIFIER, STANDARD_CONNECT_IDENTIFIER,
+        STANDARD_DISCONNECT_IDENTIFIER, STANDARD_EVENTS_IDENTIFIER,
     },
 };
 
+use web_sys::js_sys::Array;
+
 use crate::{
     Connect, Disconnect, Reflection, SemverVersion, SignIn, SignMessage, SignTransaction,
     StandardEvents, WalletError, WalletResult,
@@ -28,29 +31,42 @@ pub struct Features {
     pub(crate) sign_and_send_tx: SignTransaction,
     /// solana:signTransaction
     pub(crate) sign_tx: SignTransaction,
+    /// solana:signAllTransactions
+    pub(crate) sign_all_tx: Option<SignTransaction>,
     /// solana:signMessage
     pub(crate) sign_message: SignMessage,
     /// solana:signIn
     pub(crate) sign_in: Option<SignIn>,
+    /// The identifiers of the `standard:` and `solana:` features parsed above
+    standard_identifiers: Vec<String>,
     /// Non-standard features
     extensions: Vec<String>,
+    /// `standard:` and `solana:` features not known to this crate
+    unknown_features: Vec<String>,
 }
 
 impl Features {
     /// Parse all the features from a wallet described as a [web_sys::wasm_bindgen::JsValue]
     pub(crate) fn parse(reflection: &Reflection) -> WalletResult<(Self, FeatureSupport)> {
-        let features_keys = reflection.object_to_vec_string("features")?;
-        let features_object = Reflection::new_from_str(reflection.get_inner(), "features")?;
-
         let mut features = Features::default();
         let mut supported_features = FeatureSupport::default();
 
-        features_keys.into_iter().try_for_each(|feature| {
-            let inner_object = features_object.reflect_inner(&feature)?;
-            let inner_object = Reflection::new(inner_object)?;
+        let features_entries = Self::entries(reflection)?;
+
+        features_entries.into_iter().try_for_each(|entry| {
+            let (feature, inner_object) = entry;
 
             if feature.starts_with("standard:") || feature.starts_with("solana:") {
+                // A feature added to the standard after this crate was released
+                // should not prevent using the wallet for the features it knows
+                if WalletFeature::from_identifier(&feature).is_none() {
+                    features.unknown_features.push(feature);
+
+                    return Ok(());
+                }
+
                 let version = SemverVersion::from_jsvalue(&inner_object)?;
+                features.standard_identifiers.push(feature.clone());
 
                 if feature == STANDARD_CONNECT_IDENTIFIER {
                     features.connect = Connect::new(&inner_object, version)?;
@@ -68,6 +84,11 @@ impl Features {
                 } else if feature == SOLANA_SIGN_TRANSACTION_IDENTIFIER {
                     features.sign_tx = SignTransaction::new_sign_tx(&inner_object, version)?;
                     supported_features.sign_tx = true;
+                } else if feature == SOLANA_SIGN_ALL_TRANSACTIONS_IDENTIFIER {
+                    features
+                        .sign_all_tx
+                        .replace(SignTransaction::new_sign_all_tx(&inner_object, version)?);
+                    supported_features.sign_all_tx = true;
                 } else if feature == SOLANA_SIGN_MESSAGE_IDENTIFIER {
                     features.sign_message = SignMessage::new(&inner_object, version)?;
                     supported_features.sign_message = true;
@@ -89,8 +110,138 @@ impl Features {
         Ok((features, supported_features))
     }
 
+    /// Get the identifier and the value of every feature. Most wallets expose `features`
+    /// as an object keyed by the feature identifier but some expose them as an array
+    /// of objects where each object contains the identifier in a `name` field
+    fn entries(reflection: &Reflection) -> WalletResult<Vec<(String, Reflection)>> {
+        let features_value = reflection.reflect_inner("features")?;
+
+        if Array::is_array(&features_value) {
+            Reflection::new(features_value)?
+                .into_array()?
+                .iter()
+                .map(|feature_value| {
+                    let inner_object = Reflection::new(feature_value)?;
+
+                    Ok((inner_object.string("name")?, inner_object))
+                })
+                .collect()
+        } else {
+            let features_object = Reflection::new_from_str(reflection.get_inner(), "features")?;
+
+            reflection
+                .object_to_vec_string("features")?
+                .into_iter()
+                .map(|feature| {
+                    let inner_object = Reflection::new(features_object.reflect_inner(&feature)?)?;
+
+                    Ok((feature, inner_object))
+                })
+                .collect()
+        }
+    }
+
     /// Get all extensions on the wallet
     pub fn extensions(&self) -> &[String] {
         &self.extensions
     }
+
+    /// Get the identifiers of all the features the wallet advertised, the standard features,
+    /// the [Self::unknown_features] and the [Self::extensions], sorted alphabetically.
+    /// Useful to include in a support or diagnostics report
+    pub fn all_identifiers(&self) -> Vec<String> {
+        let mut identifiers = self
+            .standard_identifiers
+            .iter()
+            .chain(self.unknown_features.iter())
+            .chain(self.extensions.iter())
+            .cloned()
+            .collect::<Vec<String>>();
+        identifiers.sort();
+
+        identifiers
+    }
+
+    /// Get the `standard:` and `solana:` features of the wallet that are not known
+    /// to this crate, for example features added to the wallet standard later.
+    /// These are skipped when parsing so that the rest of the wallet can still be used
+    pub fn unknown_features(&self) -> &[String] {
+        &self.unknown_features
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod features_shape_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Function;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn parse_array_shaped_features() {
+        let wallet = Function::new_no_args(
+            r#"
+            return {
+                features: [
+                    {
+                        name: "standard:connect",
+                        version: "1.0.0",
+                        connect: async () => ({ accounts: [] }),
+                    },
+                    {
+                        name: "solana:signMessage",
+                        version: "1.0.0",
+                        signMessage: async () => [],
+                    },
+                    {
+                        name: "foo:bar",
+                        version: "1.0.0",
+                    },
+                ],
+            };
+            "#,
+        )
+        .call0(&web_sys::wasm_bindgen::JsValue::null())
+        .unwrap();
+
+        let (features, supported_features) =
+            Features::parse(&Reflection::new(wallet).unwrap()).unwrap();
+
+        assert!(supported_features.connect);
+        assert!(supported_features.sign_message);
+        assert!(!supported_features.disconnect);
+        assert_eq!(features.extensions(), &["foo:bar".to_string()]);
+        assert!(features.unknown_features().is_empty());
+        assert_eq!(
+            features.all_identifiers(),
+            ["foo:bar", "solana:signMessage", "standard:connect"]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn unknown_standard_feature() {
+        let wallet = crate::test_support::TestWallet::new()
+            .add_feature(
+                "solana:signFuture",
+                &web_sys::js_sys::JSON::parse("{}").unwrap(),
+            )
+            .expose_accounts()
+            .build_object();
+
+        let (features, supported_features) =
+            Features::parse(&Reflection::new(wallet.clone()).unwrap()).unwrap();
+
+        assert!(supported_features.connect);
+        assert!(crate::Wallet::from_jsvalue(wallet).is_ok());
+        assert_eq!(
+            features.unknown_features(),
+            &["solana:signFuture".to_string()]
+        );
+        assert!(features.extensions().is_empty());
+        assert_eq!(
+            features.all_identifiers(),
+            ["solana:signFuture", "standard:connect", "standard:events"]
+        );
+    }
 }
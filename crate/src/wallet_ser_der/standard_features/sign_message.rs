@@ -1,14 +1,67 @@
 use ed25519_dalek::{Signature, VerifyingKey};
+use sha3::{Digest, Sha3_256};
 use wallet_adapter_common::WalletCommonUtils;
 use web_sys::{js_sys, wasm_bindgen::JsValue};
 
 use core::str;
+use std::borrow::Cow;
 
 use crate::{
-    InnerUtils, Reflection, SemverVersion, StandardFunction, WalletAccount, WalletError,
-    WalletResult,
+    InnerUtils, Reflection, ResponseInspector, SemverVersion, StandardFunction, WalletAccount,
+    WalletError, WalletResult,
 };
 
+/// The signing domain that starts every Solana off-chain message. The leading `0xff` byte
+/// ensures the message can never be a valid transaction
+pub(crate) const OFFCHAIN_SIGNING_DOMAIN: &[u8] = b"\xffsolana offchain";
+
+/// The maximum length of an off-chain message including the header that Ledger
+/// hardware wallets can sign, which is the maximum size of a transaction packet
+pub(crate) const OFFCHAIN_MAX_LEDGER_LEN: usize = 1232;
+
+/// Encode the `message` as a version `0` Solana off-chain message signed by the `signer`.
+/// The header consists of the [OFFCHAIN_SIGNING_DOMAIN], the header version, the
+/// application `domain` padded with zeroes to 32 bytes, the message format, the number of
+/// signers, the `signer` public key and the length of the message as a little-endian `u16`.
+/// The format is restricted ASCII or limited UTF-8 if the message can be signed by Ledger
+/// hardware wallets and extended otherwise
+pub(crate) fn offchain_message(
+    domain: &str,
+    signer: &[u8; 32],
+    message: &[u8],
+) -> WalletResult<Vec<u8>> {
+    if domain.len() > 32 {
+        return Err(WalletError::SigningDomainTooLong(domain.len()));
+    }
+    let message_len =
+        u16::try_from(message.len()).or(Err(WalletError::MessageTooLarge(message.len())))?;
+
+    let mut application_domain = [0u8; 32];
+    application_domain[..domain.len()].copy_from_slice(domain.as_bytes());
+
+    let header_len = OFFCHAIN_SIGNING_DOMAIN.len() + 1 + 32 + 1 + 1 + 32 + 2;
+    let fits_ledger = header_len + message.len() <= OFFCHAIN_MAX_LEDGER_LEN;
+    let format: u8 = if fits_ledger && message.iter().all(|byte| (0x20..=0x7e).contains(byte)) {
+        0
+    } else if fits_ledger && str::from_utf8(message).is_ok() {
+        1
+    } else {
+        2
+    };
+
+    let mut encoded = Vec::with_capacity(header_len + message.len());
+    encoded.extend_from_slice(OFFCHAIN_SIGNING_DOMAIN);
+    encoded.push(0);
+    encoded.extend_from_slice(&application_domain);
+    encoded.push(format);
+    encoded.push(1);
+    encoded.extend_from_slice(signer);
+    encoded.extend_from_slice(&message_len.to_le_bytes());
+    encoded.extend_from_slice(message);
+
+    Ok(encoded)
+}
+
 /// `solana:signMessage` containing the `version` and `callback` within
 /// the [StandardFunction] field
 #[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -30,6 +83,7 @@ impl SignMessage {
         &self,
         wallet_account: &WalletAccount,
         message: &'a [u8],
+        inspector: &ResponseInspector,
     ) -> WalletResult<SignedMessageOutput<'a>> {
         let message_value: js_sys::Uint8Array = message.into();
 
@@ -38,13 +92,13 @@ impl SignMessage {
         message_object.set_object(&"message".into(), &message_value)?;
 
         // Call the callback with message and account
-        let outcome = self
-            .0
-            .callback
-            .call1(&JsValue::null(), message_object.get_inner())?;
-
-        let outcome = js_sys::Promise::resolve(&outcome);
+        let outcome = InnerUtils::into_promise(
+            self.0
+                .callback
+                .call1(&JsValue::null(), message_object.get_inner()),
+        );
         let signed_message_result = wasm_bindgen_futures::JsFuture::from(outcome).await?;
+        inspector.inspect(&signed_message_result);
         let incase_of_error = Err(WalletError::InternalError(format!(
             "solana:signedMessage -> SignedMessageOutput: Casting `{signed_message_result:?}` did not yield a Uini8Array"
         )));
@@ -83,6 +137,7 @@ impl SignMessage {
 
             Ok(SignedMessageOutput {
                 message,
+                domain: None,
                 public_key: wallet_account.account.public_key,
                 signature: signature.to_bytes(),
             })
@@ -96,16 +151,70 @@ impl SignMessage {
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
 pub struct SignedMessageOutput<'a> {
     message: &'a [u8],
+    domain: Option<&'a str>,
     public_key: [u8; 32],
     signature: [u8; 64],
 }
 
-impl SignedMessageOutput<'_> {
-    /// Get the message as a [UTF-8 str](core::str)
+impl<'a> SignedMessageOutput<'a> {
+    /// Replace the signed off-chain message with the `message` without the header
+    /// and record the `domain` it was signed for, see [offchain_message]
+    pub(crate) fn with_domain<'b>(
+        self,
+        domain: &'b str,
+        message: &'b [u8],
+    ) -> SignedMessageOutput<'b> {
+        SignedMessageOutput {
+            message,
+            domain: Some(domain),
+            public_key: self.public_key,
+            signature: self.signature,
+        }
+    }
+
+    /// Get the domain the message was signed for if it was signed
+    /// with [crate::WalletAdapter::sign_message_domain_separated]
+    pub fn domain(&self) -> Option<&'a str> {
+        self.domain
+    }
+
+    /// Get the bytes the wallet signed, which is the message encoded as a Solana
+    /// off-chain message if the message was signed for a [Self::domain]
+    pub fn signed_bytes(&self) -> WalletResult<Cow<'a, [u8]>> {
+        match self.domain {
+            Some(domain) => Ok(Cow::Owned(offchain_message(
+                domain,
+                &self.public_key,
+                self.message,
+            )?)),
+            None => Ok(Cow::Borrowed(self.message)),
+        }
+    }
+
+    /// Verify the signature over the [Self::signed_bytes] using the public key
+    pub fn verify(&self) -> WalletResult<()> {
+        Ok(WalletCommonUtils::verify_signature(
+            self.public_key()?,
+            &self.signed_bytes()?,
+            self.signature(),
+        )?)
+    }
+
+    /// Get the message as a [UTF-8 str](core::str).
+    /// An empty str is returned if the message is not valid UTF-8, use [Self::message_bytes] instead
     pub fn message(&self) -> &str {
-        //Should never fail since verified message is always UTF-8 Format hence `.unwrap()` is used.
-        // This is verified to be the input message where the input message is always UTF-8 encoded
-        str::from_utf8(self.message).unwrap()
+        str::from_utf8(self.message).unwrap_or_default()
+    }
+
+    /// Get the message as bytes
+    pub fn message_bytes(&self) -> &'a [u8] {
+        self.message
+    }
+
+    /// Get the [Sha3_256] hash of the signed message bytes.
+    /// Useful as a compact key when logging or correlating a signature with the message
+    pub fn message_hash(&self) -> [u8; 32] {
+        Sha3_256::digest(self.message).into()
     }
 
     /// Get the public key as an [Ed25519 Public Key](VerifyingKey)
@@ -134,8 +243,135 @@ impl Default for SignedMessageOutput<'_> {
     fn default() -> Self {
         Self {
             message: &[],
+            domain: None,
             public_key: [0u8; 32],
             signature: [0u8; 64],
         }
     }
 }
+
+#[cfg(test)]
+mod signed_message_hash_checks {
+    use super::*;
+
+    #[test]
+    fn message_hash() {
+        let signed = SignedMessageOutput {
+            message: b"Hello Solana",
+            ..Default::default()
+        };
+
+        assert_eq!(signed.message_hash(), signed.message_hash());
+        assert_eq!(
+            signed.message_hash(),
+            <[u8; 32]>::from(Sha3_256::digest(b"Hello Solana"))
+        );
+
+        let other = SignedMessageOutput {
+            message: b"Hello Solana!",
+            ..signed
+        };
+        assert_ne!(signed.message_hash(), other.message_hash());
+    }
+
+    #[test]
+    fn base58_known_vectors() {
+        let signed = SignedMessageOutput {
+            message: b"Hello Solana",
+            domain: None,
+            public_key: [1u8; 32],
+            signature: [2u8; 64],
+        };
+
+        assert_eq!(
+            signed.base58_signature().unwrap(),
+            "3L3RY5sT8K4kyEnqhizwaqxLEbcYvpGrGPNEYRwtbCSUtL6YL86jdrvCbohnP5q8VxQ3qzGmt3W3iQJW97rD7m3"
+        );
+        assert_eq!(
+            signed.address().unwrap(),
+            "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi"
+        );
+        assert_eq!(
+            SignedMessageOutput::default().base58_signature().unwrap(),
+            "1".repeat(64)
+        );
+    }
+}
+
+#[cfg(test)]
+mod domain_separated_checks {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    use super::*;
+
+    #[test]
+    fn offchain_header() {
+        let encoded = offchain_message("foo.example", &[7u8; 32], b"Hello Solana").unwrap();
+
+        assert_eq!(&encoded[..16], b"\xffsolana offchain");
+        assert_eq!(encoded[16], 0);
+        assert_eq!(&encoded[17..28], b"foo.example");
+        assert_eq!(&encoded[28..49], &[0u8; 21]);
+        assert_eq!(encoded[49], 0);
+        assert_eq!(encoded[50], 1);
+        assert_eq!(&encoded[51..83], &[7u8; 32]);
+        assert_eq!(&encoded[83..85], &[12, 0]);
+        assert_eq!(&encoded[85..], b"Hello Solana");
+
+        assert_eq!(
+            Err(WalletError::SigningDomainTooLong(33)),
+            offchain_message(&"a".repeat(33), &[7u8; 32], b"")
+        );
+        assert_eq!(
+            Err(WalletError::MessageTooLarge(u16::MAX as usize + 1)),
+            offchain_message("", &[7u8; 32], &vec![b'a'; u16::MAX as usize + 1])
+        );
+    }
+
+    #[test]
+    fn offchain_format() {
+        let format = |message: &[u8]| offchain_message("", &[7u8; 32], message).unwrap()[49];
+
+        assert_eq!(format(b"Hello Solana"), 0);
+        assert_eq!(format("Hello Solana\n".as_bytes()), 1);
+        assert_eq!(format("Hello Sølana".as_bytes()), 1);
+        assert_eq!(format(&[b'a'; OFFCHAIN_MAX_LEDGER_LEN]), 2);
+        // Messages that are not valid UTF-8 are signed too
+        assert_eq!(format(&[0xff, 0xfe]), 2);
+    }
+
+    #[test]
+    fn verify_accounts_for_header() {
+        let signing_key = SigningKey::from_bytes(&[5u8; 32]);
+        let public_key = signing_key.verifying_key().to_bytes();
+        let message: &[u8] = &[0xff, 0xfe, b'a'];
+        let encoded = offchain_message("foo.example", &public_key, message).unwrap();
+
+        let signed = SignedMessageOutput {
+            message: &encoded,
+            domain: None,
+            public_key,
+            signature: signing_key.sign(&encoded).to_bytes(),
+        };
+        assert!(signed.verify().is_ok());
+
+        let separated = signed.with_domain("foo.example", message);
+        assert_eq!(separated.domain(), Some("foo.example"));
+        assert_eq!(separated.message_bytes(), message);
+        assert_eq!(separated.message(), "");
+        assert_eq!(
+            separated.signed_bytes().unwrap().as_ref(),
+            encoded.as_slice()
+        );
+        assert!(separated.verify().is_ok());
+
+        let replayed = SignedMessageOutput {
+            domain: None,
+            ..separated
+        };
+        assert!(replayed.verify().is_err());
+
+        let other_domain = signed.with_domain("bar.example", message);
+        assert!(other_domain.verify().is_err());
+    }
+}
@@ -7,7 +7,10 @@ use web_sys::{
 
 use core::hash::Hash;
 
-use crate::{Commitment, Reflection, SemverVersion, WalletAccount, WalletError, WalletResult};
+use crate::{
+    Commitment, InnerUtils, Reflection, ResponseInspector, SemverVersion, WalletAccount,
+    WalletError, WalletResult,
+};
 
 /// Used in `solana:SignTransaction` and `solana:SignAndSendTransaction`.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -54,6 +57,14 @@ impl SignTransaction {
         Self::new(reflection, version, "signTransaction")
     }
 
+    /// Parse a `solana:signAllTransactions` callback from the [JsValue]
+    pub(crate) fn new_sign_all_tx(
+        reflection: &Reflection,
+        version: SemverVersion,
+    ) -> WalletResult<Self> {
+        Self::new(reflection, version, "signAllTransactions")
+    }
+
     /// Parse a `solana:signAndSendTransaction` callback from the [JsValue]
     pub(crate) fn new_sign_and_send_tx(
         reflection: &Reflection,
@@ -92,27 +103,44 @@ impl SignTransaction {
         Ok((legacy, version_zero))
     }
 
+    /// Build the `{ account, transaction, chain }` input object passed to the callback
+    /// for each transaction. The `chain` key is omitted if no `cluster` is given
+    pub(crate) fn sign_tx_input(
+        wallet_account: &WalletAccount,
+        tx_bytes: &[u8],
+        cluster: Option<Cluster>,
+    ) -> WalletResult<JsValue> {
+        let tx_uint8array: js_sys::Uint8Array = tx_bytes.into();
+        let mut tx_object = Reflection::new_object();
+        tx_object.set_object(&"account".into(), &wallet_account.js_value)?;
+        tx_object.set_object(&"transaction".into(), &tx_uint8array)?;
+        if let Some(ref cluster) = cluster {
+            tx_object.set_object(&"chain".into(), &cluster.chain().into())?;
+        }
+
+        Ok(tx_object.take())
+    }
+
     pub(crate) async fn call_sign_multiple_tx(
         &self,
         wallet_account: &WalletAccount,
         transactions: &[impl AsRef<[u8]>],
         cluster: Option<Cluster>,
+        inspector: &ResponseInspector,
     ) -> WalletResult<Vec<Vec<u8>>> {
         let inputs_array = js_sys::Array::new();
         for tx_bytes in transactions {
-            let tx_uint8array: js_sys::Uint8Array = tx_bytes.as_ref().into();
-            let mut tx_object = Reflection::new_object();
-            tx_object.set_object(&"account".into(), &wallet_account.js_value)?;
-            tx_object.set_object(&"transaction".into(), &tx_uint8array)?;
-            if let Some(ref cluster) = cluster {
-                tx_object.set_object(&"chain".into(), &cluster.chain().into())?;
-            }
-            inputs_array.push(&tx_object.take());
+            inputs_array.push(&Self::sign_tx_input(
+                wallet_account,
+                tx_bytes.as_ref(),
+                cluster,
+            )?);
         }
 
-        let outcome = self.callback.apply(&JsValue::null(), &inputs_array)?;
-        let outcome = js_sys::Promise::resolve(&outcome);
+        let outcome =
+            InnerUtils::into_promise(self.callback.apply(&JsValue::null(), &inputs_array));
         let success = wasm_bindgen_futures::JsFuture::from(outcome).await?;
+        inspector.inspect(&success);
 
         let results_array = Reflection::new(success)?.into_array()?;
         let mut signed_txs = Vec::with_capacity(results_array.length() as usize);
@@ -129,6 +157,7 @@ impl SignTransaction {
         transaction_bytes: &[u8],
         cluster: Cluster,
         options: SendOptions,
+        inspector: &ResponseInspector,
     ) -> WalletResult<Signature> {
         let tx_bytes_value: js_sys::Uint8Array = transaction_bytes.into();
 
@@ -138,17 +167,17 @@ impl SignTransaction {
         tx_object.set_object(&"chain".into(), &cluster.chain().into())?;
         tx_object.set_object(&"options".into(), &options.to_object()?)?;
 
-        let outcome = self.callback.call1(&JsValue::null(), &tx_object.take())?;
-
-        let outcome = js_sys::Promise::resolve(&outcome);
+        let outcome =
+            InnerUtils::into_promise(self.callback.call1(&JsValue::null(), &tx_object.take()));
 
         let success = wasm_bindgen_futures::JsFuture::from(outcome).await?;
+        inspector.inspect(&success);
 
         Reflection::new(success)?
             .get_bytes_from_vec("signature")?
             .first()
             .map(|value| {
-                let bytes = WalletCommonUtils::to64byte_array(value)?;
+                let bytes = InnerUtils::signature_bytes(value)?;
                 Ok(WalletCommonUtils::signature(&bytes))
             })
             .ok_or(WalletError::SendAndSignTransactionSignatureEmpty)?
@@ -179,23 +208,200 @@ impl Hash for SignTransaction {
 /// - [preflight_commitment](Commitment)
 /// - [skip_preflight](bool)
 /// - [max_retries](u8)
-#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+/// - [memo](String)
+///
+/// The [confirm_commitment](Self::confirm_commitment) is used by
+/// [crate::WalletAdapter::sign_and_send_transaction_confirmed] and is not sent to the wallet
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
 pub struct SendOptions {
-    preflight_commitment: Commitment,
+    preflight_commitment: Option<Commitment>,
     skip_preflight: bool,
     max_retries: u8,
+    memo: Option<String>,
+    confirm_commitment: Option<Commitment>,
 }
 
 impl SendOptions {
+    /// The maximum size in bytes of a memo accepted on-chain
+    pub const MAX_MEMO_BYTES: usize = 566;
+
+    /// Set the commitment level used for the preflight simulation of the transaction
+    pub fn set_preflight_commitment(&mut self, commitment: Commitment) -> &mut Self {
+        self.preflight_commitment.replace(commitment);
+
+        self
+    }
+
+    /// Get the commitment level used for the preflight simulation of the transaction.
+    /// [None] if it was not set, in which case [crate::WalletAdapter::sign_and_send_transaction]
+    /// uses the [default commitment](crate::WalletAdapter::default_commitment)
+    /// and [Commitment::default] is sent otherwise
+    pub fn preflight_commitment(&self) -> Option<Commitment> {
+        self.preflight_commitment
+    }
+
+    /// Set the commitment level [crate::WalletAdapter::sign_and_send_transaction_confirmed]
+    /// waits for after sending the transaction, which may differ from the
+    /// [preflight commitment](Self::set_preflight_commitment).
+    /// It is not part of the options sent to the wallet
+    pub fn set_confirm_commitment(&mut self, commitment: Commitment) -> &mut Self {
+        self.confirm_commitment.replace(commitment);
+
+        self
+    }
+
+    /// Get the commitment level to wait for when confirming the sent transaction,
+    /// defaults to the [preflight commitment](Self::preflight_commitment) if not set
+    pub fn confirm_commitment(&self) -> Commitment {
+        self.confirm_commitment
+            .or(self.preflight_commitment)
+            .unwrap_or_default()
+    }
+
+    /// Set a memo to attach to the transaction, for example to tag deposits to an exchange.
+    /// Memos larger than [Self::MAX_MEMO_BYTES] return the error [WalletError::MemoTooLarge]
+    pub fn set_memo(&mut self, memo: &str) -> WalletResult<&mut Self> {
+        if memo.len() > Self::MAX_MEMO_BYTES {
+            return Err(WalletError::MemoTooLarge(memo.len()));
+        }
+
+        self.memo.replace(memo.to_string());
+
+        Ok(self)
+    }
+
+    /// Get the memo
+    pub fn memo(&self) -> Option<&str> {
+        self.memo.as_deref()
+    }
+
     /// Converts [SendOptions] to a [JsValue] which can be passed
     /// to the browser wallet when making requests.
     /// Internally, it is a [js_sys::Object]
     pub fn to_object(&self) -> WalletResult<JsValue> {
         let mut reflection = Reflection::new_object();
-        reflection.set_object_str("preflightCommitment", self.preflight_commitment.as_str())?;
+        reflection.set_object_str(
+            "preflightCommitment",
+            self.preflight_commitment.unwrap_or_default().as_str(),
+        )?;
         reflection.set_object(&"skipPreflight".into(), &JsValue::from(self.skip_preflight))?;
         reflection.set_object(&"maxRetries".into(), &JsValue::from(self.max_retries))?;
+        reflection.set_object_string_optional("memo", self.memo.as_ref())?;
 
         Ok(reflection.take())
     }
+
+    /// Check if the JS options object `value` has the same fields as [Self], for example
+    /// to verify that a wallet echoed back the options it received.
+    /// Missing keys are a mismatch, a `maxRetries` that is not a valid
+    /// unsigned integer returns a [WalletError::InvalidNumber] error
+    pub fn matches_object(&self, value: &JsValue) -> WalletResult<bool> {
+        let reflection = Reflection::new(value.clone())?;
+
+        let skip_preflight = js_sys::Reflect::get(reflection.get_inner(), &"skipPreflight".into())?;
+
+        Ok(reflection
+            .string_optional("preflightCommitment")?
+            .as_deref()
+            == Some(self.preflight_commitment.unwrap_or_default().as_str())
+            && skip_preflight.as_bool() == Some(self.skip_preflight)
+            && reflection.opt_u64("maxRetries")? == Some(self.max_retries as u64)
+            && reflection.string_optional("memo")? == self.memo)
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod send_options_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn memo() {
+        let mut send_options = SendOptions::default();
+
+        let object = Reflection::new(send_options.to_object().unwrap()).unwrap();
+        assert!(object.string_optional("memo").unwrap().is_none());
+
+        send_options.set_memo("deposit:42").unwrap();
+        let object = Reflection::new(send_options.to_object().unwrap()).unwrap();
+        assert_eq!(object.string("memo").unwrap(), "deposit:42");
+
+        let oversize = "a".repeat(SendOptions::MAX_MEMO_BYTES + 1);
+        assert_eq!(
+            Some(WalletError::MemoTooLarge(SendOptions::MAX_MEMO_BYTES + 1)),
+            send_options.set_memo(&oversize).err()
+        );
+        assert_eq!(send_options.memo(), Some("deposit:42"));
+
+        let max = "a".repeat(SendOptions::MAX_MEMO_BYTES);
+        assert!(send_options.set_memo(&max).is_ok());
+    }
+
+    #[wasm_bindgen_test]
+    fn confirm_commitment_not_sent() {
+        let mut send_options = SendOptions::default();
+        send_options.set_preflight_commitment(Commitment::Processed);
+        assert_eq!(send_options.confirm_commitment(), Commitment::Processed);
+
+        send_options.set_confirm_commitment(Commitment::Finalized);
+        assert_eq!(send_options.confirm_commitment(), Commitment::Finalized);
+        assert_eq!(
+            send_options.preflight_commitment(),
+            Some(Commitment::Processed)
+        );
+
+        let object = send_options.to_object().unwrap();
+        assert!(js_sys::Reflect::get(&object, &"confirmCommitment".into())
+            .unwrap()
+            .is_undefined());
+        assert_eq!(
+            Reflection::new(object)
+                .unwrap()
+                .string("preflightCommitment")
+                .unwrap(),
+            "processed"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn unset_preflight_commitment() {
+        let mut send_options = SendOptions::default();
+        assert_eq!(send_options.preflight_commitment(), None);
+        assert_eq!(send_options.confirm_commitment(), Commitment::Finalized);
+
+        let object = Reflection::new(send_options.to_object().unwrap()).unwrap();
+        assert_eq!(object.string("preflightCommitment").unwrap(), "finalized");
+
+        // An explicit default value is distinguished from an unset value
+        send_options.set_preflight_commitment(Commitment::default());
+        assert_eq!(
+            send_options.preflight_commitment(),
+            Some(Commitment::Finalized)
+        );
+        assert_ne!(send_options, SendOptions::default());
+    }
+
+    #[wasm_bindgen_test]
+    fn matches_object_round_trip() {
+        let mut send_options = SendOptions::default();
+        send_options
+            .set_preflight_commitment(Commitment::Processed)
+            .set_memo("deposit:42")
+            .unwrap();
+
+        let object = send_options.to_object().unwrap();
+        assert!(send_options.matches_object(&object).unwrap());
+        assert!(!SendOptions::default().matches_object(&object).unwrap());
+
+        let mut echoed = Reflection::new(object).unwrap();
+        echoed
+            .set_object(&"skipPreflight".into(), &JsValue::from(true))
+            .unwrap();
+        assert!(!send_options.matches_object(echoed.get_inner()).unwrap());
+
+        let empty = Reflection::new_object().take();
+        assert!(!send_options.matches_object(&empty).unwrap());
+    }
 }
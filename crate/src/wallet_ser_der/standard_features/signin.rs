@@ -1,9 +1,12 @@
-use wallet_adapter_common::{signin_standard::SignInOutput, WalletCommonUtils};
-use web_sys::{js_sys, wasm_bindgen::JsValue};
+use wallet_adapter_common::{
+    signin_standard::{SignInOutput, SiwsMessage},
+    WalletCommonUtils,
+};
+use web_sys::wasm_bindgen::JsValue;
 
 use crate::{
-    Reflection, SemverVersion, SigninInput, StandardFunction, WalletAccount, WalletError,
-    WalletResult,
+    InnerUtils, Reflection, ResponseInspector, SemverVersion, SigninInput, StandardFunction,
+    WalletAccount, WalletError, WalletResult,
 };
 
 /// A `solana:signin` struct containing the `version` and `callback`
@@ -23,15 +26,16 @@ impl SignIn {
         &self,
         signin_input: &SigninInput,
         public_key: [u8; 32],
+        inspector: &ResponseInspector,
     ) -> WalletResult<SignInOutput> {
-        let outcome = self
-            .0
-            .callback
-            .call1(&JsValue::null(), &signin_input.get_object()?)?;
-
-        let outcome = js_sys::Promise::resolve(&outcome);
+        let outcome = InnerUtils::into_promise(
+            self.0
+                .callback
+                .call1(&JsValue::null(), &signin_input.get_object()?),
+        );
 
         let value = wasm_bindgen_futures::JsFuture::from(outcome).await?;
+        inspector.inspect(&value);
         let output_array = Reflection::new(value)?.get_array()?;
 
         let first_index = Reflection::new(output_array.get(0))?;
@@ -50,16 +54,14 @@ impl SignIn {
         signin_input.0.check_eq(message)?;
 
         let signature_value = first_index.reflect_inner("signature")?;
-        let signature_bytes: [u8; 64] = Reflection::new(signature_value)?
-            .into_bytes()?
-            .try_into()
-            .or(Err(WalletError::InvalidEd25519SignatureBytes))?;
+        let signature_bytes =
+            InnerUtils::signature_bytes(&Reflection::new(signature_value)?.into_bytes()?)?;
 
         WalletCommonUtils::verify(&public_key, &message_bytes, &signature_bytes)?;
 
         Ok(SignInOutput {
             account: wallet_account.account,
-            message: message.to_string(),
+            message: SiwsMessage::new(message),
             signature: signature_bytes,
             public_key,
         })
@@ -1,36 +1,147 @@
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    sync::{atomic::AtomicUsize, Arc},
+};
 
 use async_channel::Receiver;
 use ed25519_dalek::Signature;
 use wallet_adapter_common::{
-    chains::ChainSupport, clusters::Cluster, signin_standard::SignInOutput, WalletData,
+    chains::ChainSupport,
+    clusters::Cluster,
+    feature_support::{WalletFeature, WalletOperation},
+    signin_standard::SignInOutput,
+    WalletData,
 };
-use web_sys::wasm_bindgen::JsValue;
+use web_sys::{js_sys::Object, wasm_bindgen::JsValue};
 
 use crate::{
-    ConnectionInfoInner, Features, Reflection, SemverVersion, SigninInput, WalletAccount,
-    WalletError, WalletEventSender, WalletIcon, WalletResult,
+    ConnectionInfoInner, Features, LogLevel, Logger, Reflection, ResponseInspector, SemverVersion,
+    SigninInput, WalletAccount, WalletError, WalletEventSender, WalletIcon, WalletName,
+    WalletResult,
 };
 
-use super::{SendOptions, SignedMessageOutput};
+use super::{SendOptions, SignTransaction, SignedMessageOutput};
 
-/// A wallet implementing wallet standard
-#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+/// A wallet implementing wallet standard.
+/// Wallets are compared by their data, accounts and features, the JS object
+/// they registered with and the key they are stored under are not compared
+#[derive(Debug, Clone, Default)]
 pub struct Wallet {
     pub(crate) data: WalletData,
     pub(crate) accounts: Vec<WalletAccount>,
     pub(crate) features: Features,
+    pub(crate) response_inspector: ResponseInspector,
+    pub(crate) logger: Logger,
+    pub(crate) js_object: WalletObject,
+    pub(crate) storage_key: Option<[u8; 32]>,
+}
+
+impl PartialEq for Wallet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Wallet {}
+
+impl PartialOrd for Wallet {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Wallet {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.data
+            .cmp(&other.data)
+            .then_with(|| self.accounts.cmp(&other.accounts))
+            .then_with(|| self.features.cmp(&other.features))
+    }
+}
+
+/// The JS object a [Wallet] registered itself with
+#[derive(Debug, Clone, Default)]
+pub(crate) struct WalletObject(pub(crate) Option<JsValue>);
+
+impl WalletObject {
+    /// Whether both are the same JS object, compared by identity using `Object.is`
+    /// so that two different wallets are told apart even if they report the same data
+    pub(crate) fn is_same(&self, other: &Self) -> bool {
+        match (self.0.as_ref(), other.0.as_ref()) {
+            (Some(object), Some(other_object)) => Object::is(object, other_object),
+            (None, None) => true,
+            _ => false,
+        }
+    }
 }
 
 impl Wallet {
     /// Send a request to connect to a browser wallet
     pub async fn connect(&self) -> WalletResult<WalletAccount> {
-        self.features.connect.call_connect().await
+        self.connect_with_options(false).await
+    }
+
+    /// Send a request to connect to a browser wallet. If `silent` is `true` the wallet
+    /// only connects if the dapp was previously authorized, without prompting the user,
+    /// and [WalletError::ConnectHasNoAccounts] is returned otherwise
+    pub async fn connect_with_options(&self, silent: bool) -> WalletResult<WalletAccount> {
+        Ok(self.connect_authorized(silent).await?.remove(0))
+    }
+
+    /// Send a request to connect to a browser wallet with the `silent` option and
+    /// return all the accounts the user authorized, the first being the active account
+    pub(crate) async fn connect_authorized(
+        &self,
+        silent: bool,
+    ) -> WalletResult<Vec<WalletAccount>> {
+        self.features
+            .connect
+            .call_connect(&self.response_inspector, silent)
+            .await
+    }
+
+    /// Send a request to connect to a browser wallet and return all the accounts
+    /// the user authorized instead of only the first one
+    pub async fn connect_accounts(&self) -> WalletResult<Vec<WalletAccount>> {
+        self.features
+            .connect
+            .call_connect_accounts(&self.response_inspector, false)
+            .await
     }
 
-    /// Send a request to the browser wallet to disconnect
+    /// Send a request to the browser wallet to disconnect.
+    /// If the wallet does not support `standard:disconnect` this is a no-op
+    /// that returns `Ok(())` since only the local state needs to be cleared.
+    /// Use [Self::disconnect_with_force] to get an error instead.
     pub async fn disconnect(&self) -> WalletResult<()> {
-        self.features.disconnect.call_disconnect().await
+        self.disconnect_with_force(false).await
+    }
+
+    /// Send a request to the browser wallet to disconnect.
+    /// If `force` is `true` and the wallet does not support `standard:disconnect`
+    /// the error [WalletError::MissingDisconnectFunction] is returned, otherwise
+    /// the missing feature is logged and `Ok(())` is returned.
+    pub async fn disconnect_with_force(&self, force: bool) -> WalletResult<()> {
+        if !self.standard_disconnect() {
+            if force {
+                return Err(WalletError::MissingDisconnectFunction);
+            }
+
+            self.logger.log(
+                LogLevel::Debug,
+                &format!(
+                    "`standard:disconnect` NOT SUPPORTED, ONLY CLEARING LOCAL STATE FOR: {}",
+                    self.name()
+                ),
+            );
+
+            return Ok(());
+        }
+
+        self.features
+            .disconnect
+            .call_disconnect(&self.response_inspector)
+            .await
     }
 
     /// Send a signin request to the browser wallet
@@ -40,7 +151,9 @@ impl Wallet {
         public_key: [u8; 32],
     ) -> WalletResult<SignInOutput> {
         if let Some(fn_exists) = self.features.sign_in.as_ref() {
-            fn_exists.call_signin(signin_input, public_key).await
+            fn_exists
+                .call_signin(signin_input, public_key, &self.response_inspector)
+                .await
         } else {
             Err(WalletError::MissingSignInFunction)
         }
@@ -55,7 +168,7 @@ impl Wallet {
     ) -> WalletResult<SignedMessageOutput<'a>> {
         self.features
             .sign_message
-            .call_sign_message(account, message)
+            .call_sign_message(account, message, &self.response_inspector)
             .await
     }
 
@@ -68,10 +181,39 @@ impl Wallet {
     ) -> WalletResult<Vec<Vec<u8>>> {
         self.features
             .sign_tx
-            .call_sign_multiple_tx(account, transactions, cluster)
+            .call_sign_multiple_tx(account, transactions, cluster, &self.response_inspector)
             .await
     }
 
+    /// Build the exact JS object that [Self::sign_transaction] passes to the wallet for the
+    /// `tx_bytes` without sending the request, for example to log it when debugging
+    /// a wallet that rejects the request
+    pub fn build_sign_tx_request(
+        &self,
+        account: &WalletAccount,
+        tx_bytes: &[u8],
+        cluster: Option<Cluster>,
+    ) -> WalletResult<JsValue> {
+        SignTransaction::sign_tx_input(account, tx_bytes, cluster)
+    }
+
+    /// Send a request to the browser wallet to sign all the transactions in a single approval
+    /// using the `solana:signAllTransactions` feature.
+    pub async fn sign_all_transactions(
+        &self,
+        transactions: &[impl AsRef<[u8]>],
+        cluster: Option<Cluster>,
+        account: &WalletAccount,
+    ) -> WalletResult<Vec<Vec<u8>>> {
+        if let Some(fn_exists) = self.features.sign_all_tx.as_ref() {
+            fn_exists
+                .call_sign_multiple_tx(account, transactions, cluster, &self.response_inspector)
+                .await
+        } else {
+            Err(WalletError::MissingSignAllTransactionsFunction)
+        }
+    }
+
     /// Send a sign and send transaction request to the browser wallet.
     pub async fn sign_and_send_transaction(
         &self,
@@ -82,21 +224,53 @@ impl Wallet {
     ) -> WalletResult<Signature> {
         self.features
             .sign_and_send_tx
-            .call_sign_and_send_transaction(account, transaction_bytes, cluster, options)
+            .call_sign_and_send_transaction(
+                account,
+                transaction_bytes,
+                cluster,
+                options,
+                &self.response_inspector,
+            )
             .await
     }
 
-    /// Get the standard events [Function](web_sys::js_sys::Function) `[standard:events].on`
+    pub(crate) fn set_response_inspector(&mut self, inspector: ResponseInspector) -> &mut Self {
+        self.response_inspector = inspector;
+
+        self
+    }
+
+    pub(crate) fn set_logger(&mut self, logger: Logger) -> &mut Self {
+        self.logger = logger;
+
+        self
+    }
+
+    /// Listen for `change` events with `[standard:events].on` until the `signal_receiver`
+    /// channel is closed. Wallets that do not support `standard:events` are skipped.
+    /// The `active_listeners` count is incremented while the listener is registered
     pub async fn call_on_event(
         &self,
         connection_info: ConnectionInfoInner,
         wallet_name: String,
         sender: WalletEventSender,
         signal_receiver: Receiver<()>,
+        active_listeners: Arc<AtomicUsize>,
     ) -> WalletResult<()> {
+        if !self.supports(WalletFeature::Events) {
+            return Ok(());
+        }
+
         self.features
             .events
-            .call_on_event(connection_info, wallet_name, sender, signal_receiver)
+            .call_on_event(
+                connection_info,
+                wallet_name,
+                sender,
+                signal_receiver,
+                active_listeners,
+                self.logger.clone(),
+            )
             .await
     }
 
@@ -128,6 +302,15 @@ impl Wallet {
         let name = reflection.string("name")?;
         let version = SemverVersion::parse(&reflection.string("version")?)?;
         let icon = WalletIcon::from_jsvalue(&reflection)?;
+        // Not part of the wallet standard, only some wallets advertise their homepage
+        let website = reflection.string_optional("website")?;
+        // Also not part of the wallet standard, an unrecognized chain is ignored
+        // since it only serves to pre-select a network
+        let current_cluster = match reflection.string_optional("defaultChain")? {
+            Some(chain) => Some(chain),
+            None => reflection.string_optional("currentChain")?,
+        }
+        .and_then(|chain| chain.parse::<Cluster>().ok());
         let accounts = Self::get_accounts(&reflection, "accounts")?;
         let (features, supported_features) = Features::parse(&reflection)?;
 
@@ -140,6 +323,8 @@ impl Wallet {
                     .set_patch(version.patch()),
             )
             .set_icon(icon.as_ref())
+            .set_website(website)
+            .set_current_cluster(current_cluster)
             .replace_accounts(
                 accounts
                     .iter()
@@ -154,6 +339,10 @@ impl Wallet {
             data,
             accounts,
             features,
+            response_inspector: ResponseInspector::default(),
+            logger: Logger::default(),
+            js_object: WalletObject(Some(reflection.take())),
+            storage_key: Option::default(),
         })
     }
 
@@ -168,6 +357,15 @@ impl Wallet {
             .collect::<WalletResult<Vec<WalletAccount>>>()
     }
 
+    /// Read the `accounts` the wallet currently exposes on its JavaScript object.
+    /// Unlike [Self::accounts], which are parsed when the wallet registers,
+    /// these reflect the accounts the wallet authorized since, without sending a request
+    pub(crate) fn current_accounts(&self) -> WalletResult<Vec<WalletAccount>> {
+        let js_object = self.js_object.0.clone().ok_or(WalletError::ValueNotFound)?;
+
+        Self::get_accounts(&Reflection::new(js_object)?, "accounts")
+    }
+
     /// Get the features of the wallet
     pub fn features(&self) -> &Features {
         &self.features
@@ -178,6 +376,15 @@ impl Wallet {
         &self.accounts
     }
 
+    /// Get the accounts that support a certain [WalletFeature].
+    /// An account may have been authorized for a narrower set of features than the wallet supports
+    pub fn accounts_with_feature(&self, feature: WalletFeature) -> Vec<&WalletAccount> {
+        self.accounts
+            .iter()
+            .filter(|wallet_account| wallet_account.supports(feature))
+            .collect()
+    }
+
     /// Get the chains supported by the wallet
     pub fn chains(&self) -> &[Cluster] {
         self.data.chains()
@@ -203,6 +410,37 @@ impl Wallet {
         self.data.localnet()
     }
 
+    /// Check whether the wallet supports a certain [WalletFeature]
+    pub fn supports(&self, feature: WalletFeature) -> bool {
+        self.data.supports(feature)
+    }
+
+    /// Check whether the wallet supports the feature with the `identifier`, either a
+    /// wallet standard feature like `solana:signIn` or a non-standard extension.
+    /// Useful when the required features are listed in a configuration
+    pub fn has_feature(&self, identifier: &str) -> bool {
+        match WalletFeature::from_identifier(identifier) {
+            Some(feature) => self.supports(feature),
+            None => self
+                .features
+                .extensions()
+                .iter()
+                .any(|extension| extension == identifier),
+        }
+    }
+
+    /// Get the [WalletFeature]s required by the [WalletOperation] that the wallet
+    /// does not support, for example to tell the user that the wallet does not support
+    /// signing multiple transactions at once. Empty if the operation is supported
+    pub fn missing_features_for(&self, operation: WalletOperation) -> Vec<WalletFeature> {
+        operation
+            .required_features()
+            .iter()
+            .filter(|feature| !self.supports(**feature))
+            .copied()
+            .collect()
+    }
+
     /// Check whether the wallet supports `standard:connect` feature
     pub fn standard_connect(&self) -> bool {
         self.data.standard_connect()
@@ -238,11 +476,48 @@ impl Wallet {
         self.data.solana_sign_transaction()
     }
 
+    /// Check whether the wallet supports `solana:signAllTransactions` feature
+    pub fn solana_sign_all_transactions(&self) -> bool {
+        self.data.solana_sign_all_transactions()
+    }
+
     /// Get the optional wallet icon
     pub fn icon(&self) -> Option<&Cow<'static, str>> {
         self.data.icon()
     }
 
+    /// Get the optional wallet icon as a [WalletIcon] to inspect its image type
+    pub fn wallet_icon(&self) -> Option<WalletIcon<'_>> {
+        self.icon().map(|icon| WalletIcon::new(icon))
+    }
+
+    /// Get the optional URL of the homepage of the wallet, for example to link to it
+    /// from a wallet picker. Most wallets do not advertise a website
+    pub fn website(&self) -> Option<&str> {
+        self.data.website()
+    }
+
+    /// Get the [Cluster] the wallet advertises as currently selected through a
+    /// `defaultChain` or `currentChain` field, so that a dapp can pre-select the
+    /// matching network. Returns [None] if the wallet advertises neither
+    pub fn current_cluster(&self) -> Option<Cluster> {
+        self.data.current_cluster()
+    }
+
+    /// A stable identifier of the wallet, for example to key a list of wallets in a UI framework.
+    /// This is the key the wallet is stored under in [crate::WalletStorage], so different wallets
+    /// that registered under the same name have different ids, see [WalletName::storage_key_at].
+    /// A wallet that is not stored uses the [WalletName::storage_key] of its name
+    pub fn id(&self) -> [u8; 32] {
+        self.storage_key
+            .unwrap_or_else(|| WalletName::new(self.name()).storage_key())
+    }
+
+    /// The [Self::id] as a lowercase hex string
+    pub fn id_hex(&self) -> String {
+        self.id().iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
     /// Get the name of the wallet
     pub fn name(&self) -> &str {
         self.data.name()
@@ -255,3 +530,413 @@ impl Wallet {
         SemverVersion(version.clone())
     }
 }
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod wallet_disconnect_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+    use crate::test_support::TestWallet;
+
+    #[wasm_bindgen_test]
+    async fn disconnect_without_standard_disconnect() {
+        let wallet = Wallet::default();
+
+        assert!(!wallet.standard_disconnect());
+        assert_eq!(Ok(()), wallet.disconnect().await);
+        assert_eq!(
+            Err(WalletError::MissingDisconnectFunction),
+            wallet.disconnect_with_force(true).await
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn adapter_clears_state_without_standard_disconnect() {
+        let mut adapter = crate::WalletAdapter::init().unwrap();
+        let events = adapter.events();
+        let wallet = TestWallet::new().build();
+        assert!(!wallet.standard_disconnect());
+
+        adapter.connect(wallet).await.unwrap();
+        assert!(matches!(
+            events.recv().await,
+            Ok(crate::WalletEvent::Connected(_))
+        ));
+
+        adapter.disconnect().await;
+
+        assert_eq!(Ok(crate::WalletEvent::Disconnected), events.recv().await);
+        assert!(!adapter.is_connected().await);
+        let connection_info = adapter.connection_info().await;
+        assert!(connection_info.connected_wallet().is_err());
+        assert!(connection_info.connected_account().is_err());
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod accounts_with_feature_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Function;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn accounts_with_feature() {
+        let wallet = Function::new_no_args(
+            r#"
+            const account = (address, fill, features) => ({
+                address,
+                publicKey: new Uint8Array(32).fill(fill),
+                chains: ["solana:devnet"],
+                features,
+            });
+
+            return {
+                name: "Feature Subset Wallet",
+                version: "1.0.0",
+                chains: ["solana:devnet"],
+                accounts: [
+                    account("4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi", 1, [
+                        "solana:signMessage",
+                        "solana:signTransaction",
+                    ]),
+                    account("8qbHbw2BbbTHBW1sbeqakYXVKRQM8Ne7pLK7m6CVfeR", 2, [
+                        "solana:signMessage",
+                    ]),
+                    account("CktRuQ2mttgRGkXJtyksdKHjUdc2C4TgDzyB98oEzy8", 3, [
+                        "solana:signTransaction",
+                    ]),
+                ],
+                features: {
+                    "standard:connect": {
+                        version: "1.0.0",
+                        connect: async () => ({ accounts: [] }),
+                    },
+                },
+            };
+            "#,
+        )
+        .call0(&JsValue::null())
+        .unwrap();
+        let wallet = Wallet::from_jsvalue(wallet).unwrap();
+
+        let public_keys = |feature: WalletFeature| {
+            wallet
+                .accounts_with_feature(feature)
+                .iter()
+                .map(|wallet_account| wallet_account.public_key()[0])
+                .collect::<Vec<u8>>()
+        };
+
+        assert_eq!(public_keys(WalletFeature::SignTransaction), vec![1, 3]);
+        assert_eq!(public_keys(WalletFeature::SignMessage), vec![1, 2]);
+        assert!(public_keys(WalletFeature::SignIn).is_empty());
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod missing_features_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Function;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn wallet_without_sign_all() {
+        let wallet = Function::new_no_args(
+            r#"
+            return {
+                name: "Single Sign Wallet",
+                version: "1.0.0",
+                chains: ["solana:devnet"],
+                accounts: [],
+                features: {
+                    "standard:connect": {
+                        version: "1.0.0",
+                        connect: async () => ({ accounts: [] }),
+                    },
+                    "solana:signTransaction": {
+                        version: "1.0.0",
+                        supportedTransactionVersions: ["legacy", 0],
+                        signTransaction: async () => [],
+                    },
+                },
+            };
+            "#,
+        )
+        .call0(&JsValue::null())
+        .unwrap();
+        let wallet = Wallet::from_jsvalue(wallet).unwrap();
+
+        assert!(wallet
+            .missing_features_for(WalletOperation::SignTransaction)
+            .is_empty());
+        assert!(wallet
+            .missing_features_for(WalletOperation::Connect)
+            .is_empty());
+        assert_eq!(
+            wallet.missing_features_for(WalletOperation::SignAll),
+            vec![WalletFeature::SignAllTransactions]
+        );
+        assert_eq!(
+            wallet.missing_features_for(WalletOperation::SignAndSend),
+            vec![WalletFeature::SignAndSendTransaction]
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod sign_tx_request_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::{Function, Uint8Array};
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn request_object() {
+        let wallet = Function::new_no_args(
+            r#"
+            return {
+                name: "Preview Wallet",
+                version: "1.0.0",
+                chains: ["solana:devnet"],
+                accounts: [{
+                    address: "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi",
+                    publicKey: new Uint8Array(32).fill(1),
+                    chains: ["solana:devnet"],
+                    features: ["solana:signTransaction"],
+                }],
+                features: {
+                    "standard:connect": {
+                        version: "1.0.0",
+                        connect: async () => ({ accounts: [] }),
+                    },
+                    "solana:signTransaction": {
+                        version: "1.0.0",
+                        supportedTransactionVersions: ["legacy", 0],
+                        signTransaction: () => {
+                            throw new Error("The callback must not be invoked");
+                        },
+                    },
+                },
+            };
+            "#,
+        )
+        .call0(&JsValue::null())
+        .unwrap();
+        let wallet = Wallet::from_jsvalue(wallet).unwrap();
+        let account = &wallet.accounts()[0];
+
+        let request = Reflection::new(
+            wallet
+                .build_sign_tx_request(account, &[1, 2, 3], Some(Cluster::DevNet))
+                .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(request.reflect_inner("account").unwrap(), account.js_value);
+        assert_eq!(
+            Uint8Array::new(&request.reflect_inner("transaction").unwrap()).to_vec(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(request.string("chain").unwrap(), "solana:devnet");
+
+        let request = Reflection::new(
+            wallet
+                .build_sign_tx_request(account, &[1, 2, 3], None)
+                .unwrap(),
+        )
+        .unwrap();
+        assert!(request.reflect_inner("account").is_ok());
+        assert!(request.reflect_inner("chain").is_err());
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod website_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Function;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn optional_website() {
+        let create_wallet = Function::new_with_args(
+            "website",
+            r#"
+            const wallet = {
+                name: "Website Wallet",
+                version: "1.0.0",
+                chains: ["solana:devnet"],
+                accounts: [],
+                features: {
+                    "standard:connect": {
+                        version: "1.0.0",
+                        connect: async () => ({ accounts: [] }),
+                    },
+                },
+            };
+
+            if (website !== undefined) {
+                wallet.website = website;
+            }
+
+            return wallet;
+            "#,
+        );
+
+        let with_website = create_wallet
+            .call1(&JsValue::null(), &"https://wallet.example".into())
+            .unwrap();
+        let wallet = Wallet::from_jsvalue(with_website).unwrap();
+        assert_eq!(wallet.website(), Some("https://wallet.example"));
+
+        let without_website = create_wallet
+            .call1(&JsValue::null(), &JsValue::undefined())
+            .unwrap();
+        let wallet = Wallet::from_jsvalue(without_website).unwrap();
+        assert_eq!(wallet.website(), None);
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod current_cluster_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Function;
+
+    use super::*;
+
+    fn wallet(key: &str, chain: &str) -> Wallet {
+        let wallet = Function::new_with_args(
+            "key, chain",
+            r#"
+            const wallet = {
+                name: "Cluster Wallet",
+                version: "1.0.0",
+                chains: ["solana:mainnet", "solana:devnet"],
+                accounts: [],
+                features: {},
+            };
+
+            if (key) {
+                wallet[key] = chain;
+            }
+
+            return wallet;
+            "#,
+        )
+        .call2(&JsValue::null(), &key.into(), &chain.into())
+        .unwrap();
+
+        Wallet::from_jsvalue(wallet).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    fn advertised_chain() {
+        assert_eq!(
+            wallet("currentChain", "solana:devnet").current_cluster(),
+            Some(Cluster::DevNet)
+        );
+        assert_eq!(
+            wallet("defaultChain", "solana:mainnet").current_cluster(),
+            Some(Cluster::MainNet)
+        );
+        assert_eq!(wallet("", "").current_cluster(), None);
+        assert_eq!(wallet("currentChain", "ethereum:1").current_cluster(), None);
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod has_feature_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::JSON;
+
+    use crate::test_support::TestWallet;
+
+    #[wasm_bindgen_test]
+    fn standard_and_extension() {
+        let wallet = TestWallet::new()
+            .add_feature(
+                "foo:bar",
+                &JSON::parse(r#"{ "version": "1.0.0" }"#).unwrap(),
+            )
+            .expose_accounts()
+            .build();
+
+        assert!(wallet.has_feature("standard:connect"));
+        assert!(!wallet.has_feature("solana:signIn"));
+        assert!(wallet.has_feature("foo:bar"));
+        assert!(!wallet.has_feature("foo:baz"));
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod wallet_id_checks {
+    use wallet_adapter_common::WalletData;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+    use crate::WalletStorage;
+
+    #[wasm_bindgen_test]
+    fn id_is_storage_key() {
+        let wallet = Wallet {
+            data: WalletData::new().set_name("Foo Wallet"),
+            ..Default::default()
+        };
+        assert_eq!(wallet.id(), WalletName::new("foo wallet").storage_key());
+        assert_eq!(wallet.id_hex().len(), 64);
+        assert!(wallet
+            .id_hex()
+            .starts_with(&format!("{:02x}", wallet.id()[0])));
+
+        let storage = WalletStorage::default();
+        WalletStorage::register(&storage.clone_inner(), wallet.clone());
+
+        let stored = storage.get_wallet("Foo Wallet").unwrap();
+        assert_eq!(stored.id(), wallet.id());
+        assert!(storage.clone_inner().borrow().get(&wallet.id()) == Some(&stored));
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod wallet_eq_checks {
+    use wallet_adapter_common::WalletData;
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Object;
+
+    use super::*;
+
+    fn wallet(name: &str) -> Wallet {
+        Wallet {
+            data: WalletData::new().set_name(name),
+            js_object: WalletObject(Some(Object::new().into())),
+            ..Default::default()
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn js_object_and_storage_key_are_not_compared() {
+        let first = wallet("Foo Wallet");
+        let mut second = wallet("Foo Wallet");
+        second.storage_key = Some(WalletName::new("Foo Wallet").storage_key_at(1));
+
+        assert_eq!(first, second);
+        assert_eq!(first.cmp(&second), std::cmp::Ordering::Equal);
+        assert!(!first.js_object.is_same(&second.js_object));
+        assert!(first.js_object.is_same(&first.clone().js_object));
+
+        let other = wallet("Bar Wallet");
+        assert_ne!(first, other);
+        assert_eq!(first.cmp(&other), other.cmp(&first).reverse());
+    }
+}
@@ -3,11 +3,12 @@ use std::borrow::Cow;
 use wallet_adapter_common::{
     chains::ChainSupport,
     clusters::Cluster,
-    feature_support::FeatureSupport,
+    feature_support::{FeatureSupport, WalletFeature},
     standardized_events::{
-        SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER, SOLANA_SIGN_IN_IDENTIFIER,
-        SOLANA_SIGN_MESSAGE_IDENTIFIER, SOLANA_SIGN_TRANSACTION_IDENTIFIER,
-        STANDARD_CONNECT_IDENTIFIER, STANDARD_DISCONNECT_IDENTIFIER, STANDARD_EVENTS_IDENTIFIER,
+        SOLANA_SIGN_ALL_TRANSACTIONS_IDENTIFIER, SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER,
+        SOLANA_SIGN_IN_IDENTIFIER, SOLANA_SIGN_MESSAGE_IDENTIFIER,
+        SOLANA_SIGN_TRANSACTION_IDENTIFIER, STANDARD_CONNECT_IDENTIFIER,
+        STANDARD_DISCONNECT_IDENTIFIER, STANDARD_EVENTS_IDENTIFIER,
     },
     WalletAccountData, WalletCommonUtils,
 };
@@ -61,6 +62,16 @@ impl WalletAccount {
         self.account.icon.as_ref()
     }
 
+    /// Get a wallet specific string field of the account that is not part of the
+    /// wallet standard, for example a `derivationPath` attached by a hardware wallet.
+    /// Returns [None] if the field is missing or is not a string
+    pub fn metadata(&self, key: &str) -> Option<String> {
+        Reflection::new(self.js_value.clone())
+            .and_then(|reflection| reflection.string_optional(key))
+            .ok()
+            .flatten()
+    }
+
     /// Get the shortened address of the `Base58 address` .
     /// It displays the first 4 characters and the last for characters
     /// separated by ellipsis eg `FXdl...RGd4` .
@@ -97,7 +108,7 @@ impl WalletAccount {
     /// Parse A [WalletAccount] from [JsValue]
     pub(crate) fn parse(reflection: Reflection) -> WalletResult<Self> {
         let address = reflection.string("address")?;
-        let public_key = reflection.byte32array("publicKey")?;
+        let public_key = reflection.byte32array_or_base58("publicKey")?;
         let chains = reflection.vec_string_accept_undefined("chains")?;
         let features = reflection.vec_string_accept_undefined("features")?;
 
@@ -121,7 +132,7 @@ impl WalletAccount {
 
         let mut supported_features = FeatureSupport::default();
 
-        features.iter().try_for_each(|feature| {
+        features.iter().for_each(|feature| {
             if feature.as_str() == STANDARD_CONNECT_IDENTIFIER {
                 supported_features.connect = true;
             } else if feature.as_str() == STANDARD_DISCONNECT_IDENTIFIER {
@@ -134,14 +145,14 @@ impl WalletAccount {
                 supported_features.sign_and_send_tx = true;
             } else if feature.as_str() == SOLANA_SIGN_TRANSACTION_IDENTIFIER {
                 supported_features.sign_tx = true;
+            } else if feature.as_str() == SOLANA_SIGN_ALL_TRANSACTIONS_IDENTIFIER {
+                supported_features.sign_all_tx = true;
             } else if feature.as_str() == SOLANA_SIGN_MESSAGE_IDENTIFIER {
                 supported_features.sign_message = true;
-            } else {
-                return Err(WalletError::UnsupportedWalletFeature(feature.to_owned()));
             }
-
-            Ok(())
-        })?;
+            // Other features, for example extensions or features added to the
+            // standard later, are kept in `features` without a support flag
+        });
 
         let icon = WalletIcon::from_jsvalue(&reflection)?;
 
@@ -192,6 +203,11 @@ impl WalletAccount {
         self.account.supported_chains.localnet
     }
 
+    /// Check whether the account supports a certain [WalletFeature]
+    pub fn supports(&self, feature: WalletFeature) -> bool {
+        self.account.supported_features.supports(feature)
+    }
+
     /// Checks if `standard:connect` is supported
     pub fn standard_connect(&self) -> bool {
         self.account.supported_features.connect
@@ -226,6 +242,46 @@ impl WalletAccount {
     pub fn solana_sign_transaction(&self) -> bool {
         self.account.supported_features.sign_tx
     }
+
+    /// Checks if `solana:signAllTransactions` is supported
+    pub fn solana_sign_all_transactions(&self) -> bool {
+        self.account.supported_features.sign_all_tx
+    }
+}
+
+impl WalletAccount {
+    /// Convert to a [PortableAccount] which does not contain a [JsValue]
+    /// and can therefore be persisted, for example, as a hint to reconnect the account.
+    /// Use [crate::WalletAdapter::restore_account] to get the [WalletAccount] back.
+    pub fn to_portable(&self) -> PortableAccount {
+        PortableAccount {
+            address: self.account.address.clone(),
+            public_key: self.account.public_key,
+            chains: self.account.chains.clone(),
+            features: self.account.features.clone(),
+            label: self.account.label.clone(),
+            icon: self.account.icon.clone(),
+        }
+    }
+}
+
+/// The details of a [WalletAccount] without the [JsValue] used to make requests to the browser wallet.
+/// Enable the `serde` feature to serialize and deserialize this struct.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PortableAccount {
+    /// Address of the account, corresponding with a public key.
+    pub address: String,
+    /// Public key of the account, corresponding with a secret key to use.
+    pub public_key: [u8; 32],
+    /// Chains supported by the account.
+    pub chains: Vec<String>,
+    /// Feature names supported by the account.
+    pub features: Vec<String>,
+    /// Optional user-friendly descriptive label or name for the account.
+    pub label: Option<String>,
+    /// An optional [Wallet Icon](String)
+    pub icon: Option<String>,
 }
 
 impl core::fmt::Debug for WalletAccount {
@@ -289,3 +345,113 @@ impl<'a> From<&'a WalletAccount> for InnerWalletAccount<'a> {
         }
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "serde")]
+mod portable_account_serde_checks {
+    use super::*;
+
+    #[test]
+    fn serde_round_trip() {
+        let portable = PortableAccount {
+            address: "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi".to_string(),
+            public_key: [1u8; 32],
+            chains: vec!["solana:devnet".to_string()],
+            features: vec!["solana:signMessage".to_string()],
+            label: Some("Main".to_string()),
+            icon: None,
+        };
+
+        let serialized = serde_json::to_string(&portable).unwrap();
+
+        assert_eq!(
+            portable,
+            serde_json::from_str::<PortableAccount>(&serialized).unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod account_metadata_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Function;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn derivation_path() {
+        let account = Function::new_no_args(
+            r#"
+            return {
+                address: "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi",
+                publicKey: new Uint8Array(32).fill(1),
+                chains: ["solana:devnet"],
+                features: ["solana:signMessage"],
+                derivationPath: "m/44'/501'/0'/0'",
+                isLedger: true,
+            };
+            "#,
+        )
+        .call0(&JsValue::null())
+        .unwrap();
+        let account = WalletAccount::parse(Reflection::new(account).unwrap()).unwrap();
+
+        assert_eq!(
+            account.metadata("derivationPath").as_deref(),
+            Some("m/44'/501'/0'/0'")
+        );
+        assert!(account.metadata("isLedger").is_none());
+        assert!(account.metadata("missing").is_none());
+        assert!(WalletAccount::default()
+            .metadata("derivationPath")
+            .is_none());
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_arch = "wasm32")]
+mod public_key_encoding_checks {
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use web_sys::js_sys::Function;
+
+    use super::*;
+
+    fn parse(public_key: &str) -> WalletResult<WalletAccount> {
+        let account = Function::new_with_args(
+            "publicKey",
+            r#"
+            return {
+                address: "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi",
+                publicKey: eval(publicKey),
+                chains: ["solana:devnet"],
+                features: ["solana:signMessage"],
+            };
+            "#,
+        )
+        .call1(&JsValue::null(), &public_key.into())
+        .unwrap();
+
+        WalletAccount::parse(Reflection::new(account).unwrap())
+    }
+
+    #[wasm_bindgen_test]
+    fn bytes_or_base58() {
+        let from_bytes = parse("new Uint8Array(32).fill(1)").unwrap();
+        let from_base58 = parse(r#""4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi""#).unwrap();
+
+        assert_eq!(from_bytes.public_key(), [1u8; 32]);
+        assert_eq!(from_bytes.public_key(), from_base58.public_key());
+
+        assert_eq!(
+            parse(r#""11111111111111111111111111111111""#)
+                .unwrap()
+                .public_key(),
+            [0u8; 32]
+        );
+        assert_eq!(
+            parse(r#""0OIl""#).err(),
+            Some(WalletError::InvalidBase58Address)
+        );
+    }
+}
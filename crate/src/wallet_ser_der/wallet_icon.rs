@@ -1,9 +1,67 @@
 use crate::{Reflection, WalletError, WalletResult};
 
+/// The image types allowed in the data URI of a wallet icon by the wallet standard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ImageMime {
+    /// `image/svg+xml`
+    Svg,
+    /// `image/webp`
+    Webp,
+    /// `image/png`
+    Png,
+    /// `image/gif`
+    Gif,
+}
+
+impl ImageMime {
+    /// Get the MIME type as a `&str`
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Svg => "image/svg+xml",
+            Self::Webp => "image/webp",
+            Self::Png => "image/png",
+            Self::Gif => "image/gif",
+        }
+    }
+
+    /// Parse a MIME type, ignoring ASCII case
+    pub fn from_mime(mime: &str) -> Option<Self> {
+        [Self::Svg, Self::Webp, Self::Png, Self::Gif]
+            .into_iter()
+            .find(|image_mime| image_mime.as_str().eq_ignore_ascii_case(mime.trim()))
+    }
+}
+
 /// A data URI containing a base64-encoded SVG, WebP, PNG, or GIF image.
-pub(crate) struct WalletIcon;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WalletIcon<'a>(&'a str);
+
+impl<'a> WalletIcon<'a> {
+    /// Wrap the data URI of an icon
+    pub fn new(data_uri: &'a str) -> Self {
+        Self(data_uri)
+    }
+
+    /// Get the data URI
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+
+    /// Get the [ImageMime] of the data URI without decoding the image.
+    /// Returns [None] if the icon is not a data URI or the image type is not supported
+    pub fn mime(&self) -> Option<ImageMime> {
+        let (media_type, _data) = self.0.strip_prefix("data:")?.split_once(',')?;
+
+        ImageMime::from_mime(media_type.split(';').next()?)
+    }
+
+    /// Check whether the icon is an SVG image. SVG images can contain scripts
+    /// so they should not be inlined into the page without sanitizing them,
+    /// render them using the `src` attribute of an `<img>` element instead
+    pub fn is_svg(&self) -> bool {
+        self.mime() == Some(ImageMime::Svg)
+    }
 
-impl WalletIcon {
     /// Parse the wallet from a [web_sys::wasm_bindgen::JsValue]
     pub(crate) fn from_jsvalue(reflection: &Reflection) -> WalletResult<Option<String>> {
         let icon = match reflection.string_optional("icon") {
@@ -19,3 +77,29 @@ impl WalletIcon {
         Ok(icon)
     }
 }
+
+#[cfg(test)]
+mod wallet_icon_mime_checks {
+    use super::*;
+
+    #[test]
+    fn data_uri_mime() {
+        let png = WalletIcon::new("data:image/png;base64,iVBORw0KGgo=");
+        assert_eq!(png.mime(), Some(ImageMime::Png));
+        assert!(!png.is_svg());
+
+        let svg = WalletIcon::new("data:image/svg+xml;base64,PHN2Zz48L3N2Zz4=");
+        assert_eq!(svg.mime(), Some(ImageMime::Svg));
+        assert!(svg.is_svg());
+
+        let svg_utf8 = WalletIcon::new("data:Image/SVG+XML;charset=utf-8,<svg></svg>");
+        assert!(svg_utf8.is_svg());
+
+        assert_eq!(
+            WalletIcon::new("data:text/html;base64,PHNjcmlwdD4=").mime(),
+            None
+        );
+        assert_eq!(WalletIcon::new("https://foo.example/icon.svg").mime(), None);
+        assert_eq!(WalletIcon::new("data:image/png").mime(), None);
+    }
+}
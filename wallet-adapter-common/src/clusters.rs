@@ -1,3 +1,5 @@
+use crate::WalletUtilsError;
+
 /// Solana Mainnet cluster
 pub const MAINNET_ENDPOINT: &str = "https://api.mainnet-beta.solana.com";
 /// Solana Devnet cluster
@@ -7,6 +9,15 @@ pub const TESTNET_ENDPOINT: &str = "https://api.testnet.solana.com";
 /// Solana Localnet cluster
 pub const LOCALNET_ENDPOINT: &str = "http://localhost:8899";
 
+/// Solana Mainnet cluster websocket endpoint
+pub const MAINNET_WS_ENDPOINT: &str = "wss://api.mainnet-beta.solana.com";
+/// Solana Devnet cluster websocket endpoint
+pub const DEVNET_WS_ENDPOINT: &str = "wss://api.devnet.solana.com";
+/// Solana Testnet cluster websocket endpoint
+pub const TESTNET_WS_ENDPOINT: &str = "wss://api.testnet.solana.com";
+/// Solana Localnet cluster websocket endpoint, served on the port after the RPC port
+pub const LOCALNET_WS_ENDPOINT: &str = "ws://localhost:8900";
+
 /// Solana Mainnet cluster,  [https://api.mainnet-beta.solana.com](https://api.mainnet-beta.solana.com)
 pub const MAINNET_IDENTIFIER: &str = "solana:mainnet";
 /// Solana Devnet cluster, e.g. [https://api.devnet.solana.com](https://api.devnet.solana.com)
@@ -25,6 +36,21 @@ pub const TESTNET: &str = "testnet";
 /// Solana Localnet cluster, e.g. [http://localhost:8899](http://localhost:8899)
 pub const LOCALNET: &str = "localnet";
 
+/// The genesis hash of Solana Mainnet cluster
+pub const MAINNET_GENESIS_HASH: &str = "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d";
+/// The genesis hash of Solana Devnet cluster
+pub const DEVNET_GENESIS_HASH: &str = "EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG";
+/// The genesis hash of Solana Testnet cluster
+pub const TESTNET_GENESIS_HASH: &str = "4uhcVJyU9pJkvQyS88uRDiswHXSCkY3zQawwpjk2NsNY";
+
+/// The known genesis hashes of the public clusters. Localnet is not included
+/// since each local validator creates its own genesis block
+pub const KNOWN_GENESIS_HASHES: [(Cluster, &str); 3] = [
+    (Cluster::MainNet, MAINNET_GENESIS_HASH),
+    (Cluster::DevNet, DEVNET_GENESIS_HASH),
+    (Cluster::TestNet, TESTNET_GENESIS_HASH),
+];
+
 /// Solana Clusters
 #[derive(Debug, PartialEq, Eq, Default, PartialOrd, Ord, Clone, Copy, Hash)]
 pub enum Cluster {
@@ -50,6 +76,16 @@ impl Cluster {
         }
     }
 
+    /// A Solana websocket endpoint URI used for subscriptions
+    pub fn ws_endpoint(&self) -> &str {
+        match self {
+            Cluster::MainNet => MAINNET_WS_ENDPOINT,
+            Cluster::DevNet => DEVNET_WS_ENDPOINT,
+            Cluster::TestNet => TESTNET_WS_ENDPOINT,
+            Cluster::LocalNet => LOCALNET_WS_ENDPOINT,
+        }
+    }
+
     /// A Solana cluster identifier
     pub fn chain(&self) -> &str {
         match self {
@@ -60,6 +96,15 @@ impl Cluster {
         }
     }
 
+    /// The known genesis hash of the cluster encoded as a base58 string.
+    /// Returns [None] for [Cluster::LocalNet]
+    pub fn genesis_hash(&self) -> Option<&'static str> {
+        KNOWN_GENESIS_HASHES
+            .iter()
+            .find(|(cluster, _)| cluster == self)
+            .map(|(_, genesis_hash)| *genesis_hash)
+    }
+
     /// A Solana cluster identifier as a &str
     pub fn display(&self) -> &str {
         match self {
@@ -77,6 +122,22 @@ impl core::fmt::Display for Cluster {
     }
 }
 
+impl core::str::FromStr for Cluster {
+    type Err = WalletUtilsError;
+
+    /// Strictly parse a chain identifier, endpoint or cluster name,
+    /// unlike the `From<&str>` conversion which falls back to [Cluster::DevNet]
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            MAINNET_IDENTIFIER | MAINNET_ENDPOINT | MAINNET => Ok(Self::MainNet),
+            DEVNET_IDENTIFIER | DEVNET_ENDPOINT | DEVNET => Ok(Self::DevNet),
+            TESTNET_IDENTIFIER | TESTNET_ENDPOINT | TESTNET => Ok(Self::TestNet),
+            LOCALNET_IDENTIFIER | LOCALNET_ENDPOINT | LOCALNET => Ok(Self::LocalNet),
+            _ => Err(WalletUtilsError::UnsupportedChain(value.to_string())),
+        }
+    }
+}
+
 impl From<&str> for Cluster {
     fn from(value: &str) -> Self {
         match value {
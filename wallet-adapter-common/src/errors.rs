@@ -25,9 +25,15 @@ pub enum WalletUtilsError {
     ///Expected a timestamp in the format specified by ISO8601
     #[error("Invalid ISO 8601 timestamp `{0}. Only timestamps in the format specified by ISO8601 are supported.")]
     InvalidISO8601Timestamp(String),
+    /// The SIWS resource is not a URI with a scheme
+    #[error("The resource `{0}` is not a URI with a scheme like `https://example.com`")]
+    InvalidResourceUri(String),
     /// Invalid Base58 Address
     #[error("Invalid Base58 Address")]
     InvalidBase58Address,
+    /// The decoded public key is not 32 bytes in length
+    #[error("The public key length is expected to be 32 bytes but encountered public key of a length `{0}` bytes")]
+    InvalidEd25519PublicKeyLen(u8),
     /// The bytes provided for the Ed25519 Public Key are invalid
     #[error("The bytes provided for the Ed25519 Public Key are invalid")]
     InvalidEd25519PublicKeyBytes,
@@ -43,7 +49,22 @@ pub enum WalletUtilsError {
     /// The nonce is required to be at least 8 characters long
     #[error("The nonce is required to be at least 8 characters long")]
     NonceMustBeAtLeast8Characters,
+    /// The nonce is required to only contain alphanumeric characters
+    #[error("The nonce is required to only contain alphanumeric characters")]
+    NonceMustBeAlphanumeric,
     /// The message signed by the wallet is not the same as the message sent to the wallet for signing
     #[error("The message signed by the wallet is not the same as the message sent to the wallet for signing")]
     MessageResponseMismatch,
+    /// The chain is not a recognized Solana cluster
+    #[error("The chain `{0}` is not a recognized Solana cluster")]
+    UnsupportedChain(String),
+    /// The address of a verification payload is not the Base58 encoded public key
+    #[error("The address `{0}` is not the Base58 encoded public key of the verification payload")]
+    AddressPublicKeyMismatch(String),
+    /// None of the bump seeds yielded a program derived address that is off the Ed25519 curve
+    #[error("No bump seed yielded a valid program derived address")]
+    ProgramAddressNotFound,
+    /// The string is not a Base58 encoded signature of 64 bytes
+    #[error("The string `{0}` is not a Base58 encoded signature of 64 bytes")]
+    InvalidBase58Signature(String),
 }
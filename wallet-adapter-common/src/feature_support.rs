@@ -1,3 +1,9 @@
+use crate::standardized_events::{
+    SOLANA_SIGN_ALL_TRANSACTIONS_IDENTIFIER, SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER,
+    SOLANA_SIGN_IN_IDENTIFIER, SOLANA_SIGN_MESSAGE_IDENTIFIER, SOLANA_SIGN_TRANSACTION_IDENTIFIER,
+    STANDARD_CONNECT_IDENTIFIER, STANDARD_DISCONNECT_IDENTIFIER, STANDARD_EVENTS_IDENTIFIER,
+};
+
 /// Used as a helper struct to contain all the features supported by a wallet
 /// as defined by the wallet standard
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
@@ -16,4 +22,116 @@ pub struct FeatureSupport {
     pub sign_and_send_tx: bool,
     /// 'solana:signTransaction'
     pub sign_tx: bool,
+    /// 'solana:signAllTransactions'
+    pub sign_all_tx: bool,
+}
+
+impl FeatureSupport {
+    /// Check whether a certain [WalletFeature] is supported
+    pub fn supports(&self, feature: WalletFeature) -> bool {
+        match feature {
+            WalletFeature::Connect => self.connect,
+            WalletFeature::Disconnect => self.disconnect,
+            WalletFeature::Events => self.events,
+            WalletFeature::SignIn => self.sign_in,
+            WalletFeature::SignMessage => self.sign_message,
+            WalletFeature::SignAndSendTransaction => self.sign_and_send_tx,
+            WalletFeature::SignTransaction => self.sign_tx,
+            WalletFeature::SignAllTransactions => self.sign_all_tx,
+        }
+    }
+}
+
+/// The features defined by the wallet standard
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum WalletFeature {
+    /// 'standard:connect'
+    Connect,
+    /// 'standard:disconnect'
+    Disconnect,
+    /// 'standard:events'
+    Events,
+    /// 'solana:signIn'
+    SignIn,
+    /// 'solana:signMessage'
+    SignMessage,
+    /// 'solana:signAndSendTransaction'
+    SignAndSendTransaction,
+    /// 'solana:signTransaction'
+    SignTransaction,
+    /// 'solana:signAllTransactions'
+    SignAllTransactions,
+}
+
+impl WalletFeature {
+    /// Get the wallet standard identifier of the feature, eg. `standard:connect`
+    pub fn identifier(&self) -> &str {
+        match self {
+            Self::Connect => STANDARD_CONNECT_IDENTIFIER,
+            Self::Disconnect => STANDARD_DISCONNECT_IDENTIFIER,
+            Self::Events => STANDARD_EVENTS_IDENTIFIER,
+            Self::SignIn => SOLANA_SIGN_IN_IDENTIFIER,
+            Self::SignMessage => SOLANA_SIGN_MESSAGE_IDENTIFIER,
+            Self::SignAndSendTransaction => SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER,
+            Self::SignTransaction => SOLANA_SIGN_TRANSACTION_IDENTIFIER,
+            Self::SignAllTransactions => SOLANA_SIGN_ALL_TRANSACTIONS_IDENTIFIER,
+        }
+    }
+
+    /// Get the feature with the wallet standard `identifier`, eg. `standard:connect`.
+    /// Returns [None] for identifiers of non-standard features
+    pub fn from_identifier(identifier: &str) -> Option<Self> {
+        match identifier {
+            STANDARD_CONNECT_IDENTIFIER => Some(Self::Connect),
+            STANDARD_DISCONNECT_IDENTIFIER => Some(Self::Disconnect),
+            STANDARD_EVENTS_IDENTIFIER => Some(Self::Events),
+            SOLANA_SIGN_IN_IDENTIFIER => Some(Self::SignIn),
+            SOLANA_SIGN_MESSAGE_IDENTIFIER => Some(Self::SignMessage),
+            SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER => Some(Self::SignAndSendTransaction),
+            SOLANA_SIGN_TRANSACTION_IDENTIFIER => Some(Self::SignTransaction),
+            SOLANA_SIGN_ALL_TRANSACTIONS_IDENTIFIER => Some(Self::SignAllTransactions),
+            _ => None,
+        }
+    }
+}
+
+/// The operations a dapp requests from a wallet, used to find the [WalletFeature]s
+/// a wallet is missing in order to perform an operation
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum WalletOperation {
+    /// Connect to the wallet
+    Connect,
+    /// Disconnect from the wallet
+    Disconnect,
+    /// Sign In With Solana
+    SignIn,
+    /// Sign a message
+    SignMessage,
+    /// Sign a transaction without sending it
+    SignTransaction,
+    /// Sign a transaction and let the wallet send it
+    SignAndSend,
+    /// Sign multiple transactions in a single approval
+    SignAll,
+}
+
+impl WalletOperation {
+    /// The [WalletFeature]s a wallet must support to perform the operation
+    pub fn required_features(&self) -> &'static [WalletFeature] {
+        match self {
+            Self::Connect => &[WalletFeature::Connect],
+            Self::Disconnect => &[WalletFeature::Disconnect],
+            Self::SignIn => &[WalletFeature::SignIn],
+            Self::SignMessage => &[WalletFeature::SignMessage],
+            Self::SignTransaction => &[WalletFeature::SignTransaction],
+            Self::SignAndSend => &[WalletFeature::SignAndSendTransaction],
+            Self::SignAll => &[WalletFeature::SignAllTransactions],
+        }
+    }
+}
+
+impl core::fmt::Display for WalletFeature {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.identifier())
+    }
 }
@@ -18,6 +18,9 @@ pub use version::*;
 mod utils;
 pub use utils::*;
 
+mod program_address;
+pub use program_address::*;
+
 /// Feature support struct
 pub mod feature_support;
 
@@ -69,6 +72,19 @@ mod chain_tests {
         assert!(Cluster::DevNet == "https://cluster.foo".into());
     }
 
+    #[test]
+    fn strict_chain() {
+        assert_eq!(Ok(Cluster::MainNet), "solana:mainnet".parse());
+        assert_eq!(Ok(Cluster::LocalNet), "http://localhost:8899".parse());
+        assert_eq!(Ok(Cluster::TestNet), "testnet".parse());
+        assert_eq!(
+            Err(crate::WalletUtilsError::UnsupportedChain(
+                "solana:localnet2".to_string()
+            )),
+            "solana:localnet2".parse::<Cluster>()
+        );
+    }
+
     #[test]
     fn validate_endpoint() {
         assert_eq!(
@@ -82,4 +98,12 @@ mod chain_tests {
         );
         assert_eq!(Cluster::LocalNet.endpoint(), "http://localhost:8899");
     }
+
+    #[test]
+    fn genesis_hash() {
+        assert_eq!(Cluster::MainNet.genesis_hash(), Some(MAINNET_GENESIS_HASH));
+        assert_eq!(Cluster::DevNet.genesis_hash(), Some(DEVNET_GENESIS_HASH));
+        assert_eq!(Cluster::TestNet.genesis_hash(), Some(TESTNET_GENESIS_HASH));
+        assert_eq!(Cluster::LocalNet.genesis_hash(), None);
+    }
 }
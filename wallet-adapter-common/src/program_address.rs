@@ -0,0 +1,104 @@
+use ed25519_dalek::VerifyingKey;
+use sha2::{Digest, Sha256};
+
+use crate::{WalletUtilsError, WalletUtilsResult};
+
+/// The program id of the SPL Token program `TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA`
+pub const TOKEN_PROGRAM_ID: [u8; 32] = [
+    6, 221, 246, 225, 215, 101, 161, 147, 217, 203, 225, 70, 206, 235, 121, 172, 28, 180, 133, 237,
+    95, 91, 55, 145, 58, 140, 245, 133, 126, 255, 0, 169,
+];
+
+/// The program id of the SPL Associated Token Account program
+/// `ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL`
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: [u8; 32] = [
+    140, 151, 37, 143, 78, 36, 137, 241, 187, 61, 16, 41, 20, 142, 13, 131, 11, 90, 19, 153, 218,
+    255, 16, 132, 4, 142, 123, 216, 219, 233, 248, 89,
+];
+
+/// The maximum length in bytes of a single seed of a program derived address
+pub const MAX_SEED_LEN: usize = 32;
+
+const PDA_MARKER: &[u8] = b"ProgramDerivedAddress";
+
+/// Find the program derived address of the `seeds` for the `program_id` and the bump seed
+/// used to derive it. Starting from a bump of `255`, the bump is decremented until the
+/// address is not a point on the Ed25519 curve, so that no private key exists for it.
+/// Returns [None] if a seed is longer than [MAX_SEED_LEN] or if no bump yields an address.
+pub fn find_program_address(seeds: &[&[u8]], program_id: &[u8; 32]) -> Option<([u8; 32], u8)> {
+    if seeds.iter().any(|seed| seed.len() > MAX_SEED_LEN) {
+        return None;
+    }
+
+    (0..=u8::MAX).rev().find_map(|bump| {
+        let mut hasher = Sha256::new();
+        seeds.iter().for_each(|seed| hasher.update(seed));
+        hasher.update([bump]);
+        hasher.update(program_id);
+        hasher.update(PDA_MARKER);
+
+        let address: [u8; 32] = hasher.finalize().into();
+
+        // A valid program derived address must not decompress to a curve point
+        VerifyingKey::from_bytes(&address)
+            .is_err()
+            .then_some((address, bump))
+    })
+}
+
+/// Get the address of the associated token account holding the tokens of the `mint`
+/// for the `owner`, derived for the [TOKEN_PROGRAM_ID]
+pub fn associated_token_address(owner: &[u8; 32], mint: &[u8; 32]) -> WalletUtilsResult<[u8; 32]> {
+    find_program_address(
+        &[owner, &TOKEN_PROGRAM_ID, mint],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .map(|(address, _)| address)
+    .ok_or(WalletUtilsError::ProgramAddressNotFound)
+}
+
+#[cfg(test)]
+mod program_address_checks {
+    use super::*;
+
+    fn decode(base58: &str) -> [u8; 32] {
+        bs58::decode(base58).into_vec().unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn program_ids() {
+        assert_eq!(
+            TOKEN_PROGRAM_ID,
+            decode("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")
+        );
+        assert_eq!(
+            ASSOCIATED_TOKEN_PROGRAM_ID,
+            decode("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")
+        );
+    }
+
+    #[test]
+    fn known_associated_token_address() {
+        let mint = decode("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+
+        assert_eq!(
+            associated_token_address(&[1u8; 32], &mint),
+            Ok(decode("Gs6NxmndAL3PULGZeYUHCknpMdDVbL46eFCBh856p5z6"))
+        );
+
+        // The first bumps of this owner yield addresses on the curve
+        let owner = [4u8; 32];
+        let (address, bump) = find_program_address(
+            &[&owner, &TOKEN_PROGRAM_ID, &mint],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        )
+        .unwrap();
+        assert_eq!(bump, 248);
+        assert_eq!(
+            address,
+            decode("DhscvS2mfxadjGkSvoGxKzgEi6ZnuLunmFEjcZ5sd5u1")
+        );
+
+        assert!(find_program_address(&[&[0u8; 33]], &ASSOCIATED_TOKEN_PROGRAM_ID).is_none());
+    }
+}
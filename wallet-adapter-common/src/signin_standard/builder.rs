@@ -0,0 +1,324 @@
+use std::time::SystemTime;
+
+use crate::{
+    clusters::Cluster, signin_standard::SigninInput, WalletCommonUtils, WalletUtilsError,
+    WalletUtilsResult,
+};
+
+/// Collects the fields of a [SigninInput] and validates all of them together in
+/// [Self::build], so that the result does not depend on the order the setters are called in.
+///
+/// #### Example
+/// ```rust
+/// use std::time::{Duration, SystemTime};
+/// use wallet_adapter_common::{clusters::Cluster, signin_standard::SigninInputBuilder};
+///
+/// let now = SystemTime::now();
+///
+/// let signin_input = SigninInputBuilder::new()
+///     .set_domain("example.com")
+///     .set_chain_id(Cluster::MainNet)
+///     .set_nonce("85c8b2a3f1d04e56")
+///     .set_expiration_time(now + Duration::from_secs(300))
+///     .set_issued_at(now)
+///     .build(now)
+///     .unwrap();
+///
+/// assert_eq!(signin_input.domain().map(|domain| domain.as_str()), Some("example.com"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SigninInputBuilder {
+    domain: Option<String>,
+    address: Option<String>,
+    statement: Option<String>,
+    uri: Option<String>,
+    version: Option<String>,
+    chain_id: Option<Cluster>,
+    nonce: Option<String>,
+    issued_at: Option<SystemTime>,
+    expiration_time: Option<SystemTime>,
+    not_before: Option<SystemTime>,
+    request_id: Option<String>,
+    resources: Vec<String>,
+}
+
+impl SigninInputBuilder {
+    /// Instantiate a builder without any fields set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the EIP-4361 domain, see [SigninInput::set_domain]
+    pub fn set_domain(&mut self, domain: &str) -> &mut Self {
+        self.domain.replace(domain.to_string());
+
+        self
+    }
+
+    /// Set the Base58 address, validated in [Self::build]
+    pub fn set_address(&mut self, address: &str) -> &mut Self {
+        self.address.replace(address.to_string());
+
+        self
+    }
+
+    /// Set the EIP-4361 statement, see [SigninInput::set_statement]
+    pub fn set_statement(&mut self, statement: &str) -> &mut Self {
+        self.statement.replace(statement.to_string());
+
+        self
+    }
+
+    /// Set the EIP-4361 URI, see [SigninInput::set_uri]
+    pub fn set_uri(&mut self, uri: &str) -> &mut Self {
+        self.uri.replace(uri.to_string());
+
+        self
+    }
+
+    /// Set the EIP-4361 version, see [SigninInput::set_version]
+    pub fn set_version(&mut self, version: &str) -> &mut Self {
+        self.version.replace(version.to_string());
+
+        self
+    }
+
+    /// Set the EIP-4361 chain ID, see [SigninInput::set_chain_id]
+    pub fn set_chain_id(&mut self, cluster: Cluster) -> &mut Self {
+        self.chain_id.replace(cluster);
+
+        self
+    }
+
+    /// Set the nonce, validated in [Self::build]
+    pub fn set_nonce(&mut self, nonce: &str) -> &mut Self {
+        self.nonce.replace(nonce.to_string());
+
+        self
+    }
+
+    /// Set the time the request was issued at
+    pub fn set_issued_at(&mut self, time: SystemTime) -> &mut Self {
+        self.issued_at.replace(time);
+
+        self
+    }
+
+    /// Set the time the request expires, validated in [Self::build]
+    pub fn set_expiration_time(&mut self, time: SystemTime) -> &mut Self {
+        self.expiration_time.replace(time);
+
+        self
+    }
+
+    /// Set the time the request becomes valid, validated in [Self::build]
+    pub fn set_not_before(&mut self, time: SystemTime) -> &mut Self {
+        self.not_before.replace(time);
+
+        self
+    }
+
+    /// Set the EIP-4361 request ID, see [SigninInput::set_request_id]
+    pub fn set_request_id(&mut self, id: &str) -> &mut Self {
+        self.request_id.replace(id.to_string());
+
+        self
+    }
+
+    /// Add an EIP-4361 resource, see [SigninInput::add_resource]
+    pub fn add_resource(&mut self, resource: &str) -> &mut Self {
+        self.resources.push(resource.to_string());
+
+        self
+    }
+
+    /// Validate all the fields and build the [SigninInput], returning the first error found.
+    /// The address must be a valid Ed25519 public key, the nonce must be at least
+    /// 8 alphanumeric characters and the times must be ordered as
+    /// `issued_at <= not_before <= expiration_time` with `not_before` and `expiration_time`
+    /// not earlier than `now`
+    pub fn build(&self, now: SystemTime) -> WalletUtilsResult<SigninInput> {
+        let mut signin_input = SigninInput::new();
+
+        if let Some(address) = self.address.as_ref() {
+            signin_input.set_address(address)?;
+
+            let mut public_key = [0u8; 32];
+            bs58::decode(address)
+                .onto(&mut public_key)
+                .or(Err(WalletUtilsError::InvalidBase58Address))?;
+            WalletCommonUtils::public_key(&public_key)?;
+        }
+
+        if let Some(nonce) = self.nonce.as_ref() {
+            signin_input.set_custom_nonce(nonce)?;
+
+            if !nonce.chars().all(|char| char.is_ascii_alphanumeric()) {
+                return Err(WalletUtilsError::NonceMustBeAlphanumeric);
+            }
+        }
+
+        // The setters validate the times against the fields already set,
+        // so the issued time is set first and the not before time last
+        if let Some(issued_at) = self.issued_at {
+            signin_input.set_issued_at(issued_at);
+        }
+        if let Some(expiration_time) = self.expiration_time {
+            signin_input.set_expiration_time(now, expiration_time)?;
+        }
+        if let Some(not_before) = self.not_before {
+            signin_input.set_not_before_time(now, not_before)?;
+        }
+
+        if let Some(domain) = self.domain.as_ref() {
+            signin_input.set_domain(domain);
+        }
+        if let Some(statement) = self.statement.as_ref() {
+            signin_input.set_statement(statement);
+        }
+        if let Some(uri) = self.uri.as_ref() {
+            signin_input.set_uri(uri);
+        }
+        if let Some(version) = self.version.as_ref() {
+            signin_input.set_version(version);
+        }
+        if let Some(cluster) = self.chain_id {
+            signin_input.set_chain_id(cluster);
+        }
+        if let Some(request_id) = self.request_id.as_ref() {
+            signin_input.set_request_id(request_id);
+        }
+        self.resources.iter().for_each(|resource| {
+            signin_input.add_resource(resource);
+        });
+
+        Ok(signin_input)
+    }
+}
+
+#[cfg(test)]
+mod signin_input_builder_checks {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn now() -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(1_735_734_600)
+    }
+
+    fn minutes(minutes: u64) -> Duration {
+        Duration::from_secs(minutes * 60)
+    }
+
+    #[test]
+    fn valid_input() {
+        let signin_input = SigninInputBuilder::new()
+            .set_domain("example.com")
+            .set_address("4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi")
+            .set_nonce("85c8b2a3f1d04e56")
+            // The setters can be called in any order
+            .set_not_before(now() + minutes(1))
+            .set_expiration_time(now() + minutes(5))
+            .set_issued_at(now())
+            .add_resource("https://example.com/terms")
+            .build(now())
+            .unwrap();
+
+        assert_eq!(signin_input.issued_at(), Some(&now()));
+        assert_eq!(signin_input.not_before(), Some(&(now() + minutes(1))));
+        assert_eq!(signin_input.expiration_time(), Some(&(now() + minutes(5))));
+        assert_eq!(signin_input.nonce().unwrap(), "85c8b2a3f1d04e56");
+        assert_eq!(signin_input.resources(), ["https://example.com/terms"]);
+    }
+
+    #[test]
+    fn invalid_times() {
+        let mut builder = SigninInputBuilder::new();
+        builder
+            .set_issued_at(now() + minutes(2))
+            .set_expiration_time(now() + minutes(1));
+        assert_eq!(
+            Err(WalletUtilsError::ExpiryTimeEarlierThanIssuedTime),
+            builder.build(now())
+        );
+
+        let mut builder = SigninInputBuilder::new();
+        builder.set_expiration_time(now() - minutes(1));
+        assert_eq!(
+            Err(WalletUtilsError::ExpirationTimeIsInThePast),
+            builder.build(now())
+        );
+
+        let mut builder = SigninInputBuilder::new();
+        builder
+            .set_issued_at(now() + minutes(2))
+            .set_not_before(now() + minutes(1));
+        assert_eq!(
+            Err(WalletUtilsError::NotBeforeTimeEarlierThanIssuedTime),
+            builder.build(now())
+        );
+
+        let mut builder = SigninInputBuilder::new();
+        builder.set_not_before(now() - minutes(1));
+        assert_eq!(
+            Err(WalletUtilsError::NotBeforeTimeIsInThePast),
+            builder.build(now())
+        );
+
+        let mut builder = SigninInputBuilder::new();
+        builder
+            .set_not_before(now() + minutes(2))
+            .set_expiration_time(now() + minutes(1));
+        assert_eq!(
+            Err(WalletUtilsError::NotBeforeTimeLaterThanExpirationTime),
+            builder.build(now())
+        );
+    }
+
+    #[test]
+    fn invalid_nonce() {
+        assert_eq!(
+            Err(WalletUtilsError::NonceMustBeAtLeast8Characters),
+            SigninInputBuilder::new().set_nonce("abc123").build(now())
+        );
+        assert_eq!(
+            Err(WalletUtilsError::NonceMustBeAlphanumeric),
+            SigninInputBuilder::new()
+                .set_nonce("abc-1234_xyz")
+                .build(now())
+        );
+    }
+
+    #[test]
+    fn invalid_address() {
+        assert_eq!(
+            Err(WalletUtilsError::InvalidBase58Address),
+            SigninInputBuilder::new().set_address("0OIl").build(now())
+        );
+        assert_eq!(
+            Err(WalletUtilsError::InvalidEd25519PublicKeyLen(1)),
+            SigninInputBuilder::new().set_address("2g").build(now())
+        );
+        // `[2u8; 32]` is not a point on the Ed25519 curve
+        assert_eq!(
+            Err(WalletUtilsError::InvalidEd25519PublicKeyBytes),
+            SigninInputBuilder::new()
+                .set_address("8qbHbw2BbbTHBW1sbeqakYXVKRQM8Ne7pLK7m6CVfeR")
+                .build(now())
+        );
+    }
+
+    #[test]
+    fn first_error_is_returned() {
+        let mut builder = SigninInputBuilder::new();
+        builder
+            .set_address("2g")
+            .set_nonce("short")
+            .set_expiration_time(now() - minutes(1));
+
+        assert_eq!(
+            Err(WalletUtilsError::InvalidEd25519PublicKeyLen(1)),
+            builder.build(now())
+        );
+    }
+}
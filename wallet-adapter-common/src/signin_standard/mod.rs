@@ -3,3 +3,6 @@ pub use signin::*;
 
 mod output;
 pub use output::*;
+
+mod builder;
+pub use builder::*;
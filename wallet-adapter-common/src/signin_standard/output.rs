@@ -1,4 +1,57 @@
-use crate::WalletAccountData;
+use core::ops::Deref;
+
+use crate::{
+    signin_standard::SigninInput, WalletAccountData, WalletCommonUtils, WalletUtilsError,
+    WalletUtilsResult,
+};
+
+/// The Sign In With Solana (SIWS) message signed by a wallet,
+/// kept distinct from other strings so that it is not passed to the wrong API
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default)]
+pub struct SiwsMessage(String);
+
+impl SiwsMessage {
+    /// Instantiate a [SiwsMessage] from the UTF-8 message text
+    pub fn new(message: impl Into<String>) -> Self {
+        Self(message.into())
+    }
+
+    /// Parse the message into the [SigninInput] fields it contains
+    pub fn parse(&self) -> WalletUtilsResult<SigninInput> {
+        SigninInput::parser(&self.0)
+    }
+
+    /// Get the message text
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl Deref for SiwsMessage {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl From<String> for SiwsMessage {
+    fn from(message: String) -> Self {
+        Self(message)
+    }
+}
+
+impl From<&str> for SiwsMessage {
+    fn from(message: &str) -> Self {
+        Self::new(message)
+    }
+}
+
+impl core::fmt::Display for SiwsMessage {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
 
 /// The output of Sign In With Solana (SIWS) response from a wallet
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
@@ -6,7 +59,7 @@ pub struct SignInOutput {
     /// A [An Account](WalletAccountData)
     pub account: WalletAccountData,
     /// The UTF-8 encoded message
-    pub message: String,
+    pub message: SiwsMessage,
     /// The signature as a  byte array of 64 bytes in length corresponding to a
     /// [Ed25519 Signature](ed25519_dalek::Signature)
     pub signature: [u8; 64],
@@ -25,8 +78,164 @@ impl SignInOutput {
     pub fn public_key(&self) -> String {
         bs58::encode(&self.public_key).into_string()
     }
+
     /// The address of the account the wallet used to sign the message
     pub fn address(&self) -> &str {
         self.account.address.as_str()
     }
+
+    /// Convert the output into a [VerificationPayload] that can be sent to a backend
+    /// and checked there with [verify_payload]
+    pub fn to_verification_payload(&self) -> VerificationPayload {
+        VerificationPayload {
+            signature: self.signature(),
+            public_key: self.public_key(),
+            message: self.message.to_string(),
+            address: self.address().to_string(),
+        }
+    }
+}
+
+/// The parts of a [SignInOutput] a server needs to verify a Sign In With Solana (SIWS)
+/// response, with the binary fields Base58 encoded. Serializable with the `serde` feature.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VerificationPayload {
+    /// Base58 encoded [Ed25519 Signature](ed25519_dalek::Signature) of the message
+    pub signature: String,
+    /// Base58 encoded [Ed25519 Public Key](ed25519_dalek::VerifyingKey) that signed the message
+    pub public_key: String,
+    /// The UTF-8 encoded message
+    pub message: String,
+    /// The address of the account that signed in
+    pub address: String,
+}
+
+/// Verify that the signature of a [VerificationPayload] is valid for its message and public key
+/// and that the address is the public key of the payload. Does not need a browser so it can be
+/// used on a server receiving the payload from a dapp.
+pub fn verify_payload(payload: &VerificationPayload) -> WalletUtilsResult<()> {
+    let public_key = bs58::decode(&payload.public_key)
+        .into_vec()
+        .or(Err(WalletUtilsError::InvalidBase58Address))?;
+    let public_key = WalletCommonUtils::to32byte_array(&public_key)?;
+
+    let signature = WalletCommonUtils::signature_from_base58(&payload.signature)?;
+
+    if payload.address != payload.public_key {
+        return Err(WalletUtilsError::AddressPublicKeyMismatch(
+            payload.address.clone(),
+        ));
+    }
+
+    WalletCommonUtils::verify(
+        &public_key,
+        payload.message.as_bytes(),
+        &signature.to_bytes(),
+    )
+}
+
+#[cfg(test)]
+mod base58_checks {
+    use super::*;
+
+    #[test]
+    fn known_vectors() {
+        let output = SignInOutput {
+            account: WalletAccountData::default(),
+            message: SiwsMessage::default(),
+            signature: [2u8; 64],
+            public_key: [1u8; 32],
+        };
+
+        assert_eq!(
+            output.signature(),
+            "3L3RY5sT8K4kyEnqhizwaqxLEbcYvpGrGPNEYRwtbCSUtL6YL86jdrvCbohnP5q8VxQ3qzGmt3W3iQJW97rD7m3"
+        );
+        assert_eq!(
+            output.public_key(),
+            "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi"
+        );
+    }
+}
+
+#[cfg(test)]
+mod siws_message_checks {
+    use super::*;
+    use crate::clusters::Cluster;
+
+    #[test]
+    fn construct_and_parse() {
+        let mut signin_input = SigninInput::new();
+        signin_input
+            .set_domain("example.com")
+            .set_statement("Sign in to example.com")
+            .set_chain_id(Cluster::DevNet)
+            .set_custom_nonce("0123456789abcdef")
+            .unwrap()
+            .set_address("11111111111111111111111111111111")
+            .unwrap();
+
+        let message = SiwsMessage::new(signin_input.to_message());
+        assert_eq!(message.to_string(), signin_input.to_message());
+        assert!(message.starts_with("example.com wants you to sign in"));
+        assert_eq!(message.parse().unwrap(), signin_input);
+        assert!(signin_input.check_eq(&message).is_ok());
+
+        assert_eq!(SiwsMessage::from("foo").as_str(), "foo");
+    }
+}
+
+#[cfg(test)]
+mod verification_payload_checks {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let signing_key = SigningKey::from_bytes(&[3u8; 32]);
+        let public_key = signing_key.verifying_key().to_bytes();
+        let message = SiwsMessage::new("example.com wants you to sign in");
+
+        let output = SignInOutput {
+            account: WalletAccountData {
+                address: bs58::encode(public_key).into_string(),
+                public_key,
+                ..Default::default()
+            },
+            signature: signing_key.sign(message.as_bytes()).to_bytes(),
+            message,
+            public_key,
+        };
+
+        let payload = output.to_verification_payload();
+        assert_eq!(payload.signature, output.signature());
+        assert_eq!(payload.public_key, output.public_key());
+        assert_eq!(payload.address, output.address());
+        assert!(verify_payload(&payload).is_ok());
+
+        let mut tampered = payload.clone();
+        tampered.message.push('!');
+        assert_eq!(
+            verify_payload(&tampered),
+            Err(WalletUtilsError::InvalidSignature)
+        );
+
+        let mut mismatched = payload.clone();
+        mismatched.address = "11111111111111111111111111111111".to_string();
+        assert_eq!(
+            verify_payload(&mismatched),
+            Err(WalletUtilsError::AddressPublicKeyMismatch(
+                mismatched.address.clone()
+            ))
+        );
+
+        let mut invalid = payload;
+        invalid.public_key = "0OIl".to_string();
+        assert_eq!(
+            verify_payload(&invalid),
+            Err(WalletUtilsError::InvalidBase58Address)
+        );
+    }
 }
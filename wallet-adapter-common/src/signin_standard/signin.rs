@@ -1,6 +1,7 @@
 use std::time::{Duration, SystemTime};
 
 use sha3::{Digest, Sha3_256};
+use zeroize::Zeroizing;
 
 use crate::{clusters::Cluster, WalletCommonUtils, WalletUtilsError, WalletUtilsResult};
 
@@ -67,6 +68,56 @@ pub struct SigninInput {
     resources: Vec<String>,
 }
 
+/// The fields of a Sign In With Solana (SIWS) message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SigninField {
+    /// The `domain` field
+    Domain,
+    /// The `address` field
+    Address,
+    /// The `statement` field
+    Statement,
+    /// The `uri` field
+    Uri,
+    /// The `version` field
+    Version,
+    /// The `chain_id` field
+    ChainId,
+    /// The `nonce` field
+    Nonce,
+    /// The `issued_at` field
+    IssuedAt,
+    /// The `expiration_time` field
+    ExpirationTime,
+    /// The `not_before` field
+    NotBefore,
+    /// The `request_id` field
+    RequestId,
+    /// The `resources` field
+    Resources,
+}
+
+impl core::fmt::Display for SigninField {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let as_str = match self {
+            Self::Domain => "Domain",
+            Self::Address => "Address",
+            Self::Statement => "Statement",
+            Self::Uri => "URI",
+            Self::Version => "Version",
+            Self::ChainId => "Chain ID",
+            Self::Nonce => "Nonce",
+            Self::IssuedAt => "Issued At",
+            Self::ExpirationTime => "Expiration Time",
+            Self::NotBefore => "Not Before",
+            Self::RequestId => "Request ID",
+            Self::Resources => "Resources",
+        };
+
+        write!(f, "{as_str}")
+    }
+}
+
 impl SigninInput {
     /// Same as `Self::default()` as it initializes [Self] with default values
     pub fn new() -> Self {
@@ -87,10 +138,16 @@ impl SigninInput {
     /// a result of the sent message not corresponding with the signed message
     pub fn set_address(&mut self, address: &str) -> WalletUtilsResult<&mut Self> {
         let mut buffer = [0u8; 32];
-        bs58::decode(address)
+        let buffer_written_len = bs58::decode(address)
             .onto(&mut buffer)
             .or(Err(WalletUtilsError::InvalidBase58Address))?;
 
+        if buffer_written_len != 32 {
+            return Err(WalletUtilsError::InvalidEd25519PublicKeyLen(
+                buffer_written_len as u8,
+            ));
+        }
+
         self.address.replace(address.to_string());
 
         Ok(self)
@@ -133,10 +190,13 @@ impl SigninInput {
     /// An EIP-4361 Nonce which is an alphanumeric string containing a minimum of 8 characters.
     /// This is generated from the Cryptographically Secure Random Number Generator
     /// and the bytes converted to hex formatted string.
+    /// The random bytes are held in a [Zeroizing] buffer so they are cleared
+    /// when dropped, even while unwinding, leaving only the hex nonce.
     pub fn set_nonce(&mut self) -> &mut Self {
-        let random_bytes = WalletCommonUtils::rand_32bytes();
+        let mut random_bytes = Zeroizing::new([0u8; 32]);
+        WalletCommonUtils::fill_rand_bytes(random_bytes.as_mut_slice());
 
-        let hash = Sha3_256::digest(&random_bytes);
+        let hash = Sha3_256::digest(random_bytes.as_slice());
         self.nonce
             .replace(hash.iter().map(|b| format!("{:02x}", b)).collect());
 
@@ -162,7 +222,7 @@ impl SigninInput {
     /// If not provided, the wallet does not include Issued At in the message.
     /// This also follows the ISO 8601 datetime.
     pub fn set_issued_at(&mut self, time: SystemTime) -> &mut Self {
-        self.issued_at.replace(time);
+        self.issued_at.replace(WalletCommonUtils::siws_time(time));
 
         self
     }
@@ -215,6 +275,8 @@ impl SigninInput {
         now: SystemTime,
         expiration_time: SystemTime,
     ) -> WalletUtilsResult<&mut Self> {
+        let expiration_time = WalletCommonUtils::siws_time(expiration_time);
+
         if let Some(issued_at) = self.issued_at {
             if issued_at > expiration_time {
                 return Err(WalletUtilsError::ExpiryTimeEarlierThanIssuedTime);
@@ -279,6 +341,8 @@ impl SigninInput {
         now: SystemTime,
         not_before: SystemTime,
     ) -> WalletUtilsResult<&mut Self> {
+        let not_before = WalletCommonUtils::siws_time(not_before);
+
         if let Some(issued_at) = self.issued_at {
             if issued_at > not_before {
                 return Err(WalletUtilsError::NotBeforeTimeEarlierThanIssuedTime);
@@ -325,10 +389,14 @@ impl SigninInput {
                 .transpose()
         };
 
+        let mut in_resources = false;
+
         input
             .split("\n")
             .enumerate()
             .try_for_each(|(index, input)| {
+                // Field labels only match at the start of a line so that a statement
+                // or resource mentioning a label does not overwrite that field
                 if index == 1 {
                     signin_input.address.replace(input.trim().to_string());
                 }
@@ -337,65 +405,162 @@ impl SigninInput {
                     signin_input.statement.replace(input.trim().to_string());
                 }
 
-                if input.contains("URI") {
+                if input.trim_start().starts_with("URI:") {
                     signin_input.uri = split_colon(input);
                 }
 
-                if input.contains("Version") {
+                if input.trim_start().starts_with("Version:") {
                     signin_input.version = split_colon(input);
                 }
 
-                if input.contains("Chain ID") {
+                if input.trim_start().starts_with("Chain ID:") {
                     if let Some((_left, right)) = input.split_once(":") {
                         let cluster: Cluster = right.trim().into();
 
                         signin_input.chain_id.replace(cluster);
                     }
                 }
-                if input.contains("Nonce") {
+                if input.trim_start().starts_with("Nonce:") {
                     signin_input.nonce = split_colon(input);
                 }
 
-                if input.contains("Issued At") {
+                if input.trim_start().starts_with("Issued At:") {
                     signin_input.issued_at = split_colon_system_time(input)?;
                 }
 
-                if input.contains("Expiration") {
+                if input.trim_start().starts_with("Expiration Time:") {
                     signin_input.expiration_time = split_colon_system_time(input)?;
                 }
 
-                if input.contains("Not Before") {
+                if input.trim_start().starts_with("Not Before:") {
                     signin_input.not_before = split_colon_system_time(input)?;
                 }
 
-                if input.contains("Request ID") {
+                if input.trim_start().starts_with("Request ID:") {
                     signin_input.request_id = split_colon(input);
                 }
 
-                if input.starts_with("-") {
-                    if let Some(value) = input.split("-").nth(1) {
+                // Resources are the `- ` prefixed lines following the `Resources:` line,
+                // the URIs themselves may contain `-`
+                if in_resources {
+                    if let Some(value) = input.strip_prefix("- ") {
                         signin_input.resources.push(value.trim().to_string());
                     }
                 }
 
+                if input.trim() == "Resources:" {
+                    in_resources = true;
+                }
+
                 Ok::<(), WalletUtilsError>(())
             })?;
 
         Ok(signin_input)
     }
 
+    /// Builds the Sign In With Solana (SIWS) message text as defined by the
+    /// [SIWS](https://github.com/phantom/sign-in-with-solana) standard.
+    /// This is the inverse of [Self::parser()]
+    pub fn to_message(&self) -> String {
+        let mut message = format!(
+            "{} wants you to sign in with your Solana account:\n{}",
+            self.domain.as_deref().unwrap_or_default(),
+            self.address.as_deref().unwrap_or_default()
+        );
+
+        if let Some(statement) = self.statement.as_ref() {
+            message.push_str("\n\n");
+            message.push_str(statement);
+        }
+
+        let mut fields = Vec::<String>::new();
+
+        if let Some(uri) = self.uri.as_ref() {
+            fields.push(format!("URI: {uri}"));
+        }
+        if let Some(version) = self.version.as_ref() {
+            fields.push(format!("Version: {version}"));
+        }
+        if let Some(chain_id) = self.chain_id.as_ref() {
+            fields.push(format!("Chain ID: {}", chain_id.chain()));
+        }
+        if let Some(nonce) = self.nonce.as_ref() {
+            fields.push(format!("Nonce: {nonce}"));
+        }
+        if let Some(issued_at) = self.issued_at_iso8601() {
+            fields.push(format!("Issued At: {issued_at}"));
+        }
+        if let Some(expiration_time) = self.expiration_time_iso8601() {
+            fields.push(format!("Expiration Time: {expiration_time}"));
+        }
+        if let Some(not_before) = self.not_before_iso8601() {
+            fields.push(format!("Not Before: {not_before}"));
+        }
+        if let Some(request_id) = self.request_id.as_ref() {
+            fields.push(format!("Request ID: {request_id}"));
+        }
+        if !self.resources.is_empty() {
+            fields.push("Resources:".to_string());
+            self.resources
+                .iter()
+                .for_each(|resource| fields.push(format!("- {resource}")));
+        }
+
+        if !fields.is_empty() {
+            message.push_str("\n\n");
+            message.push_str(&fields.join("\n"));
+        }
+
+        message
+    }
+
+    /// The [Sha3_256] hash of the message generated by [Self::to_message()].
+    /// Useful as a compact key when logging or correlating a sign in request
+    /// with the message signed by the wallet
+    pub fn message_hash(&self) -> [u8; 32] {
+        Sha3_256::digest(self.to_message().as_bytes()).into()
+    }
+
     /// Checks if the response of a Sign In With Solana (SIWS) from the Wallet is the same as the
     /// request data sent to the wallet to be signed
     pub fn check_eq(&self, other: &str) -> WalletUtilsResult<()> {
-        let other = SigninInput::parser(other)?;
-
-        if self.eq(&other) {
+        if self.diff(other)?.is_empty() {
             Ok(())
         } else {
             Err(WalletUtilsError::MessageResponseMismatch)
         }
     }
 
+    /// Parses the Sign In With Solana (SIWS) message from the Wallet and returns the
+    /// fields that differ from the request data sent to the wallet to be signed.
+    /// This is useful to log exactly which fields a wallet altered.
+    pub fn diff(&self, other: &str) -> WalletUtilsResult<Vec<SigninField>> {
+        let other = SigninInput::parser(other)?;
+
+        let checks = [
+            (SigninField::Domain, self.domain == other.domain),
+            (SigninField::Address, self.address == other.address),
+            (SigninField::Statement, self.statement == other.statement),
+            (SigninField::Uri, self.uri == other.uri),
+            (SigninField::Version, self.version == other.version),
+            (SigninField::ChainId, self.chain_id == other.chain_id),
+            (SigninField::Nonce, self.nonce == other.nonce),
+            (SigninField::IssuedAt, self.issued_at == other.issued_at),
+            (
+                SigninField::ExpirationTime,
+                self.expiration_time == other.expiration_time,
+            ),
+            (SigninField::NotBefore, self.not_before == other.not_before),
+            (SigninField::RequestId, self.request_id == other.request_id),
+            (SigninField::Resources, self.resources == other.resources),
+        ];
+
+        Ok(checks
+            .into_iter()
+            .filter_map(|(field, is_eq)| (!is_eq).then_some(field))
+            .collect())
+    }
+
     /// An EIP-4361 Request ID.
     /// In addition to using nonce to avoid replay attacks,
     /// dapps can also choose to include a unique signature in the requestId .
@@ -427,6 +592,17 @@ impl SigninInput {
         self
     }
 
+    /// Same as [Self::add_resource()] but the resource must be a URI with a scheme,
+    /// like `https://example.com/terms`, otherwise the error
+    /// [WalletUtilsError::InvalidResourceUri] is returned
+    pub fn add_resource_uri(&mut self, uri: &str) -> WalletUtilsResult<&mut Self> {
+        ResourceUri::parse(uri)?;
+
+        self.resources.push(uri.to_string());
+
+        Ok(self)
+    }
+
     /// Get the `domain` field
     pub fn domain(&self) -> Option<&String> {
         self.domain.as_ref()
@@ -479,20 +655,17 @@ impl SigninInput {
 
     /// Get the `issued_at` field as ISO8601 date time string
     pub fn issued_at_iso8601(&self) -> Option<String> {
-        self.issued_at
-            .map(|time_exists| WalletCommonUtils::to_iso860(time_exists).to_string())
+        self.issued_at.map(WalletCommonUtils::siws_timestamp)
     }
 
     /// Get the `expiration_time` field as ISO8601 date time string
     pub fn expiration_time_iso8601(&self) -> Option<String> {
-        self.expiration_time
-            .map(|time_exists| WalletCommonUtils::to_iso860(time_exists).to_string())
+        self.expiration_time.map(WalletCommonUtils::siws_timestamp)
     }
 
     /// Get the `not_before` field as ISO8601 date time string
     pub fn not_before_iso8601(&self) -> Option<String> {
-        self.not_before
-            .map(|time_exists| WalletCommonUtils::to_iso860(time_exists).to_string())
+        self.not_before.map(WalletCommonUtils::siws_timestamp)
     }
 
     /// Get the `request_id` field
@@ -504,6 +677,57 @@ impl SigninInput {
     pub fn resources(&self) -> &[String] {
         self.resources.as_slice()
     }
+
+    /// Get the `resources` that are URIs split into their components.
+    /// Resources added with [Self::add_resource()] that are not URIs are skipped
+    pub fn resource_uris(&self) -> Vec<ResourceUri<'_>> {
+        self.resources
+            .iter()
+            .filter_map(|resource| ResourceUri::parse(resource).ok())
+            .collect()
+    }
+}
+
+/// A SIWS resource URI split into its scheme and the part after the scheme,
+/// eg. `https` and `//example.com/terms` for `https://example.com/terms`
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct ResourceUri<'a> {
+    scheme: &'a str,
+    rest: &'a str,
+}
+
+impl<'a> ResourceUri<'a> {
+    /// Parse a URI with a scheme as defined by RFC 3986, a letter followed by letters, digits,
+    /// `+`, `-` or `.`, then a `:` and a non-empty remainder without whitespace
+    pub fn parse(uri: &'a str) -> WalletUtilsResult<Self> {
+        let invalid = || WalletUtilsError::InvalidResourceUri(uri.to_string());
+
+        let (scheme, rest) = uri.split_once(':').ok_or_else(invalid)?;
+
+        let scheme_is_valid = scheme
+            .chars()
+            .next()
+            .is_some_and(|first| first.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|char| char.is_ascii_alphanumeric() || matches!(char, '+' | '-' | '.'));
+
+        if !scheme_is_valid || rest.is_empty() || uri.chars().any(char::is_whitespace) {
+            return Err(invalid());
+        }
+
+        Ok(Self { scheme, rest })
+    }
+
+    /// The scheme, eg. `https`
+    pub fn scheme(&self) -> &'a str {
+        self.scheme
+    }
+
+    /// The part of the URI after the scheme and `:`, eg. `//example.com/terms`
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
 }
 
 #[cfg(test)]
@@ -589,3 +813,252 @@ mod signin_input_sanity_checks {
         assert!(signin_input.set_not_before_time_seconds(4).is_ok());
     }
 }
+
+#[cfg(test)]
+mod signin_message_hash_checks {
+    use super::*;
+
+    #[test]
+    fn message_hash() {
+        let mut signin_input = SigninInput::new();
+        signin_input
+            .set_domain("example.com")
+            .set_statement("Sign in to example.com")
+            .set_chain_id(Cluster::DevNet)
+            .set_custom_nonce("0123456789abcdef")
+            .unwrap()
+            .set_address("11111111111111111111111111111111")
+            .unwrap();
+
+        assert_eq!(signin_input.message_hash(), signin_input.message_hash());
+        assert_eq!(
+            signin_input.message_hash(),
+            signin_input.clone().message_hash()
+        );
+        assert_eq!(
+            signin_input,
+            SigninInput::parser(&signin_input.to_message()).unwrap()
+        );
+
+        let previous_hash = signin_input.message_hash();
+        signin_input.set_statement("Sign in to example.org");
+        assert_ne!(previous_hash, signin_input.message_hash());
+    }
+}
+
+#[cfg(test)]
+mod signin_diff_checks {
+    use super::*;
+
+    #[test]
+    fn diff_altered_statement() {
+        let mut signin_input = SigninInput::new();
+        signin_input
+            .set_domain("example.com")
+            .set_statement("Sign in to example.com")
+            .set_uri("https://example.com")
+            .set_chain_id(Cluster::MainNet)
+            .set_custom_nonce("0123456789abcdef")
+            .unwrap()
+            .set_address("11111111111111111111111111111111")
+            .unwrap();
+
+        let message = signin_input.to_message();
+        assert!(signin_input.diff(&message).unwrap().is_empty());
+        assert!(signin_input.check_eq(&message).is_ok());
+
+        let altered = message.replace("Sign in to example.com", "Sign in to evil.com");
+        assert_eq!(
+            vec![SigninField::Statement],
+            signin_input.diff(&altered).unwrap()
+        );
+        assert_eq!(
+            Err(WalletUtilsError::MessageResponseMismatch),
+            signin_input.check_eq(&altered)
+        );
+    }
+}
+
+#[cfg(test)]
+mod statement_label_checks {
+    use super::*;
+
+    #[test]
+    fn statement_mentioning_labels() {
+        let mut signin_input = SigninInput::new();
+        signin_input
+            .set_domain("example.com")
+            .set_statement("Visit our URI policy: Nonce rules and Version: 2 apply")
+            .set_uri("https://example.com/login")
+            .set_version("1")
+            .set_custom_nonce("0123456789abcdef")
+            .unwrap()
+            .add_resource("https://example.com/URI: policy")
+            .set_address("11111111111111111111111111111111")
+            .unwrap();
+
+        let parsed = SigninInput::parser(&signin_input.to_message()).unwrap();
+        assert_eq!(parsed.uri().unwrap(), "https://example.com/login");
+        assert_eq!(parsed.version().unwrap(), "1");
+        assert_eq!(parsed.nonce().unwrap(), "0123456789abcdef");
+        assert_eq!(parsed, signin_input);
+    }
+}
+
+#[cfg(test)]
+mod resources_format_checks {
+    use super::*;
+
+    fn signin_input() -> SigninInput {
+        let mut signin_input = SigninInput::new();
+        signin_input
+            .set_domain("example.com")
+            .set_statement("- not a resource")
+            .set_uri("https://example.com/login")
+            .set_custom_nonce("0123456789abcdef")
+            .unwrap()
+            .set_address("11111111111111111111111111111111")
+            .unwrap();
+
+        signin_input
+    }
+
+    #[test]
+    fn two_resources() {
+        let mut signin_input = signin_input();
+        signin_input.add_resources(&["https://my-site.example/terms", "ipfs://bafy-foo"]);
+
+        let message = signin_input.to_message();
+        assert!(
+            message.ends_with("\nResources:\n- https://my-site.example/terms\n- ipfs://bafy-foo")
+        );
+
+        let parsed = SigninInput::parser(&message).unwrap();
+        assert_eq!(
+            parsed.resources(),
+            &["https://my-site.example/terms", "ipfs://bafy-foo"]
+        );
+        assert_eq!(parsed, signin_input);
+    }
+
+    #[test]
+    fn no_resources() {
+        let signin_input = signin_input();
+
+        let message = signin_input.to_message();
+        assert!(!message.contains("Resources:"));
+
+        let parsed = SigninInput::parser(&message).unwrap();
+        assert!(parsed.resources().is_empty());
+        assert_eq!(parsed, signin_input);
+    }
+}
+
+#[cfg(test)]
+mod nonce_checks {
+    use super::*;
+
+    #[test]
+    fn hex_nonce() {
+        let mut signin_input = SigninInput::new();
+        signin_input.set_nonce();
+
+        let nonce = signin_input.nonce().unwrap().clone();
+        assert_eq!(nonce.len(), 64);
+        assert!(nonce
+            .chars()
+            .all(|char| char.is_ascii_digit() || ('a'..='f').contains(&char)));
+
+        signin_input.set_nonce();
+        assert_ne!(signin_input.nonce().unwrap(), &nonce);
+    }
+}
+
+#[cfg(test)]
+mod siws_timestamp_checks {
+    use super::*;
+
+    #[test]
+    fn identical_across_code_paths() {
+        // An instant with sub-millisecond precision
+        let instant = SystemTime::UNIX_EPOCH + Duration::from_nanos(1_735_734_600_123_456_789);
+        let timestamp = WalletCommonUtils::siws_timestamp(instant);
+        assert_eq!(timestamp, "2025-01-01T12:30:00.123Z");
+        assert_eq!(timestamp, WalletCommonUtils::to_iso860(instant).to_string());
+
+        let mut signin_input = SigninInput::new();
+        signin_input
+            .set_domain("example.com")
+            .set_issued_at(instant);
+        assert_eq!(signin_input.issued_at_iso8601(), Some(timestamp.clone()));
+
+        let message = signin_input.to_message();
+        assert!(message.contains(&format!("Issued At: {timestamp}")));
+
+        let parsed = SigninInput::parser(&message).unwrap();
+        assert_eq!(parsed.issued_at(), signin_input.issued_at());
+        assert_eq!(parsed.issued_at_iso8601(), Some(timestamp));
+    }
+}
+
+#[cfg(test)]
+mod address_checks {
+    use super::*;
+
+    #[test]
+    fn too_short_address() {
+        let mut signin_input = SigninInput::new();
+
+        // `2g` decodes to a single byte
+        assert_eq!(
+            Some(WalletUtilsError::InvalidEd25519PublicKeyLen(1)),
+            signin_input.set_address("2g").err()
+        );
+        assert_eq!(
+            Some(WalletUtilsError::InvalidBase58Address),
+            signin_input.set_address("0OIl").err()
+        );
+        assert!(signin_input.address().is_none());
+
+        assert!(signin_input
+            .set_address("4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi")
+            .is_ok());
+    }
+}
+
+#[cfg(test)]
+mod resource_uri_checks {
+    use super::*;
+
+    #[test]
+    fn valid_and_invalid_resources() {
+        let mut signin_input = SigninInput::new();
+
+        signin_input
+            .add_resource_uri("https://example.com/terms")
+            .unwrap();
+        assert_eq!(
+            Some(WalletUtilsError::InvalidResourceUri("terms".to_string())),
+            signin_input.add_resource_uri("terms").err()
+        );
+        assert!(signin_input
+            .add_resource_uri("1http://example.com")
+            .is_err());
+        assert!(signin_input.add_resource_uri("https:").is_err());
+        assert!(signin_input
+            .add_resource_uri("https://example .com")
+            .is_err());
+
+        // The lenient method still accepts any string
+        signin_input.add_resource("terms");
+        assert_eq!(
+            signin_input.resources(),
+            ["https://example.com/terms", "terms"]
+        );
+
+        let uris = signin_input.resource_uris();
+        assert_eq!(uris.len(), 1);
+        assert_eq!(uris[0].scheme(), "https");
+        assert_eq!(uris[0].rest(), "//example.com/terms");
+    }
+}
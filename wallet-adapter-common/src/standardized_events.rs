@@ -13,6 +13,8 @@ pub const STANDARD_DISCONNECT_IDENTIFIER: &str = "standard:disconnect";
 pub const SOLANA_SIGN_AND_SEND_TRANSACTION_IDENTIFIER: &str = "solana:signAndSendTransaction";
 /// Identifier for `solana:signTransaction`
 pub const SOLANA_SIGN_TRANSACTION_IDENTIFIER: &str = "solana:signTransaction";
+/// Identifier for `solana:signAllTransactions`
+pub const SOLANA_SIGN_ALL_TRANSACTIONS_IDENTIFIER: &str = "solana:signAllTransactions";
 /// Identifier for `solana:signIn`
 pub const SOLANA_SIGN_IN_IDENTIFIER: &str = "solana:signIn";
 /// Identifier for `solana:signMessage`
@@ -1,4 +1,7 @@
-use std::{borrow::Cow, time::SystemTime};
+use std::{
+    borrow::Cow,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 
@@ -21,15 +24,22 @@ impl WalletCommonUtils {
 
     /// Generate a 32 byte array from random bytes
     pub fn rand_32bytes() -> [u8; 32] {
+        let mut buffer = [0u8; 32];
+
+        Self::fill_rand_bytes(&mut buffer);
+
+        buffer
+    }
+
+    /// Fill the `buffer` with random bytes. This allows the caller to own the buffer,
+    /// for example a [zeroize::Zeroizing] buffer that is cleared when dropped
+    pub fn fill_rand_bytes(buffer: &mut [u8]) {
         use rand_chacha::ChaCha12Rng;
         use rand_core::{RngCore, SeedableRng};
 
         let mut rng = ChaCha12Rng::from_os_rng();
-        let mut buffer = [0u8; 32];
-
-        rng.fill_bytes(&mut buffer);
 
-        buffer
+        rng.fill_bytes(buffer);
     }
 
     /// Parse a [PublicKey](VerifyingKey) from an array of 32 bytes
@@ -94,6 +104,18 @@ impl WalletCommonUtils {
         bs58::encode(signature.to_bytes()).into_string()
     }
 
+    /// Decode a [Signature] from a Base58 string, for example a transaction signature
+    /// copied from an explorer, the inverse of [Self::base58_signature].
+    /// The decoded bytes must be 64 bytes in length
+    pub fn signature_from_base58(base58: &str) -> WalletUtilsResult<Signature> {
+        bs58::decode(base58)
+            .into_vec()
+            .ok()
+            .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+            .map(|bytes| Signature::from_bytes(&bytes))
+            .ok_or(WalletUtilsError::InvalidBase58Signature(base58.to_string()))
+    }
+
     /// Get the shortened string of the `Base58 string` .
     /// It displays the first 4 characters and the last for characters
     /// separated by ellipsis eg `FXdl...RGd4` .
@@ -145,8 +167,55 @@ impl WalletCommonUtils {
     }
 
     /// Converts [SystemTime] to ISO 8601 datetime string as required by
-    /// Sign In With Solana standard
+    /// Sign In With Solana standard, see [Self::siws_timestamp]
     pub fn to_iso860(system_time: SystemTime) -> humantime::Rfc3339Timestamp {
         humantime::format_rfc3339_millis(system_time)
     }
+
+    /// Format [SystemTime] the way every timestamp of a Sign In With Solana (SIWS) message
+    /// is formatted, as an RFC 3339 (ISO 8601) UTC datetime with millisecond precision,
+    /// `YYYY-MM-DDTHH:MM:SS.sssZ`, for example `2025-01-01T12:30:00.123Z`.
+    /// Sub-millisecond precision is truncated
+    pub fn siws_timestamp(time: SystemTime) -> String {
+        Self::to_iso860(time).to_string()
+    }
+
+    /// Truncate [SystemTime] to the millisecond precision of [Self::siws_timestamp]
+    /// so that a time compares equal to the time parsed back from a SIWS message
+    pub(crate) fn siws_time(time: SystemTime) -> SystemTime {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(since_epoch) => UNIX_EPOCH + Duration::from_millis(since_epoch.as_millis() as u64),
+            Err(_) => time,
+        }
+    }
+}
+
+#[cfg(test)]
+mod base58_signature_checks {
+    use super::*;
+
+    #[test]
+    fn explorer_signature() {
+        let base58 =
+            "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW";
+
+        let signature = WalletCommonUtils::signature_from_base58(base58).unwrap();
+        let bytes = signature.to_bytes();
+        assert_eq!(bytes[..4], [224, 105, 239, 47]);
+        assert_eq!(bytes[60..], [59, 71, 156, 15]);
+        assert_eq!(WalletCommonUtils::base58_signature(signature), base58);
+
+        assert_eq!(
+            WalletCommonUtils::base58_signature(Signature::from_bytes(&[2u8; 64])),
+            "3L3RY5sT8K4kyEnqhizwaqxLEbcYvpGrGPNEYRwtbCSUtL6YL86jdrvCbohnP5q8VxQ3qzGmt3W3iQJW97rD7m3"
+        );
+
+        assert_eq!(
+            WalletCommonUtils::signature_from_base58("4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi"),
+            Err(WalletUtilsError::InvalidBase58Signature(
+                "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi".to_string()
+            ))
+        );
+        assert!(WalletCommonUtils::signature_from_base58("0OIl").is_err());
+    }
 }
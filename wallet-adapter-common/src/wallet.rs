@@ -1,8 +1,10 @@
 use std::borrow::Cow;
 
 use crate::{
-    chains::ChainSupport, clusters::Cluster, feature_support::FeatureSupport, SemverVersion,
-    WalletAccountData,
+    chains::ChainSupport,
+    clusters::Cluster,
+    feature_support::{FeatureSupport, WalletFeature},
+    SemverVersion, WalletAccountData,
 };
 
 /// Wallet information without any browser function calls for `wallet-adapter` standard operations
@@ -11,8 +13,10 @@ pub struct WalletData {
     name: Cow<'static, str>,
     version: SemverVersion,
     icon: Option<Cow<'static, str>>,
+    website: Option<Cow<'static, str>>,
     accounts: Vec<WalletAccountData>,
     chains: Vec<Cluster>,
+    current_cluster: Option<Cluster>,
     // Convenience field, instead of going through the `features` field
     supported_features: FeatureSupport,
     // Convenience field, instead of iteration through the `chains` field
@@ -47,6 +51,20 @@ impl WalletData {
         self
     }
 
+    /// Set the URL of the homepage of the wallet
+    pub fn set_website(mut self, website: Option<impl ToString>) -> Self {
+        website.map(|value| self.website.replace(Cow::Owned(value.to_string())));
+
+        self
+    }
+
+    /// Set the [Cluster] the wallet is currently pointed at
+    pub fn set_current_cluster(mut self, cluster: Option<Cluster>) -> Self {
+        self.current_cluster = cluster;
+
+        self
+    }
+
     /// Add a [Wallet account](WalletAccountData) data
     pub fn add_account(mut self, account: WalletAccountData) -> Self {
         self.accounts.push(account);
@@ -133,6 +151,11 @@ impl WalletData {
         self.supported_chains.localnet
     }
 
+    /// Check whether the wallet supports a certain [WalletFeature]
+    pub fn supports(&self, feature: WalletFeature) -> bool {
+        self.supported_features.supports(feature)
+    }
+
     /// Check whether the wallet supports `standard:connect` feature
     pub fn standard_connect(&self) -> bool {
         self.supported_features.connect
@@ -168,11 +191,26 @@ impl WalletData {
         self.supported_features.sign_tx
     }
 
+    /// Check whether the wallet supports `solana:signAllTransactions` feature
+    pub fn solana_sign_all_transactions(&self) -> bool {
+        self.supported_features.sign_all_tx
+    }
+
     /// Get the optional Wallet Icon
     pub fn icon(&self) -> Option<&Cow<'static, str>> {
         self.icon.as_ref()
     }
 
+    /// Get the optional URL of the homepage of the wallet
+    pub fn website(&self) -> Option<&str> {
+        self.website.as_deref()
+    }
+
+    /// Get the [Cluster] the wallet is currently pointed at, if it advertises one
+    pub fn current_cluster(&self) -> Option<Cluster> {
+        self.current_cluster
+    }
+
     /// Get the name of the wallet
     pub fn name(&self) -> &str {
         &self.name
@@ -196,8 +234,10 @@ impl core::fmt::Debug for WalletData {
             .field("name", &self.name)
             .field("version", &self.version)
             .field("icon", &self.icon)
+            .field("website", &self.website)
             .field("accounts", &self.accounts)
             .field("chains", &chains)
+            .field("current_cluster", &self.current_cluster)
             .finish()
     }
 }
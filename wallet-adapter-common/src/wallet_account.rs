@@ -151,4 +151,9 @@ impl WalletAccountData {
     pub fn solana_sign_transaction(&self) -> bool {
         self.supported_features.sign_tx
     }
+
+    /// Checks if `solana:signAllTransactions` is supported
+    pub fn solana_sign_all_transactions(&self) -> bool {
+        self.supported_features.sign_all_tx
+    }
 }